@@ -0,0 +1,41 @@
+// Demonstrates propagation instrumentation: only the entry point carries the
+// attribute, internal calls are traced automatically.
+//
+// Run with: cargo run --example propagation_demo
+
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace(propagate)]
+fn process_order(order_id: u32, quantity: u32) -> u32 {
+    let unit_price = lookup_price(order_id);
+    let total = calculate_total(unit_price, quantity);
+    apply_discount(total)
+}
+
+fn lookup_price(order_id: u32) -> u32 {
+    100 + order_id % 50
+}
+
+fn calculate_total(unit_price: u32, quantity: u32) -> u32 {
+    unit_price * quantity
+}
+
+fn apply_discount(total: u32) -> u32 {
+    if total > 500 {
+        total - total / 10
+    } else {
+        total
+    }
+}
+
+fn main() {
+    let total = process_order(42, 7);
+    println!("order total: {}", total);
+
+    let output = std::path::Path::new("propagation_demo_trace.json");
+    if let Err(e) = trace_runtime::tracer::interface::finalize(output) {
+        eprintln!("failed to write trace: {}", e);
+    } else {
+        println!("trace written to {}", output.display());
+    }
+}
@@ -0,0 +1,247 @@
+use anyhow::{bail, Context, Result, ensure};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use trace_common::schema::{CallData, TraceFile};
+
+/// Aggregated type observations for a single traced function
+#[derive(Debug, Default)]
+struct TypeObservations {
+    /// Number of recorded calls
+    call_count: usize,
+    /// Per input key: observed JSON type -> occurrence count
+    input_key_types: BTreeMap<String, BTreeMap<String, usize>>,
+    /// Observed output JSON type -> occurrence count
+    output_types: BTreeMap<String, usize>,
+    /// Number of calls where the output was null
+    output_nulls: usize,
+}
+
+/// What the per-function statistics table is ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Number of recorded calls
+    Calls,
+    /// Total recorded wall time
+    Time,
+    /// Deepest recorded call tree
+    Depth,
+    /// Total serialized record size
+    Size,
+}
+
+impl SortKey {
+    /// Parse the `--sort` flag value
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "calls" => Ok(SortKey::Calls),
+            "time" => Ok(SortKey::Time),
+            "depth" => Ok(SortKey::Depth),
+            "size" => Ok(SortKey::Size),
+            other => bail!("Unknown sort key: {} (expected calls, time, depth or size)", other),
+        }
+    }
+}
+
+/// Analyze a captured trace file
+///
+/// By default prints per-function statistics — call counts, durations,
+/// stack depths, payload sizes. `--type-report` instead infers runtime
+/// schemas from the observed inputs/outputs of each traced function.
+pub fn run(trace_file: &Path, type_report: bool, top: usize, sort: SortKey) -> Result<()> {
+    ensure!(trace_file.exists(), "Trace file does not exist: {}", trace_file.display());
+
+    let content = fs::read_to_string(trace_file)
+        .with_context(|| format!("Failed to read trace file: {}", trace_file.display()))?;
+
+    if type_report {
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse trace file as JSON array: {}", trace_file.display()))?;
+        print!("{}", generate_type_report(&entries));
+        return Ok(());
+    }
+
+    let document: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse trace file as JSON: {}", trace_file.display()))?;
+    let records = TraceFile::from_value(document)
+        .with_context(|| "Failed to load trace data")?
+        .records;
+
+    print!("{}", generate_stats_report(&records, top, sort));
+    Ok(())
+}
+
+/// Per-function aggregates behind the statistics table
+#[derive(Debug, Default)]
+struct FunctionStats {
+    call_count: usize,
+    /// Sum of recorded durations and how many calls carried one; older
+    /// files have no durations at all
+    total_duration_ns: u64,
+    timed_calls: usize,
+    max_depth: usize,
+    total_bytes: usize,
+}
+
+impl FunctionStats {
+    fn sort_value(&self, sort: SortKey) -> u128 {
+        match sort {
+            SortKey::Calls => self.call_count as u128,
+            SortKey::Time => u128::from(self.total_duration_ns),
+            SortKey::Depth => self.max_depth as u128,
+            SortKey::Size => self.total_bytes as u128,
+        }
+    }
+}
+
+/// Build the per-function statistics table for parsed records
+pub fn generate_stats_report(records: &[CallData], top: usize, sort: SortKey) -> String {
+    let mut stats: BTreeMap<String, FunctionStats> = BTreeMap::new();
+
+    for record in records {
+        let entry = stats.entry(record.root_node.name.clone()).or_default();
+        entry.call_count += 1;
+        if let Some(duration_ns) = record.duration_ns {
+            entry.total_duration_ns += duration_ns;
+            entry.timed_calls += 1;
+        }
+        entry.max_depth = entry.max_depth.max(record.root_node.depth());
+        entry.total_bytes += serde_json::to_value(record)
+            .map(|value| trace_common::size_of_value(&value))
+            .unwrap_or(0);
+    }
+
+    if stats.is_empty() {
+        return "No traced calls found in trace file.\n".to_string();
+    }
+
+    let mut rows: Vec<(String, FunctionStats)> = stats.into_iter().collect();
+    // BTreeMap ordering makes name the stable tie-breaker
+    rows.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.sort_value(sort)));
+
+    let total_functions = rows.len();
+    rows.truncate(top);
+
+    let mut report = format!(
+        "Trace analysis: {} record(s), {} function(s)\n\n",
+        records.len(),
+        total_functions
+    );
+    report.push_str(&format!(
+        "{:<30} {:>6} {:>12} {:>10} {:>6} {:>10}\n",
+        "function", "calls", "total ms", "avg ms", "depth", "bytes"
+    ));
+    for (name, entry) in &rows {
+        let (total_ms, avg_ms) = if entry.timed_calls > 0 {
+            let total = entry.total_duration_ns as f64 / 1_000_000.0;
+            (format!("{:.3}", total), format!("{:.3}", total / entry.timed_calls as f64))
+        } else {
+            ("-".to_string(), "-".to_string())
+        };
+        report.push_str(&format!(
+            "{:<30} {:>6} {:>12} {:>10} {:>6} {:>10}\n",
+            name, entry.call_count, total_ms, avg_ms, entry.max_depth, entry.total_bytes
+        ));
+    }
+    if total_functions > rows.len() {
+        report.push_str(&format!("... {} more function(s) omitted\n", total_functions - rows.len()));
+    }
+
+    report
+}
+
+/// Build the type report for a parsed list of trace entries
+///
+/// For each traced function this reports the JSON type observed for every
+/// input key and for the output, plus how often the output was null —
+/// effectively the function's runtime schema.
+pub fn generate_type_report(entries: &[serde_json::Value]) -> String {
+    let mut observations: BTreeMap<String, TypeObservations> = BTreeMap::new();
+
+    for entry in entries {
+        let Some(function_name) = entry
+            .get("root_node")
+            .and_then(|n| n.get("name"))
+            .and_then(|n| n.as_str())
+        else {
+            continue;
+        };
+
+        let obs = observations.entry(function_name.to_string()).or_default();
+        obs.call_count += 1;
+
+        if let Some(inputs) = entry.get("inputs").and_then(|i| i.as_object()) {
+            for (key, value) in inputs {
+                *obs.input_key_types
+                    .entry(key.clone())
+                    .or_default()
+                    .entry(json_type_name(value).to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        if let Some(output) = entry.get("output") {
+            *obs.output_types
+                .entry(json_type_name(output).to_string())
+                .or_insert(0) += 1;
+            if output.is_null() {
+                obs.output_nulls += 1;
+            }
+        }
+    }
+
+    if observations.is_empty() {
+        return "No traced calls found in trace file.\n".to_string();
+    }
+
+    let mut report = String::from("Runtime type report:\n\n");
+    for (function, obs) in &observations {
+        report.push_str(&format!("{} ({} call(s))\n", function, obs.call_count));
+
+        if obs.input_key_types.is_empty() {
+            report.push_str("  inputs: (none recorded)\n");
+        } else {
+            report.push_str("  inputs:\n");
+            for (key, types) in &obs.input_key_types {
+                report.push_str(&format!("    {}: {}\n", key, format_type_counts(types)));
+            }
+        }
+
+        let null_rate = if obs.call_count > 0 {
+            obs.output_nulls as f64 / obs.call_count as f64 * 100.0
+        } else {
+            0.0
+        };
+        report.push_str(&format!(
+            "  output: {} (null rate: {:.0}%)\n\n",
+            format_type_counts(&obs.output_types),
+            null_rate
+        ));
+    }
+
+    report
+}
+
+/// Human-readable JSON type name for a value
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "bool",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+/// Format a type occurrence map as "type (n), type (n)"
+fn format_type_counts(types: &BTreeMap<String, usize>) -> String {
+    if types.is_empty() {
+        return "(none recorded)".to_string();
+    }
+    types
+        .iter()
+        .map(|(ty, count)| format!("{} ({})", ty, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -20,7 +20,7 @@ pub fn run(project_dir: &Path) -> Result<()> {
         .context("Failed to remove dependencies")?;
     
     // Only show summary if dependencies were actually removed
-    if stats.added.len() > 0 {
+    if !stats.added.is_empty() {
         display_removal_summary(&stats);
     }
     
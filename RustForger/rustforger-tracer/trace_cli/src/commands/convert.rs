@@ -0,0 +1,216 @@
+use anyhow::{Context, Result, bail, ensure};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use trace_common::schema::{CallData, CallNode, TraceFile};
+
+/// Convert a recorded trace file into a standard visualization format
+///
+/// Supported targets:
+/// - `folded`: inferno-compatible folded stacks (one `a;b;c count` line
+///   per distinct stack)
+/// - `chrome`: Chrome Trace Event JSON, loadable in `chrome://tracing`
+///   and Perfetto
+/// - `otlp`: an OTLP/JSON `resourceSpans` payload for OpenTelemetry
+///   tooling
+///
+/// This is the inverse of `import`, which brings external traces into the
+/// rustforger format.
+pub fn run(trace_file: &Path, to: &str, output: &Path) -> Result<()> {
+    ensure!(trace_file.exists(), "Trace file does not exist: {}", trace_file.display());
+
+    let content = fs::read_to_string(trace_file)
+        .with_context(|| format!("Failed to read trace file: {}", trace_file.display()))?;
+    let document: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse trace file as JSON: {}", trace_file.display()))?;
+    let records = TraceFile::from_value(document)
+        .with_context(|| "Failed to load trace data")?
+        .records;
+
+    let converted = match to {
+        "folded" => to_folded(&records),
+        "chrome" => serde_json::to_string_pretty(&to_chrome(&records))?,
+        "otlp" => serde_json::to_string_pretty(&to_otlp(&records))?,
+        other => bail!("Unsupported target format: {} (expected 'folded', 'chrome' or 'otlp')", other),
+    };
+
+    fs::write(output, converted)
+        .with_context(|| format!("Failed to write converted trace to: {}", output.display()))?;
+
+    println!("converted {} call(s) to {} format at {}", records.len(), to, output.display());
+    Ok(())
+}
+
+/// Render records as folded stacks for inferno/flamegraph tooling
+///
+/// Per-node wall time is not recorded, so each call contributes one sample
+/// to its stack; the resulting flame graph shows call counts, not time.
+pub fn to_folded(records: &[CallData]) -> String {
+    let mut samples: BTreeMap<String, u64> = BTreeMap::new();
+    for record in records {
+        fold_into(&record.root_node, String::new(), &mut samples);
+    }
+    let mut out = String::new();
+    for (stack, count) in samples {
+        out.push_str(&format!("{} {}\n", stack, count));
+    }
+    out
+}
+
+fn fold_into(node: &CallNode, prefix: String, samples: &mut BTreeMap<String, u64>) {
+    let stack = if prefix.is_empty() {
+        node.name.clone()
+    } else {
+        format!("{};{}", prefix, node.name)
+    };
+    *samples.entry(stack.clone()).or_insert(0) += 1;
+    for child in &node.children {
+        fold_into(child, stack.clone(), samples);
+    }
+}
+
+/// One call laid out on a timeline, shared by the chrome and otlp targets
+struct Interval<'a> {
+    node: &'a CallNode,
+    /// Index of the parent interval within the record, `None` for the root
+    parent: Option<usize>,
+    start_ns: u64,
+    end_ns: u64,
+}
+
+/// Lay a record's call tree out on a timeline.
+///
+/// Only the root's start time and duration are recorded; children split
+/// their parent's interval evenly, which preserves nesting and relative
+/// position even though the per-call times are synthetic.
+fn layout(record: &CallData) -> Vec<Interval<'_>> {
+    let start_ns = chrono::DateTime::parse_from_rfc3339(&record.timestamp_utc)
+        .map(|timestamp| timestamp.timestamp_nanos_opt().unwrap_or(0).max(0) as u64)
+        .unwrap_or(0);
+    // Without a measured duration every call gets a nominal microsecond
+    let duration_ns = record
+        .duration_ns
+        .unwrap_or(record.root_node.count() as u64 * 1_000);
+
+    let mut intervals = Vec::new();
+    place(&record.root_node, None, start_ns, duration_ns.max(1), &mut intervals);
+    intervals
+}
+
+fn place<'a>(
+    node: &'a CallNode,
+    parent: Option<usize>,
+    start_ns: u64,
+    duration_ns: u64,
+    intervals: &mut Vec<Interval<'a>>,
+) {
+    let index = intervals.len();
+    intervals.push(Interval {
+        node,
+        parent,
+        start_ns,
+        end_ns: start_ns + duration_ns,
+    });
+    if node.children.is_empty() {
+        return;
+    }
+    let child_duration = (duration_ns / node.children.len() as u64).max(1);
+    for (child_index, child) in node.children.iter().enumerate() {
+        place(
+            child,
+            Some(index),
+            start_ns + child_index as u64 * child_duration,
+            child_duration,
+            intervals,
+        );
+    }
+}
+
+/// Render records as Chrome Trace Event JSON
+pub fn to_chrome(records: &[CallData]) -> Value {
+    let mut thread_ids: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for record in records {
+        let tid = match thread_ids.iter().position(|id| *id == record.thread_id) {
+            Some(position) => position,
+            None => {
+                thread_ids.push(&record.thread_id);
+                thread_ids.len() - 1
+            }
+        };
+        for interval in layout(record) {
+            events.push(json!({
+                "ph": "X",
+                "name": interval.node.name,
+                "cat": "function",
+                "pid": 1,
+                "tid": tid,
+                "ts": interval.start_ns / 1_000,
+                "dur": (interval.end_ns - interval.start_ns) / 1_000,
+                "args": {"file": interval.node.file, "line": interval.node.line},
+            }));
+        }
+    }
+
+    json!({"traceEvents": events, "displayTimeUnit": "ms"})
+}
+
+/// Render records as an OTLP/JSON span export
+///
+/// Recorded correlation IDs are kept; records and calls from files written
+/// before correlation IDs existed get freshly generated ones.
+pub fn to_otlp(records: &[CallData]) -> Value {
+    let mut spans = Vec::new();
+
+    for record in records {
+        let trace_id = record
+            .trace_id
+            .clone()
+            .unwrap_or_else(trace_common::schema::generate_trace_id);
+        let intervals = layout(record);
+        let span_ids: Vec<String> = intervals
+            .iter()
+            .map(|interval| {
+                interval
+                    .node
+                    .span_id
+                    .clone()
+                    .unwrap_or_else(trace_common::schema::generate_span_id)
+            })
+            .collect();
+
+        for (index, interval) in intervals.iter().enumerate() {
+            let mut span = json!({
+                "traceId": trace_id,
+                "spanId": span_ids[index],
+                "name": interval.node.name,
+                "kind": 1,
+                "startTimeUnixNano": interval.start_ns.to_string(),
+                "endTimeUnixNano": interval.end_ns.to_string(),
+                "attributes": [
+                    {"key": "code.filepath", "value": {"stringValue": interval.node.file}},
+                    {"key": "code.lineno", "value": {"intValue": interval.node.line.to_string()}},
+                    {"key": "thread.id", "value": {"stringValue": record.thread_id}},
+                ],
+            });
+            if let Some(parent) = interval.parent {
+                span["parentSpanId"] = Value::String(span_ids[parent].clone());
+            }
+            spans.push(span);
+        }
+    }
+
+    json!({
+        "resourceSpans": [{
+            "resource": {"attributes": [
+                {"key": "service.name", "value": {"stringValue": "rustforger-trace"}},
+            ]},
+            "scopeSpans": [{
+                "scope": {"name": "trace_cli"},
+                "spans": spans,
+            }],
+        }],
+    })
+}
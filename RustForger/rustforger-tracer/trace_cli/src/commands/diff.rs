@@ -0,0 +1,161 @@
+use anyhow::{Context, Result, ensure};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use trace_common::diff::{diff, ElementDiff, ValueDiff};
+use trace_common::schema::{CallData, TraceFile};
+
+/// Compare two trace files call by call
+///
+/// Calls are aligned by root function name and occurrence order — the
+/// k-th `parse` call of the old file is compared against the k-th `parse`
+/// call of the new one. For each aligned pair the inputs, output and call
+/// tree shape are compared; calls present on only one side are reported
+/// as added or removed. This is the core workflow for hunting behavior
+/// regressions between two builds.
+pub fn run(old_file: &Path, new_file: &Path) -> Result<()> {
+    let old = load(old_file)?;
+    let new = load(new_file)?;
+
+    print!("{}", generate_diff_report(&old, &new));
+    Ok(())
+}
+
+fn load(path: &Path) -> Result<Vec<CallData>> {
+    ensure!(path.exists(), "Trace file does not exist: {}", path.display());
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read trace file: {}", path.display()))?;
+    let document: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse trace file as JSON: {}", path.display()))?;
+    Ok(TraceFile::from_value(document)
+        .with_context(|| format!("Failed to load trace data from: {}", path.display()))?
+        .records)
+}
+
+/// Build the textual diff report for two sets of parsed records
+pub fn generate_diff_report(old: &[CallData], new: &[CallData]) -> String {
+    let old_groups = group_by_function(old);
+    let new_groups = group_by_function(new);
+
+    let mut functions: Vec<&String> = old_groups.keys().chain(new_groups.keys()).collect();
+    functions.sort();
+    functions.dedup();
+
+    let mut report = format!(
+        "Trace diff: {} call(s) in old, {} in new\n\n",
+        old.len(),
+        new.len()
+    );
+    let (mut changed, mut added, mut removed, mut unchanged) = (0, 0, 0, 0);
+
+    for function in functions {
+        let empty = Vec::new();
+        let old_calls = old_groups.get(function).unwrap_or(&empty);
+        let new_calls = new_groups.get(function).unwrap_or(&empty);
+
+        for occurrence in 0..old_calls.len().max(new_calls.len()) {
+            match (old_calls.get(occurrence), new_calls.get(occurrence)) {
+                (Some(old_call), Some(new_call)) => {
+                    let lines = compare_calls(old_call, new_call);
+                    if lines.is_empty() {
+                        unchanged += 1;
+                    } else {
+                        changed += 1;
+                        report.push_str(&format!("~ {} #{}\n", function, occurrence));
+                        for line in lines {
+                            report.push_str(&format!("    {}\n", line));
+                        }
+                    }
+                }
+                (Some(_), None) => {
+                    removed += 1;
+                    report.push_str(&format!("- {} #{} (removed)\n", function, occurrence));
+                }
+                (None, Some(_)) => {
+                    added += 1;
+                    report.push_str(&format!("+ {} #{} (added)\n", function, occurrence));
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+    }
+
+    if changed + added + removed == 0 {
+        report.push_str("No differences found.\n");
+    } else {
+        report.push('\n');
+    }
+    report.push_str(&format!(
+        "{} changed, {} added, {} removed, {} unchanged\n",
+        changed, added, removed, unchanged
+    ));
+    report
+}
+
+/// Records in file order, grouped by root function name
+fn group_by_function(records: &[CallData]) -> BTreeMap<String, Vec<&CallData>> {
+    let mut groups: BTreeMap<String, Vec<&CallData>> = BTreeMap::new();
+    for record in records {
+        groups.entry(record.root_node.name.clone()).or_default().push(record);
+    }
+    groups
+}
+
+/// Describe every difference between two aligned calls, one line each;
+/// empty when the calls match
+fn compare_calls(old: &CallData, new: &CallData) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    describe(&diff(&old.inputs, &new.inputs), "inputs", &mut lines);
+    describe(&diff(&old.output, &new.output), "output", &mut lines);
+
+    let old_shape: Vec<&str> = old.root_node.flatten().iter().map(|node| node.name.as_str()).collect();
+    let new_shape: Vec<&str> = new.root_node.flatten().iter().map(|node| node.name.as_str()).collect();
+    if old_shape != new_shape {
+        let divergence = old_shape
+            .iter()
+            .zip(&new_shape)
+            .position(|(old_name, new_name)| old_name != new_name);
+        let detail = match divergence {
+            Some(position) => format!(
+                "first divergence: {} -> {}",
+                old_shape[position], new_shape[position]
+            ),
+            None if new_shape.len() > old_shape.len() => {
+                format!("new calls from: {}", new_shape[old_shape.len()])
+            }
+            None => format!("calls gone from: {}", old_shape[new_shape.len()]),
+        };
+        lines.push(format!(
+            "tree: {} -> {} call(s); {}",
+            old_shape.len(),
+            new_shape.len(),
+            detail
+        ));
+    }
+
+    lines
+}
+
+/// Flatten a structured value diff into `path: before -> after` lines
+fn describe(value_diff: &ValueDiff, path: &str, lines: &mut Vec<String>) {
+    match value_diff {
+        ValueDiff::Unchanged => {}
+        ValueDiff::Changed { before, after } => {
+            lines.push(format!("{}: {} -> {}", path, before, after));
+        }
+        ValueDiff::Added { value } => lines.push(format!("{}: added {}", path, value)),
+        ValueDiff::Removed { value } => lines.push(format!("{}: removed {}", path, value)),
+        ValueDiff::Object { entries } => {
+            for (key, entry) in entries {
+                describe(entry, &format!("{}.{}", path, key), lines);
+            }
+        }
+        ValueDiff::Array { entries } => {
+            for ElementDiff { index, diff } in entries {
+                describe(diff, &format!("{}[{}]", path, index), lines);
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::{Context, Result, ensure};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use trace_common::schema::{CallData, CallNode, TraceFile};
+
+/// What the filter command keeps
+#[derive(Debug, Default)]
+pub struct FilterOptions {
+    /// Function name globs (`*` and `?`); a record is kept when any call
+    /// in its tree matches any glob
+    pub functions: Vec<String>,
+    /// Exact thread ID a record must have been captured on
+    pub thread: Option<String>,
+    /// Earliest record timestamp to keep, RFC 3339
+    pub since: Option<String>,
+    /// Latest record timestamp to keep, RFC 3339
+    pub until: Option<String>,
+    /// Deepest call tree level to keep; the root is level 1
+    pub max_depth: Option<usize>,
+}
+
+/// Slice a trace file down to matching records and subtrees
+///
+/// Record-level filters (`--function`, `--thread`, `--since`/`--until`)
+/// decide which records survive; `--max-depth` then prunes each surviving
+/// call tree. The output stays a loadable trace file, header included, so
+/// every other command works on the slice.
+pub fn run(trace_file: &Path, options: &FilterOptions, output: &Path) -> Result<()> {
+    ensure!(trace_file.exists(), "Trace file does not exist: {}", trace_file.display());
+
+    let content = fs::read_to_string(trace_file)
+        .with_context(|| format!("Failed to read trace file: {}", trace_file.display()))?;
+    let document: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse trace file as JSON: {}", trace_file.display()))?;
+
+    // Keep the original header element verbatim for the output file
+    let header = (TraceFile::detect_version(&document) >= 1)
+        .then(|| document.as_array().and_then(|entries| entries.first()).cloned())
+        .flatten();
+    let records = TraceFile::from_value(document)
+        .with_context(|| "Failed to load trace data")?
+        .records;
+
+    let total = records.len();
+    let kept = apply_filters(records, options)?;
+
+    let mut entries: Vec<Value> = Vec::with_capacity(kept.len() + 1);
+    if let Some(header) = header {
+        entries.push(header);
+    }
+    for record in &kept {
+        entries.push(serde_json::to_value(record)?);
+    }
+    fs::write(output, serde_json::to_string_pretty(&entries)?)
+        .with_context(|| format!("Failed to write filtered trace to: {}", output.display()))?;
+
+    println!("kept {} of {} call(s) at {}", kept.len(), total, output.display());
+    Ok(())
+}
+
+/// Apply the filters to parsed records
+pub fn apply_filters(records: Vec<CallData>, options: &FilterOptions) -> Result<Vec<CallData>> {
+    let since = parse_bound(options.since.as_deref(), "--since")?;
+    let until = parse_bound(options.until.as_deref(), "--until")?;
+
+    let mut kept = Vec::new();
+    for mut record in records {
+        if let Some(thread) = &options.thread {
+            if &record.thread_id != thread {
+                continue;
+            }
+        }
+
+        if since.is_some() || until.is_some() {
+            let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(&record.timestamp_utc) else {
+                continue;
+            };
+            if since.is_some_and(|since| timestamp < since)
+                || until.is_some_and(|until| timestamp > until)
+            {
+                continue;
+            }
+        }
+
+        if !options.functions.is_empty()
+            && !record
+                .root_node
+                .flatten()
+                .iter()
+                .any(|node| options.functions.iter().any(|glob| glob_match(glob, &node.name)))
+        {
+            continue;
+        }
+
+        if let Some(max_depth) = options.max_depth {
+            ensure!(max_depth > 0, "--max-depth must be at least 1");
+            prune_depth(&mut record.root_node, max_depth);
+        }
+
+        kept.push(record);
+    }
+    Ok(kept)
+}
+
+fn parse_bound(
+    raw: Option<&str>,
+    flag: &str,
+) -> Result<Option<chrono::DateTime<chrono::FixedOffset>>> {
+    raw.map(|raw| {
+        chrono::DateTime::parse_from_rfc3339(raw)
+            .with_context(|| format!("{} must be an RFC 3339 timestamp, got: {}", flag, raw))
+    })
+    .transpose()
+}
+
+/// Drop every call below `levels` levels; the node itself is level 1
+fn prune_depth(node: &mut CallNode, levels: usize) {
+    if levels <= 1 {
+        node.children.clear();
+        return;
+    }
+    for child in &mut node.children {
+        prune_depth(child, levels - 1);
+    }
+}
+
+/// Match a function name against a glob supporting `*` and `?`
+fn glob_match(glob: &str, name: &str) -> bool {
+    fn matches(glob: &[u8], name: &[u8]) -> bool {
+        match (glob.first(), name.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                matches(&glob[1..], name) || (!name.is_empty() && matches(glob, &name[1..]))
+            }
+            (Some(b'?'), Some(_)) => matches(&glob[1..], &name[1..]),
+            (Some(expected), Some(actual)) if expected == actual => {
+                matches(&glob[1..], &name[1..])
+            }
+            _ => false,
+        }
+    }
+    matches(glob.as_bytes(), name.as_bytes())
+}
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result, bail, ensure};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+
+/// Convert a third-party trace file into the rustforger CallData format
+///
+/// Supported formats:
+/// - `chrome`: Chrome trace event format (`traceEvents` array or bare array
+///   of `B`/`E`/`X` events)
+/// - `otlp-json`: OpenTelemetry OTLP/JSON export (`resourceSpans` tree)
+///
+/// The converted file is a JSON array of CallData entries, so view/analyze
+/// tooling works on it like on a native trace.
+pub fn run(input: &Path, format: &str, output: &Path) -> Result<()> {
+    ensure!(input.exists(), "Input file does not exist: {}", input.display());
+
+    let content = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+    let parsed: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse input file as JSON: {}", input.display()))?;
+
+    let entries = match format {
+        "chrome" => convert_chrome(&parsed)?,
+        "otlp-json" => convert_otlp_json(&parsed)?,
+        other => bail!("Unsupported import format: {} (expected 'chrome' or 'otlp-json')", other),
+    };
+
+    let json_string = serde_json::to_string_pretty(&entries)?;
+    fs::write(output, json_string)
+        .with_context(|| format!("Failed to write converted trace to: {}", output.display()))?;
+
+    println!("imported {} call(s) from {} trace to {}", entries.len(), format, output.display());
+    Ok(())
+}
+
+/// Convert Chrome trace events into CallData entries
+///
+/// Duration (`X`) and begin/end (`B`/`E`) events are nested per thread by
+/// their timestamps; each completed top-of-stack event becomes one entry.
+fn convert_chrome(parsed: &Value) -> Result<Vec<Value>> {
+    let events = parsed
+        .get("traceEvents")
+        .and_then(|v| v.as_array())
+        .or_else(|| parsed.as_array())
+        .context("Chrome trace must be an event array or contain a 'traceEvents' array")?;
+
+    let mut entries = Vec::new();
+    let mut next_call_id: u64 = 1;
+    // Per-thread stacks of open nodes, keyed by "pid:tid"
+    let mut stacks: std::collections::HashMap<String, Vec<Value>> = std::collections::HashMap::new();
+
+    for event in events {
+        let phase = event.get("ph").and_then(|v| v.as_str()).unwrap_or("");
+        if !matches!(phase, "B" | "E" | "X") {
+            continue;
+        }
+
+        let thread_key = format!(
+            "{}:{}",
+            event.get("pid").and_then(|v| v.as_u64()).unwrap_or(0),
+            event.get("tid").and_then(|v| v.as_u64()).unwrap_or(0)
+        );
+        let stack = stacks.entry(thread_key.clone()).or_default();
+
+        match phase {
+            "B" | "X" => {
+                let ts_micros = event.get("ts").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let node = json!({
+                    "call_id": next_call_id,
+                    "name": event.get("name").and_then(|v| v.as_str()).unwrap_or("<unnamed>"),
+                    "file": "<chrome-trace>",
+                    "line": 0,
+                    "children": [],
+                });
+                next_call_id += 1;
+
+                if phase == "B" {
+                    stack.push(node);
+                } else if let Some(parent) = stack.last_mut() {
+                    parent["children"].as_array_mut().unwrap().push(node);
+                } else {
+                    entries.push(call_data_entry(ts_micros, &thread_key, node, event));
+                }
+            }
+            "E" => {
+                if let Some(node) = stack.pop() {
+                    let ts_micros = event.get("ts").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                    if let Some(parent) = stack.last_mut() {
+                        parent["children"].as_array_mut().unwrap().push(node);
+                    } else {
+                        entries.push(call_data_entry(ts_micros, &thread_key, node, event));
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Convert an OTLP/JSON span export into CallData entries
+///
+/// Spans are nested by `parentSpanId`; each span without a known parent
+/// becomes the root of one entry.
+fn convert_otlp_json(parsed: &Value) -> Result<Vec<Value>> {
+    let resource_spans = parsed
+        .get("resourceSpans")
+        .and_then(|v| v.as_array())
+        .context("OTLP/JSON trace must contain a 'resourceSpans' array")?;
+
+    let mut spans = Vec::new();
+    for resource in resource_spans {
+        let scope_spans = resource
+            .get("scopeSpans")
+            .or_else(|| resource.get("instrumentationLibrarySpans"))
+            .and_then(|v| v.as_array());
+        for scope in scope_spans.into_iter().flatten() {
+            for span in scope.get("spans").and_then(|v| v.as_array()).into_iter().flatten() {
+                spans.push(span);
+            }
+        }
+    }
+
+    let mut nodes: std::collections::HashMap<String, Value> = std::collections::HashMap::new();
+    let mut parents: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    let mut order = Vec::new();
+
+    for (index, span) in spans.iter().enumerate() {
+        let span_id = span
+            .get("spanId")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("<missing-span-id-{}>", index));
+        let node = json!({
+            "call_id": (index + 1) as u64,
+            "name": span.get("name").and_then(|v| v.as_str()).unwrap_or("<unnamed>"),
+            "file": "<otlp-json>",
+            "line": 0,
+            "children": [],
+        });
+        if let Some(parent_id) = span.get("parentSpanId").and_then(|v| v.as_str()) {
+            if !parent_id.is_empty() {
+                parents.insert(span_id.clone(), parent_id.to_string());
+            }
+        }
+        nodes.insert(span_id.clone(), node);
+        order.push(span_id);
+    }
+
+    // Attach children to parents bottom-up; spans whose parent is not in the
+    // export stay as roots
+    for span_id in order.iter().rev() {
+        let parent_id = match parents.get(span_id) {
+            Some(parent_id) if nodes.contains_key(parent_id) => parent_id.clone(),
+            _ => continue,
+        };
+        if let Some(node) = nodes.remove(span_id) {
+            if let Some(parent) = nodes.get_mut(&parent_id) {
+                parent["children"].as_array_mut().unwrap().push(node);
+            }
+        }
+    }
+
+    let mut entries = Vec::new();
+    for (index, span_id) in order.iter().enumerate() {
+        let node = match nodes.remove(span_id) {
+            Some(node) => node,
+            None => continue, // attached to a parent above
+        };
+        let span = spans[index];
+        let start_nanos = span
+            .get("startTimeUnixNano")
+            .and_then(|v| v.as_str().and_then(|s| s.parse::<f64>().ok()).or_else(|| v.as_f64()))
+            .unwrap_or(0.0);
+        let trace_id = span.get("traceId").and_then(|v| v.as_str()).unwrap_or("unknown");
+        entries.push(json!({
+            "timestamp_utc": micros_to_rfc3339(start_nanos / 1000.0),
+            "thread_id": format!("otlp:{}", trace_id),
+            "root_node": node,
+            "inputs": span.get("attributes").cloned().unwrap_or(Value::Null),
+            "output": Value::Null,
+        }));
+    }
+
+    Ok(entries)
+}
+
+/// Build a CallData entry for a completed Chrome event tree
+fn call_data_entry(ts_micros: f64, thread_key: &str, node: Value, event: &Value) -> Value {
+    json!({
+        "timestamp_utc": micros_to_rfc3339(ts_micros),
+        "thread_id": format!("chrome:{}", thread_key),
+        "root_node": node,
+        "inputs": event.get("args").cloned().unwrap_or(Value::Null),
+        "output": Value::Null,
+    })
+}
+
+/// Render a microsecond epoch offset as an RFC 3339 timestamp
+fn micros_to_rfc3339(micros: f64) -> String {
+    let secs = (micros / 1_000_000.0) as i64;
+    let nanos = ((micros % 1_000_000.0) * 1000.0) as u32;
+    chrono::DateTime::from_timestamp(secs, nanos)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| "1970-01-01T00:00:00+00:00".to_string())
+}
@@ -8,6 +8,7 @@ use prettyplease::unparse;
 use crate::utils::fs::{find_cargo_toml, find_project_root};
 use crate::utils::cargo::{DependencyType, update_cargo_toml_with_deps};
 use crate::utils::config::{PropagationConfig, create_trace_config_file};
+use crate::utils::function_index::{self, IndexedFunction};
 
 /// Function specification that can handle both simple names and qualified paths
 #[derive(Debug, Clone)]
@@ -46,13 +47,13 @@ impl FunctionSpec {
     
     /// Check if this spec matches a simple function name
     fn matches_function_name(&self, name: &syn::Ident) -> bool {
-        name.to_string() == self.method_name
+        *name == self.method_name
     }
     
     /// Check if this spec matches a method in an impl block
     fn matches_impl_method(&self, impl_type: &syn::Type, method_name: &syn::Ident) -> bool {
         // Method name must match
-        if method_name.to_string() != self.method_name {
+        if *method_name != self.method_name {
             return false;
         }
         
@@ -92,8 +93,7 @@ fn extract_type_name(ty: &syn::Type) -> String {
             let type_str = quote::quote!(#ty).to_string();
             // Simple heuristic: take the last identifier-like token
             type_str.split(&[' ', '<', '>', ':'])
-                .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_'))
-                .last()
+                .rfind(|s| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_'))
                 .unwrap_or("")
                 .to_string()
         }
@@ -123,7 +123,7 @@ pub fn run(
     ensure!(instrumenter.found_function, 
         "Function '{}' not found in file\n\n{}", 
         function_name,
-        generate_function_suggestions_with_similarity(&syntax_tree, function_name)
+        generate_function_suggestions_cached(file_path, &source_code, &syntax_tree, function_name)
     );
     
     let formatted_code = unparse(&syntax_tree);
@@ -168,7 +168,7 @@ pub fn run_multiple(
         anyhow::bail!(
             "Functions not found in file: {:?}\n\n{}", 
             missing_functions,
-            generate_function_suggestions_with_similarity(&syntax_tree, primary_missing)
+            generate_function_suggestions_cached(file_path, &source_code, &syntax_tree, primary_missing)
         );
     }
     
@@ -190,36 +190,275 @@ pub fn run_multiple(
 
 /// Add tracing instrumentation to all functions in a file
 pub fn run_all(
-    file_path: &Path, 
+    file_path: &Path,
     trace_output: Option<&Path>,
     propagation_config: Option<PropagationConfig>
 ) -> Result<()> {
     ensure!(file_path.exists(), "File does not exist: {}", file_path.display());
-    
+
+    let instrumented_count = instrument_all_in_file(file_path, &propagation_config)?;
+
+    add_dependencies_to_cargo_toml(file_path)?;
+
+    let project_root = find_project_root(file_path)?;
+    create_trace_config_file(&project_root, trace_output, propagation_config.as_ref())?;
+
+    println!("instrumented {} functions in {}", instrumented_count, file_path.display());
+    Ok(())
+}
+
+/// Instrument every function in one file, returning how many received
+/// the trace attribute
+fn instrument_all_in_file(
+    file_path: &Path,
+    propagation_config: &Option<PropagationConfig>,
+) -> Result<usize> {
     let source_code = fs::read_to_string(file_path)
         .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
-    
+
     let mut syntax_tree = parse_file(&source_code)
         .context("Failed to parse Rust source code")?;
-    
+
     ensure_trace_imports(&mut syntax_tree);
-    
+
     let mut instrumenter = AllFunctionInstrumenter::new(propagation_config.clone());
     instrumenter.visit_file_mut(&mut syntax_tree);
-    
+
     let formatted_code = unparse(&syntax_tree);
     fs::write(file_path, formatted_code)
         .with_context(|| format!("Failed to write modified code to: {}", file_path.display()))?;
-    
-    add_dependencies_to_cargo_toml(file_path)?;
-    
-    let project_root = find_project_root(file_path)?;
+
+    Ok(instrumenter.instrumented_count)
+}
+
+/// Add tracing instrumentation to every function in a module
+///
+/// `module_path` is a Rust module path like `crate::parser::lexer`. It is
+/// resolved against the crate root (`src/lib.rs`, falling back to
+/// `src/main.rs`) of the project at `project_dir`, following `mod` items
+/// through files (`lexer.rs` or `lexer/mod.rs`) and inline `mod lexer { }`
+/// blocks. All functions in the module and its submodules are
+/// instrumented, sitting between instrumenting single functions and whole
+/// files.
+pub fn run_module(
+    project_dir: &Path,
+    module_path: &str,
+    trace_output: Option<&Path>,
+    propagation_config: Option<PropagationConfig>
+) -> Result<()> {
+    let target = resolve_module(project_dir, module_path)?;
+
+    let mut instrumented_count = 0;
+    let mut files = Vec::new();
+    match &target {
+        ModuleTarget::File { file, dir } => {
+            collect_module_files(file, dir, &mut files)?;
+            for file in &files {
+                instrumented_count += instrument_all_in_file(file, &propagation_config)?;
+            }
+        }
+        ModuleTarget::Inline { file, dir, module } => {
+            files.push(file.clone());
+            instrumented_count +=
+                instrument_inline_module(file, dir, module, &propagation_config, &mut files)?;
+        }
+    }
+
+    let primary_file = files.first().expect("a resolved module has at least one file");
+    add_dependencies_to_cargo_toml(primary_file)?;
+
+    let project_root = find_project_root(primary_file)?;
     create_trace_config_file(&project_root, trace_output, propagation_config.as_ref())?;
-    
-    println!("instrumented {} functions in {}", instrumenter.instrumented_count, file_path.display());
+
+    println!("instrumented {} function(s) in module '{}' across {} file(s)",
+             instrumented_count, module_path, files.len());
+    Ok(())
+}
+
+/// Where a module path resolved to
+enum ModuleTarget {
+    /// A module with its own file; `dir` holds its child module files
+    File { file: std::path::PathBuf, dir: std::path::PathBuf },
+    /// An inline `mod { }` block at `module` (relative to `file`'s items)
+    Inline { file: std::path::PathBuf, dir: std::path::PathBuf, module: Vec<String> },
+}
+
+/// Resolve a `crate::a::b` style path to the file or inline block it names
+fn resolve_module(project_dir: &Path, module_path: &str) -> Result<ModuleTarget> {
+    let segments: Vec<&str> = module_path
+        .split("::")
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let segments = match segments.split_first() {
+        Some((&"crate", rest)) => rest,
+        _ => &segments[..],
+    };
+    ensure!(!segments.is_empty(),
+        "Module path must name a module, got: {}", module_path);
+
+    let src_dir = project_dir.join("src");
+    let mut file = ["lib.rs", "main.rs"]
+        .iter()
+        .map(|name| src_dir.join(name))
+        .find(|candidate| candidate.exists())
+        .with_context(|| format!(
+            "No src/lib.rs or src/main.rs found under: {}", project_dir.display()))?;
+    let mut dir = src_dir;
+    let mut inline: Vec<String> = Vec::new();
+
+    for segment in segments {
+        let source_code = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        let syntax_tree = parse_file(&source_code)
+            .with_context(|| format!("Failed to parse Rust source code in: {}", file.display()))?;
+        let items = items_at(&syntax_tree.items, &inline)
+            .expect("inline path was built from this file's items");
+
+        let module = items
+            .iter()
+            .find_map(|item| match item {
+                Item::Mod(module) if module.ident == *segment => Some(module),
+                _ => None,
+            })
+            .with_context(|| format!(
+                "Module '{}' not found in {}", segment, file.display()))?;
+
+        if module.content.is_some() {
+            inline.push(segment.to_string());
+        } else {
+            file = child_module_file(&dir, segment).with_context(|| format!(
+                "No file found for module '{}' (expected {}.rs or {}/mod.rs in {})",
+                segment, segment, segment, dir.display()))?;
+            inline.clear();
+        }
+        dir = dir.join(segment);
+    }
+
+    Ok(if inline.is_empty() {
+        ModuleTarget::File { file, dir }
+    } else {
+        ModuleTarget::Inline { file, dir, module: inline }
+    })
+}
+
+/// The items of the inline module at `path`, or of the file itself when
+/// `path` is empty
+fn items_at<'a>(items: &'a [Item], path: &[String]) -> Option<&'a [Item]> {
+    let Some((first, rest)) = path.split_first() else {
+        return Some(items);
+    };
+    items.iter().find_map(|item| match item {
+        Item::Mod(module) if module.ident == first.as_str() => {
+            items_at(&module.content.as_ref()?.1, rest)
+        }
+        _ => None,
+    })
+}
+
+/// The file backing `mod name;` declared in a module whose children live
+/// in `dir`
+fn child_module_file(dir: &Path, name: &str) -> Option<std::path::PathBuf> {
+    [dir.join(format!("{}.rs", name)), dir.join(name).join("mod.rs")]
+        .into_iter()
+        .find(|candidate| candidate.exists())
+}
+
+/// Collect `file` and every descendant module file into `files`
+///
+/// Declarations whose file cannot be found (typically `#[cfg]`-gated
+/// platform modules) are skipped with a warning rather than failing the
+/// whole run.
+fn collect_module_files(
+    file: &Path,
+    dir: &Path,
+    files: &mut Vec<std::path::PathBuf>,
+) -> Result<()> {
+    files.push(file.to_path_buf());
+    let source_code = fs::read_to_string(file)
+        .with_context(|| format!("Failed to read file: {}", file.display()))?;
+    let syntax_tree = parse_file(&source_code)
+        .with_context(|| format!("Failed to parse Rust source code in: {}", file.display()))?;
+    collect_child_module_files(&syntax_tree.items, dir, files)
+}
+
+fn collect_child_module_files(
+    items: &[Item],
+    dir: &Path,
+    files: &mut Vec<std::path::PathBuf>,
+) -> Result<()> {
+    for item in items {
+        let Item::Mod(module) = item else { continue };
+        let name = module.ident.to_string();
+        match &module.content {
+            // Inline blocks are part of the enclosing file; only their
+            // own `mod x;` children add files
+            Some((_, nested)) => collect_child_module_files(nested, &dir.join(&name), files)?,
+            None => match child_module_file(dir, &name) {
+                Some(child) => collect_module_files(&child, &dir.join(&name), files)?,
+                None => eprintln!(
+                    "warning: skipping module '{}': no file found in {}", name, dir.display()),
+            },
+        }
+    }
     Ok(())
 }
 
+/// Instrument only the inline `mod { }` block at `module` within `file`,
+/// then descend into module files declared inside the block
+fn instrument_inline_module(
+    file: &Path,
+    dir: &Path,
+    module: &[String],
+    propagation_config: &Option<PropagationConfig>,
+    files: &mut Vec<std::path::PathBuf>,
+) -> Result<usize> {
+    let source_code = fs::read_to_string(file)
+        .with_context(|| format!("Failed to read file: {}", file.display()))?;
+    let mut syntax_tree = parse_file(&source_code)
+        .with_context(|| format!("Failed to parse Rust source code in: {}", file.display()))?;
+
+    ensure_trace_imports(&mut syntax_tree);
+
+    let block = inline_module_mut(&mut syntax_tree.items, module)
+        .expect("inline path was resolved against this file");
+    let mut instrumenter = AllFunctionInstrumenter::new(propagation_config.clone());
+    instrumenter.visit_item_mod_mut(block);
+    let mut instrumented_count = instrumenter.instrumented_count;
+
+    // Gather `mod x;` declarations inside the block before writing
+    let child_items = block.content.as_ref().map(|(_, items)| items.clone()).unwrap_or_default();
+
+    let formatted_code = unparse(&syntax_tree);
+    fs::write(file, formatted_code)
+        .with_context(|| format!("Failed to write modified code to: {}", file.display()))?;
+
+    let mut child_files = Vec::new();
+    collect_child_module_files(&child_items, dir, &mut child_files)?;
+    for child in &child_files {
+        instrumented_count += instrument_all_in_file(child, propagation_config)?;
+    }
+    files.extend(child_files);
+
+    Ok(instrumented_count)
+}
+
+/// The inline module item at `path`, mutably
+fn inline_module_mut<'a>(items: &'a mut [Item], path: &[String]) -> Option<&'a mut syn::ItemMod> {
+    let (first, rest) = path.split_first()?;
+    for item in items {
+        let Item::Mod(module) = item else { continue };
+        if module.ident != first.as_str() {
+            continue;
+        }
+        return if rest.is_empty() {
+            Some(module)
+        } else {
+            inline_module_mut(&mut module.content.as_mut()?.1, rest)
+        };
+    }
+    None
+}
+
 /// Ensure necessary use statements are present
 fn ensure_trace_imports(syntax_tree: &mut syn::File) {
     let has_trace_import = syntax_tree.items.iter().any(|item| {
@@ -647,6 +886,7 @@ impl syn::visit::Visit<'_> for FunctionCollector {
 
 /// Generate helpful function suggestions when user input doesn't match any functions
 /// Returns a formatted string listing all available functions organized by category
+#[allow(dead_code)]
 fn generate_function_suggestions(syntax_tree: &syn::File) -> String {
     let mut collector = FunctionCollector::new();
     syn::visit::visit_file(&mut collector, syntax_tree);
@@ -732,11 +972,11 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
     
     // Initialize first row and column
-    for i in 0..=len1 {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
     
     let s1_chars: Vec<char> = s1.chars().collect();
@@ -754,14 +994,54 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     matrix[len1][len2]
 }
 
+/// Generate function suggestions, reusing the on-disk function index when
+/// the file is unchanged since a previous invocation
+///
+/// Building the function list for a 5k-function file on every miss is the
+/// slow part of suggestion generation, so it is cached keyed by source hash.
+fn generate_function_suggestions_cached(
+    file_path: &Path,
+    source_code: &str,
+    syntax_tree: &syn::File,
+    user_input: &str,
+) -> String {
+    let hash = function_index::source_hash(source_code);
+    let functions = match function_index::load(file_path, &hash) {
+        Some(cached) => cached
+            .into_iter()
+            .map(|entry| AvailableFunction {
+                full_name: entry.full_name,
+                function_type: match entry.type_name {
+                    Some(type_name) => FunctionCategory::ImplMethod { type_name },
+                    None => FunctionCategory::Standalone,
+                },
+            })
+            .collect(),
+        None => {
+            let mut collector = FunctionCollector::new();
+            syn::visit::visit_file(&mut collector, syntax_tree);
+            let functions = collector.into_sorted_functions();
+            let indexed = functions
+                .iter()
+                .map(|func| IndexedFunction {
+                    full_name: func.full_name.clone(),
+                    type_name: match &func.function_type {
+                        FunctionCategory::ImplMethod { type_name } => Some(type_name.clone()),
+                        FunctionCategory::Standalone => None,
+                    },
+                })
+                .collect();
+            function_index::store(file_path, &hash, indexed);
+            functions
+        }
+    };
+
+    generate_function_suggestions_with_similarity(functions, user_input)
+}
+
 /// Generate function suggestions with similarity-based filtering
 /// Limits output to top 20 most similar functions when there are many options
-fn generate_function_suggestions_with_similarity(syntax_tree: &syn::File, user_input: &str) -> String {
-    let mut collector = FunctionCollector::new();
-    syn::visit::visit_file(&mut collector, syntax_tree);
-    
-    let mut functions = collector.into_sorted_functions();
-    
+fn generate_function_suggestions_with_similarity(mut functions: Vec<AvailableFunction>, user_input: &str) -> String {
     if functions.is_empty() {
         return "No public functions found in this file.".to_string();
     }
@@ -810,7 +1090,7 @@ fn format_function_list(functions: &[AvailableFunction]) -> String {
             }
             FunctionCategory::ImplMethod { type_name } => {
                 by_type.entry(type_name.clone())
-                      .or_insert_with(Vec::new)
+                      .or_default()
                       .push(func.full_name.clone());
             }
         }
@@ -839,4 +1119,164 @@ fn format_function_list(functions: &[AvailableFunction]) -> String {
     output.push_str("For methods, use the full qualified name like 'TypeName::method_name'.");
     
     output
-} 
\ No newline at end of file
+} 
+/// A single instrumentation target extracted from compiler diagnostics
+#[derive(Debug)]
+struct DiagnosticTarget {
+    file: std::path::PathBuf,
+    line: usize,
+}
+
+/// Instrument every function containing a warning/error location reported by
+/// `cargo check --message-format=json`
+///
+/// This is a convenience workflow for investigating why a diagnostic-ridden
+/// area misbehaves at runtime: pipe the check output to a file and point this
+/// command at it.
+pub fn run_from_check_json(
+    diagnostics_path: &Path,
+    trace_output: Option<&Path>,
+    propagation_config: Option<PropagationConfig>,
+) -> Result<()> {
+    ensure!(diagnostics_path.exists(), 
+        "Diagnostics file does not exist: {}", diagnostics_path.display());
+    
+    let content = fs::read_to_string(diagnostics_path)
+        .with_context(|| format!("Failed to read diagnostics file: {}", diagnostics_path.display()))?;
+    
+    let targets = parse_check_json_targets(&content)?;
+    ensure!(!targets.is_empty(), 
+        "No warning/error locations found in diagnostics file: {}", diagnostics_path.display());
+    
+    // Group target lines by source file
+    let mut lines_by_file: std::collections::BTreeMap<std::path::PathBuf, Vec<usize>> = 
+        std::collections::BTreeMap::new();
+    for target in targets {
+        lines_by_file.entry(target.file).or_default().push(target.line);
+    }
+    
+    let mut total_instrumented = 0;
+    for (file, lines) in lines_by_file {
+        if !file.exists() {
+            eprintln!("warning: skipping missing source file: {}", file.display());
+            continue;
+        }
+        
+        let source_code = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        let syntax_tree = parse_file(&source_code)
+            .with_context(|| format!("Failed to parse Rust source: {}", file.display()))?;
+        
+        let functions = find_functions_containing_lines(&syntax_tree, &lines);
+        if functions.is_empty() {
+            eprintln!("warning: no functions found at diagnostic locations in {}", file.display());
+            continue;
+        }
+        
+        run_multiple(&file, &functions, trace_output, propagation_config.clone())?;
+        total_instrumented += functions.len();
+    }
+    
+    ensure!(total_instrumented > 0, "No functions matched any diagnostic locations");
+    println!("instrumented {} function(s) from compiler diagnostics", total_instrumented);
+    Ok(())
+}
+
+/// Extract (file, line) targets from `cargo check --message-format=json` output
+///
+/// The input is one JSON object per line; only `compiler-message` entries with
+/// warning or error level contribute targets.
+fn parse_check_json_targets(content: &str) -> Result<Vec<DiagnosticTarget>> {
+    let mut targets = Vec::new();
+    
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        
+        // Lines that are not valid JSON (e.g. plain cargo output) are skipped
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        
+        if value.get("reason").and_then(|r| r.as_str()) != Some("compiler-message") {
+            continue;
+        }
+        
+        let Some(message) = value.get("message") else {
+            continue;
+        };
+        
+        let level = message.get("level").and_then(|l| l.as_str()).unwrap_or("");
+        if level != "warning" && level != "error" {
+            continue;
+        }
+        
+        if let Some(spans) = message.get("spans").and_then(|s| s.as_array()) {
+            for span in spans {
+                let is_primary = span.get("is_primary").and_then(|p| p.as_bool()).unwrap_or(false);
+                if !is_primary {
+                    continue;
+                }
+                
+                let (Some(file_name), Some(line_start)) = (
+                    span.get("file_name").and_then(|f| f.as_str()),
+                    span.get("line_start").and_then(|l| l.as_u64()),
+                ) else {
+                    continue;
+                };
+                
+                targets.push(DiagnosticTarget {
+                    file: std::path::PathBuf::from(file_name),
+                    line: line_start as usize,
+                });
+            }
+        }
+    }
+    
+    Ok(targets)
+}
+
+/// Find functions whose source span contains any of the given lines
+///
+/// Returns function specifications in the format accepted by `run_multiple`
+/// (simple names for free functions, `Type::method` for impl methods).
+fn find_functions_containing_lines(syntax_tree: &syn::File, lines: &[usize]) -> Vec<String> {
+    use syn::spanned::Spanned;
+    
+    let mut functions = Vec::new();
+    
+    let contains_line = |start: usize, end: usize| {
+        lines.iter().any(|&line| line >= start && line <= end)
+    };
+    
+    for item in &syntax_tree.items {
+        match item {
+            Item::Fn(item_fn) => {
+                let start = item_fn.span().start().line;
+                let end = item_fn.span().end().line;
+                if contains_line(start, end) {
+                    functions.push(item_fn.sig.ident.to_string());
+                }
+            }
+            Item::Impl(item_impl) => {
+                let type_name = extract_type_name(&item_impl.self_ty);
+                for impl_item in &item_impl.items {
+                    if let syn::ImplItem::Fn(method) = impl_item {
+                        let start = method.span().start().line;
+                        let end = method.span().end().line;
+                        if contains_line(start, end) {
+                            functions.push(format!("{}::{}", type_name, method.sig.ident));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    functions.sort();
+    functions.dedup();
+    functions
+}
@@ -34,7 +34,7 @@ fn search_trace_files(dir: &Path) -> Result<Vec<(String, u32, String)>> {
 /// Try searching with ripgrep
 fn try_ripgrep_search(dir: &Path) -> Result<Vec<(String, u32, String)>> {
     let output = Command::new("rg")
-        .args(&[
+        .args([
             "--line-number",
             "--type", "rust",
             "--only-matching",
@@ -51,7 +51,7 @@ fn try_ripgrep_search(dir: &Path) -> Result<Vec<(String, u32, String)>> {
 /// Try searching with grep
 fn try_grep_search(dir: &Path) -> Result<Vec<(String, u32, String)>> {
     let output = Command::new("grep")
-        .args(&[
+        .args([
             "-rn",
             "--include=*.rs",
             r"#\[.*trace.*\]",
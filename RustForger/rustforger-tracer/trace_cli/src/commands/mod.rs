@@ -1,6 +1,17 @@
+pub mod analyze;
+pub mod convert;
+pub mod diff;
+pub mod filter;
+pub mod import;
+pub mod merge;
+pub mod query;
 pub mod instrument;
+pub mod redact;
+pub mod report;
 pub mod revert;
 pub mod list_traced;
 pub mod setup;
+pub mod view;
 pub mod clean;
-pub mod run_flow; 
\ No newline at end of file
+pub mod run_flow;
+pub mod selftest; 
\ No newline at end of file
@@ -0,0 +1,173 @@
+use anyhow::{Context, Result, bail, ensure};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use trace_common::schema::TraceFile;
+
+/// Query a trace file with a small selector DSL
+///
+/// The selector walks the records of the file, starting from `calls`:
+///
+/// - `calls` — every record
+/// - `.field` — a field of each current value
+/// - `[N]` — one array element
+/// - `[*]` — every array element
+/// - `[?path=value]` — array elements whose `path` (dots allowed) equals
+///   `value`, written as a JSON literal or a bare/quoted string
+///
+/// A leading `$.` is accepted for JSONPath familiarity. For example:
+///
+/// ```text
+/// trace_cli query trace.json 'calls[?root_node.name="handle_request"].inputs'
+/// ```
+///
+/// Each match is printed as one line of JSON, ready for scripting.
+pub fn run(trace_file: &Path, selector: &str) -> Result<()> {
+    ensure!(trace_file.exists(), "Trace file does not exist: {}", trace_file.display());
+
+    let content = fs::read_to_string(trace_file)
+        .with_context(|| format!("Failed to read trace file: {}", trace_file.display()))?;
+    let document: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse trace file as JSON: {}", trace_file.display()))?;
+    let records = TraceFile::from_value(document)
+        .with_context(|| "Failed to load trace data")?
+        .records;
+    let calls = serde_json::to_value(records)?;
+
+    for matched in select(&calls, selector)? {
+        println!("{}", matched);
+    }
+    Ok(())
+}
+
+/// One step of a parsed selector
+#[derive(Debug, PartialEq)]
+enum Segment {
+    /// `.field`
+    Field(String),
+    /// `[N]`
+    Index(usize),
+    /// `[*]`
+    All,
+    /// `[?path=value]`
+    Filter { path: Vec<String>, value: Value },
+}
+
+/// Evaluate a selector against the records array of a trace file
+pub fn select(calls: &Value, selector: &str) -> Result<Vec<Value>> {
+    let segments = parse_selector(selector)?;
+
+    let mut current = vec![calls.clone()];
+    for segment in &segments {
+        let mut next = Vec::new();
+        for value in current {
+            match segment {
+                Segment::Field(field) => {
+                    if let Some(found) = value.get(field) {
+                        next.push(found.clone());
+                    }
+                }
+                Segment::Index(index) => {
+                    if let Some(found) = value.get(index) {
+                        next.push(found.clone());
+                    }
+                }
+                Segment::All => {
+                    if let Some(elements) = value.as_array() {
+                        next.extend(elements.iter().cloned());
+                    }
+                }
+                Segment::Filter { path, value: expected } => {
+                    for element in value.as_array().into_iter().flatten() {
+                        let found = path
+                            .iter()
+                            .try_fold(element, |value, field| value.get(field));
+                        if found == Some(expected) {
+                            next.push(element.clone());
+                        }
+                    }
+                }
+            }
+        }
+        current = next;
+    }
+    Ok(current)
+}
+
+/// Parse a selector string into segments
+fn parse_selector(selector: &str) -> Result<Vec<Segment>> {
+    let trimmed = selector.trim();
+    let trimmed = trimmed.strip_prefix("$.").or_else(|| trimmed.strip_prefix('$')).unwrap_or(trimmed);
+    let rest = trimmed
+        .strip_prefix("calls")
+        .with_context(|| format!("Selector must start with 'calls', got: {}", selector))?;
+
+    let mut segments = Vec::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut field = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == '.' || next == '[' {
+                        break;
+                    }
+                    field.push(next);
+                    chars.next();
+                }
+                ensure!(!field.is_empty(), "Empty field name in selector: {}", selector);
+                segments.push(Segment::Field(field));
+            }
+            '[' => {
+                let mut inner = String::new();
+                let mut in_string = false;
+                loop {
+                    match chars.next() {
+                        Some('"') => {
+                            in_string = !in_string;
+                            inner.push('"');
+                        }
+                        Some(']') if !in_string => break,
+                        Some(other) => inner.push(other),
+                        None => bail!("Unclosed '[' in selector: {}", selector),
+                    }
+                }
+                segments.push(parse_bracket(&inner, selector)?);
+            }
+            other => bail!("Unexpected {:?} in selector: {}", other, selector),
+        }
+    }
+    Ok(segments)
+}
+
+/// Parse the inside of one `[...]`
+fn parse_bracket(inner: &str, selector: &str) -> Result<Segment> {
+    if inner == "*" {
+        return Ok(Segment::All);
+    }
+    if let Ok(index) = inner.parse::<usize>() {
+        return Ok(Segment::Index(index));
+    }
+    let Some(condition) = inner.strip_prefix('?') else {
+        bail!("Expected index, '*' or '?filter' in selector: {}", selector);
+    };
+    // JSONPath writes `?(@.path==value)`; accept that shape too
+    let condition = condition
+        .strip_prefix('(')
+        .and_then(|c| c.strip_suffix(')'))
+        .unwrap_or(condition);
+    let condition = condition.strip_prefix("@.").unwrap_or(condition);
+
+    let (path, raw_value) = condition
+        .split_once("==")
+        .or_else(|| condition.split_once('='))
+        .with_context(|| format!("Filter must be 'path=value', got: [{}]", inner))?;
+    let path: Vec<String> = path.trim().split('.').map(str::to_string).collect();
+    ensure!(!path.iter().any(String::is_empty), "Bad filter path in selector: {}", selector);
+
+    // A JSON literal when it parses as one, a bare string otherwise
+    let raw_value = raw_value.trim();
+    let value = serde_json::from_str(raw_value)
+        .unwrap_or_else(|_| Value::String(raw_value.to_string()));
+    Ok(Segment::Filter { path, value })
+}
@@ -0,0 +1,88 @@
+use anyhow::{Context, Result, ensure};
+use std::fs;
+use std::path::Path;
+
+use trace_common::redact::RedactionRules;
+
+/// Apply privacy rules from a TOML file to an existing trace file
+///
+/// Reuses the trace_common redaction engine so programmatic and CLI
+/// redaction behave identically. The rules file supports:
+///
+/// ```toml
+/// keys = ["password", "api_key"]
+/// key_globs = ["*_token"]
+/// key_patterns = ["(?i)secret"]
+/// value_patterns = ["[0-9]{13,19}"]
+/// replacement = "<redacted>"
+/// max_string_len = 256
+/// ```
+pub fn run(trace_file: &Path, rules_file: &Path, output: &Path) -> Result<()> {
+    ensure!(trace_file.exists(), "Trace file does not exist: {}", trace_file.display());
+    ensure!(rules_file.exists(), "Rules file does not exist: {}", rules_file.display());
+
+    let rules = load_rules(rules_file)?;
+    ensure!(!rules.is_empty(), "Rules file contains no redaction rules: {}", rules_file.display());
+
+    let content = fs::read_to_string(trace_file)
+        .with_context(|| format!("Failed to read trace file: {}", trace_file.display()))?;
+    let mut trace: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse trace file as JSON: {}", trace_file.display()))?;
+
+    rules.apply(&mut trace);
+
+    let redacted = serde_json::to_string_pretty(&trace)?;
+    fs::write(output, redacted)
+        .with_context(|| format!("Failed to write redacted trace to: {}", output.display()))?;
+
+    println!("redacted trace written to {}", output.display());
+    Ok(())
+}
+
+/// Parse a redaction rules TOML file into engine rules
+fn load_rules(rules_file: &Path) -> Result<RedactionRules> {
+    let content = fs::read_to_string(rules_file)
+        .with_context(|| format!("Failed to read rules file: {}", rules_file.display()))?;
+    let doc = content.parse::<toml_edit::Document>()
+        .with_context(|| format!("Failed to parse rules file as TOML: {}", rules_file.display()))?;
+
+    let mut rules = RedactionRules::new();
+
+    for key in toml_string_array(&doc, "keys") {
+        rules = rules.with_key(key);
+    }
+    for glob in toml_string_array(&doc, "key_globs") {
+        rules = rules.with_key_glob(&glob)
+            .with_context(|| format!("Invalid key glob: {}", glob))?;
+    }
+    for pattern in toml_string_array(&doc, "key_patterns") {
+        rules = rules.with_key_pattern(&pattern)
+            .with_context(|| format!("Invalid key pattern: {}", pattern))?;
+    }
+    for pattern in toml_string_array(&doc, "value_patterns") {
+        rules = rules.with_value_pattern(&pattern)
+            .with_context(|| format!("Invalid value pattern: {}", pattern))?;
+    }
+    if let Some(replacement) = doc.get("replacement").and_then(|v| v.as_str()) {
+        rules = rules.with_replacement(replacement);
+    }
+    if let Some(max_len) = doc.get("max_string_len").and_then(|v| v.as_integer()) {
+        ensure!(max_len > 0, "max_string_len must be positive");
+        rules = rules.with_max_string_len(max_len as usize);
+    }
+
+    Ok(rules)
+}
+
+/// Read an array of strings from the top level of a TOML document
+fn toml_string_array(doc: &toml_edit::Document, key: &str) -> Vec<String> {
+    doc.get(key)
+        .and_then(|v| v.as_array())
+        .map(|array| {
+            array.iter()
+                .filter_map(|item| item.as_str())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,177 @@
+use anyhow::{Context, Result, ensure};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use trace_common::schema::{CallData, CallNode, TraceFile};
+
+/// Render a trace file as a self-contained HTML report
+///
+/// The page needs no external resources, so it can be attached to a bug
+/// report as one file: a per-function summary table, plus every call tree
+/// as collapsible nodes with inputs/outputs, and a search box that opens
+/// and highlights matching calls.
+pub fn run(trace_file: &Path, output: &Path) -> Result<()> {
+    ensure!(trace_file.exists(), "Trace file does not exist: {}", trace_file.display());
+
+    let content = fs::read_to_string(trace_file)
+        .with_context(|| format!("Failed to read trace file: {}", trace_file.display()))?;
+    let document: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse trace file as JSON: {}", trace_file.display()))?;
+    let records = TraceFile::from_value(document)
+        .with_context(|| "Failed to load trace data")?
+        .records;
+
+    fs::write(output, generate_html(&records))
+        .with_context(|| format!("Failed to write report to: {}", output.display()))?;
+
+    println!("report for {} call(s) written to {}", records.len(), output.display());
+    Ok(())
+}
+
+/// Build the full HTML document for parsed records
+pub fn generate_html(records: &[CallData]) -> String {
+    let mut html = String::from(concat!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+        "<title>Trace report</title>\n<style>\n",
+        "body { font-family: monospace; margin: 1.5em; }\n",
+        "table { border-collapse: collapse; margin-bottom: 1.5em; }\n",
+        "th, td { border: 1px solid #999; padding: 0.2em 0.6em; text-align: right; }\n",
+        "th:first-child, td:first-child { text-align: left; }\n",
+        "details { margin-left: 1.2em; }\n",
+        "summary { cursor: pointer; }\n",
+        ".loc { color: #777; }\n",
+        ".values { margin: 0.2em 0 0.2em 1.2em; color: #333; white-space: pre-wrap; }\n",
+        ".hit > summary { background: #ffe97a; }\n",
+        "</style>\n</head>\n<body>\n",
+    ));
+
+    let _ = writeln!(html, "<h1>Trace report</h1>");
+    let _ = writeln!(html, "<p>{} recorded call(s)</p>", records.len());
+
+    render_summary_table(&mut html, records);
+    render_calls(&mut html, records);
+
+    html.push_str(SEARCH_SCRIPT);
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Per-function summary rows, busiest first
+fn render_summary_table(html: &mut String, records: &[CallData]) {
+    #[derive(Default)]
+    struct Row {
+        calls: usize,
+        total_duration_ns: u64,
+        max_depth: usize,
+    }
+
+    let mut rows: BTreeMap<&str, Row> = BTreeMap::new();
+    for record in records {
+        let row = rows.entry(record.root_node.name.as_str()).or_default();
+        row.calls += 1;
+        row.total_duration_ns += record.duration_ns.unwrap_or(0);
+        row.max_depth = row.max_depth.max(record.root_node.depth());
+    }
+    let mut rows: Vec<(&str, Row)> = rows.into_iter().collect();
+    rows.sort_by_key(|(_, row)| std::cmp::Reverse(row.calls));
+
+    html.push_str("<h2>Functions</h2>\n<table>\n");
+    html.push_str("<tr><th>function</th><th>calls</th><th>total ms</th><th>depth</th></tr>\n");
+    for (name, row) in rows {
+        let total_ms = if row.total_duration_ns > 0 {
+            format!("{:.3}", row.total_duration_ns as f64 / 1_000_000.0)
+        } else {
+            "-".to_string()
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(name),
+            row.calls,
+            total_ms,
+            row.max_depth
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+/// Every record as a collapsible call tree with its captured values
+fn render_calls(html: &mut String, records: &[CallData]) {
+    html.push_str("<h2>Calls</h2>\n");
+    html.push_str("<p><input id=\"search\" type=\"search\" placeholder=\"search call names\"> <span id=\"matches\"></span></p>\n");
+    html.push_str("<div id=\"calls\">\n");
+    for record in records {
+        let values = format!(
+            "thread {} @ {}\ninputs: {}\noutput: {}",
+            record.thread_id,
+            record.timestamp_utc,
+            pretty(&record.inputs),
+            pretty(&record.output),
+        );
+        render_node(html, &record.root_node, Some(&values), true);
+    }
+    html.push_str("</div>\n");
+}
+
+fn render_node(html: &mut String, node: &CallNode, values: Option<&str>, open: bool) {
+    let _ = writeln!(
+        html,
+        "<details{}><summary>{} <span class=\"loc\">{}:{}</span></summary>",
+        if open { " open" } else { "" },
+        escape(&node.name),
+        escape(&node.file),
+        node.line
+    );
+    if let Some(values) = values {
+        let _ = writeln!(html, "<div class=\"values\">{}</div>", escape(values));
+    } else if let Some(args) = &node.args {
+        let _ = writeln!(html, "<div class=\"values\">args: {}</div>", escape(&pretty(args)));
+    }
+    for child in &node.children {
+        render_node(html, child, None, false);
+    }
+    html.push_str("</details>\n");
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// Escape text for safe embedding in HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Inline search: opens and highlights every call whose name matches
+const SEARCH_SCRIPT: &str = concat!(
+    "<script>\n",
+    "const search = document.getElementById('search');\n",
+    "const matches = document.getElementById('matches');\n",
+    "search.addEventListener('input', () => {\n",
+    "  const query = search.value.toLowerCase();\n",
+    "  let hits = 0;\n",
+    "  for (const node of document.querySelectorAll('#calls details')) {\n",
+    "    node.classList.remove('hit');\n",
+    "  }\n",
+    "  if (!query) { matches.textContent = ''; return; }\n",
+    "  for (const node of document.querySelectorAll('#calls details')) {\n",
+    "    const name = node.querySelector(':scope > summary').textContent.toLowerCase();\n",
+    "    if (name.includes(query)) {\n",
+    "      hits += 1;\n",
+    "      node.classList.add('hit');\n",
+    "      let parent = node;\n",
+    "      while (parent && parent.id !== 'calls') {\n",
+    "        if (parent.tagName === 'DETAILS') { parent.open = true; }\n",
+    "        parent = parent.parentElement;\n",
+    "      }\n",
+    "    }\n",
+    "  }\n",
+    "  matches.textContent = hits + ' match(es)';\n",
+    "});\n",
+    "</script>\n",
+);
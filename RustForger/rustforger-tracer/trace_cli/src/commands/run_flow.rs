@@ -21,6 +21,7 @@ struct InstrumentSpec {
 }
 
 /// Execute complete trace flow: setup, instrument, run, and optionally clean
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     test_project: &Path,
     target_projects: &[PathBuf],
@@ -151,6 +152,7 @@ fn create_propagation_config(
 }
 
 /// Execute all steps of the trace flow
+#[allow(clippy::too_many_arguments)]
 fn execute_flow_steps(
     all_projects: &HashSet<PathBuf>,
     parsed_specs: &[InstrumentSpec],
@@ -166,7 +168,7 @@ fn execute_flow_steps(
     if clean_after {
         if let Err(e) = backup_files_before_instrumentation(parsed_specs, force) {
             // Even if backup fails, try to clean up before exiting
-            let _ = handle_cleanup_and_restoration(all_projects, parsed_specs, &Err(e.into()));
+            let _ = handle_cleanup_and_restoration(all_projects, parsed_specs, &Err(e));
             // Return the original backup error
             return Err(anyhow::anyhow!("Backup failed, aborting flow."));
         }
@@ -198,6 +200,7 @@ fn execute_flow_steps(
 }
 
 /// Execute the main flow steps (setup, instrument, execute, verify)
+#[allow(clippy::too_many_arguments)]
 fn execute_main_flow_steps(
     all_projects: &HashSet<PathBuf>,
     parsed_specs: &[InstrumentSpec],
@@ -563,7 +566,7 @@ mod tests {
 
     #[test]
     fn test_parse_instrument_specs() {
-        let specs = vec![
+        let specs = [
             "src/main.rs:main,helper".to_string(),
             "src/lib.rs:*".to_string(),
         ];
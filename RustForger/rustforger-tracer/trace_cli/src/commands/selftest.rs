@@ -0,0 +1,130 @@
+use anyhow::{Context, Result, ensure};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::commands::{clean, instrument, setup};
+
+/// End-to-end smoke test of the whole toolchain
+///
+/// Creates a throwaway sample project, then runs
+/// setup → instrument → build → execute → verify → clean against the local
+/// trace crates and reports which stage failed. The fastest way to confirm
+/// paths, toolchain and dependencies are sane before touching a real project.
+pub fn run(trace_tool_path: Option<&Path>, keep: bool) -> Result<()> {
+    let project_dir = std::env::temp_dir().join(format!(
+        "rustforger_selftest_{}_{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp()
+    ));
+
+    let result = run_stages(&project_dir, trace_tool_path);
+
+    if keep {
+        println!("sample project kept at {}", project_dir.display());
+    } else {
+        let _ = fs::remove_dir_all(&project_dir);
+    }
+
+    result
+}
+
+fn run_stages(project_dir: &Path, trace_tool_path: Option<&Path>) -> Result<()> {
+    stage("scaffold", || scaffold_sample_project(project_dir))?;
+
+    let trace_output = project_dir.join("selftest_trace.json");
+    stage("setup", || {
+        setup::run(project_dir, trace_tool_path, true, Some(&trace_output), false)
+    })?;
+
+    let main_rs = project_dir.join("src").join("main.rs");
+    stage("instrument", || {
+        instrument::run_multiple(&main_rs, &["add".to_string()], Some(&trace_output), None)
+    })?;
+
+    stage("build", || run_cargo(project_dir, "build"))?;
+    stage("execute", || run_cargo(project_dir, "run"))?;
+
+    stage("verify", || verify_trace_output(&trace_output))?;
+
+    stage("clean", || clean::run(project_dir))?;
+
+    println!("selftest passed: all stages completed");
+    Ok(())
+}
+
+/// Run one named stage, attributing any failure to it
+fn stage(name: &str, body: impl FnOnce() -> Result<()>) -> Result<()> {
+    println!("selftest stage: {}", name);
+    body().with_context(|| format!("selftest failed at stage '{}'", name))
+}
+
+/// Write a minimal binary project with a traceable function
+fn scaffold_sample_project(project_dir: &Path) -> Result<()> {
+    fs::create_dir_all(project_dir.join("src"))?;
+
+    fs::write(
+        project_dir.join("Cargo.toml"),
+        r#"[package]
+name = "rustforger-selftest"
+version = "0.1.0"
+edition = "2021"
+"#,
+    )?;
+
+    fs::write(
+        project_dir.join("src").join("main.rs"),
+        r#"fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn main() {
+    println!("sum: {}", add(2, 3));
+}
+"#,
+    )?;
+
+    Ok(())
+}
+
+/// Run a cargo subcommand inside the sample project
+fn run_cargo(project_dir: &Path, subcommand: &str) -> Result<()> {
+    let output = Command::new("cargo")
+        .arg(subcommand)
+        .current_dir(project_dir)
+        .output()
+        .with_context(|| format!("Failed to spawn 'cargo {}'", subcommand))?;
+
+    ensure!(
+        output.status.success(),
+        "cargo {} exited with {}:\n{}",
+        subcommand,
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
+
+/// Check the executed sample actually produced a parseable trace of `add`
+fn verify_trace_output(trace_output: &Path) -> Result<()> {
+    ensure!(
+        trace_output.exists(),
+        "No trace file was written to {}",
+        trace_output.display()
+    );
+
+    let content = fs::read_to_string(trace_output)
+        .with_context(|| format!("Failed to read trace file: {}", trace_output.display()))?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&content)
+        .context("Trace file is not a JSON array")?;
+
+    let traced_add = entries.iter().any(|entry| {
+        entry
+            .get("root_node")
+            .and_then(|node| node.get("name"))
+            .and_then(|name| name.as_str())
+            == Some("add")
+    });
+    ensure!(traced_add, "Trace file contains no call record for 'add'");
+    Ok(())
+}
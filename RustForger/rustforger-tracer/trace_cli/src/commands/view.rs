@@ -0,0 +1,445 @@
+use anyhow::{Context, Result, ensure};
+use std::collections::HashSet;
+use std::path::Path;
+use trace_common::schema::{CallData, CallNode, TraceFile};
+
+/// Interactive trace viewer
+///
+/// Opens a two-pane terminal browser over a trace file: the call trees of
+/// one thread on the left, the selected call's inputs/outputs on the
+/// right. Keys: arrows/`j`/`k` move, Enter/Space expand or collapse,
+/// Tab switches thread, `/` searches call names (`n`/`N` jump between
+/// matches), `q` quits.
+pub fn run(trace_file: &Path) -> Result<()> {
+    ensure!(trace_file.exists(), "Trace file does not exist: {}", trace_file.display());
+
+    let content = std::fs::read_to_string(trace_file)
+        .with_context(|| format!("Failed to read trace file: {}", trace_file.display()))?;
+    let document: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| "Failed to parse trace JSON data")?;
+    let records = TraceFile::from_value(document)
+        .with_context(|| "Failed to load trace data")?
+        .records;
+    ensure!(!records.is_empty(), "Trace file holds no records: {}", trace_file.display());
+
+    let state = ViewerState::new(records);
+    tui::run(state)
+}
+
+/// One visible line of the call tree pane
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    /// Index of the record this node belongs to, within the current thread
+    pub record: usize,
+    /// Child indices from the record's root down to this node; empty for
+    /// the root itself
+    pub path: Vec<usize>,
+    pub depth: usize,
+    pub label: String,
+}
+
+/// Viewer state, independent of the terminal so navigation, search and
+/// thread switching stay unit-testable
+pub struct ViewerState {
+    /// All records, grouped as (thread_id, records of that thread)
+    threads: Vec<(String, Vec<CallData>)>,
+    current_thread: usize,
+    /// Expanded nodes of the current thread, keyed by (record, path)
+    expanded: HashSet<(usize, Vec<usize>)>,
+    selected: usize,
+    query: String,
+}
+
+impl ViewerState {
+    pub fn new(records: Vec<CallData>) -> Self {
+        let mut threads: Vec<(String, Vec<CallData>)> = Vec::new();
+        for record in records {
+            match threads.iter_mut().find(|(id, _)| *id == record.thread_id) {
+                Some((_, group)) => group.push(record),
+                None => threads.push((record.thread_id.clone(), vec![record])),
+            }
+        }
+        let mut state = Self {
+            threads,
+            current_thread: 0,
+            expanded: HashSet::new(),
+            selected: 0,
+            query: String::new(),
+        };
+        state.expand_roots();
+        state
+    }
+
+    /// Roots start expanded so a fresh screen shows one level of children
+    fn expand_roots(&mut self) {
+        self.expanded.clear();
+        for record in 0..self.current_records().len() {
+            self.expanded.insert((record, Vec::new()));
+        }
+    }
+
+    fn current_records(&self) -> &[CallData] {
+        &self.threads[self.current_thread].1
+    }
+
+    /// IDs of all threads in the file, in first-seen order
+    pub fn thread_ids(&self) -> Vec<&str> {
+        self.threads.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    pub fn current_thread_id(&self) -> &str {
+        &self.threads[self.current_thread].0
+    }
+
+    /// Switch to the next thread, wrapping around
+    pub fn next_thread(&mut self) {
+        self.current_thread = (self.current_thread + 1) % self.threads.len();
+        self.selected = 0;
+        self.expand_roots();
+    }
+
+    /// The rows currently visible in the tree pane
+    pub fn visible_rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for (record, call) in self.current_records().iter().enumerate() {
+            self.collect_rows(record, &call.root_node, Vec::new(), &mut rows);
+        }
+        rows
+    }
+
+    fn collect_rows(&self, record: usize, node: &CallNode, path: Vec<usize>, rows: &mut Vec<Row>) {
+        let expanded = self.expanded.contains(&(record, path.clone()));
+        let marker = if node.children.is_empty() {
+            "  "
+        } else if expanded {
+            "▾ "
+        } else {
+            "▸ "
+        };
+        rows.push(Row {
+            record,
+            depth: path.len(),
+            label: format!("{}{} ({}:{})", marker, node.name, short_file(&node.file), node.line),
+            path: path.clone(),
+        });
+        if expanded {
+            for (index, child) in node.children.iter().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(index);
+                self.collect_rows(record, child, child_path, rows);
+            }
+        }
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn move_down(&mut self) {
+        let last = self.visible_rows().len().saturating_sub(1);
+        self.selected = (self.selected + 1).min(last);
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Expand or collapse the selected node
+    pub fn toggle_expand(&mut self) {
+        let rows = self.visible_rows();
+        let Some(row) = rows.get(self.selected) else {
+            return;
+        };
+        let key = (row.record, row.path.clone());
+        if !self.expanded.remove(&key) {
+            self.expanded.insert(key);
+        }
+    }
+
+    /// The node behind the selected row
+    fn selected_node(&self) -> Option<(&CallData, &CallNode)> {
+        let rows = self.visible_rows();
+        let row = rows.get(self.selected)?;
+        let record = &self.current_records()[row.record];
+        let mut node = &record.root_node;
+        for &index in &row.path {
+            node = node.children.get(index)?;
+        }
+        Some((record, node))
+    }
+
+    /// Detail pane text for the selected row: record inputs/output for a
+    /// root, captured args/tags/events for a child node
+    pub fn detail(&self) -> String {
+        let Some((record, node)) = self.selected_node() else {
+            return String::new();
+        };
+        let mut out = format!("{}\n{}:{}\n", node.name, node.file, node.line);
+        if let Some(module_path) = &node.module_path {
+            out.push_str(&format!("in {}\n", module_path));
+        }
+        out.push('\n');
+        if std::ptr::eq(node, &record.root_node) {
+            out.push_str(&format!("inputs:\n{}\n\n", pretty(&record.inputs)));
+            out.push_str(&format!("output:\n{}\n", pretty(&record.output)));
+            if let Some(duration_ns) = record.duration_ns {
+                out.push_str(&format!("\nduration: {:.3} ms\n", duration_ns as f64 / 1_000_000.0));
+            }
+        } else if let Some(args) = &node.args {
+            out.push_str(&format!("args:\n{}\n", pretty(args)));
+        } else {
+            out.push_str("(no captured values for this call)\n");
+        }
+        if !node.tags.is_empty() {
+            out.push_str(&format!("\ntags:\n{}\n", pretty(&serde_json::Value::Object(node.tags.clone()))));
+        }
+        for event in &node.events {
+            out.push_str(&format!("\nevent {} @ {}:\n{}\n", event.name, event.timestamp_utc, pretty(&event.data)));
+        }
+        out
+    }
+
+    /// Set the search query and jump to its first match; returns how many
+    /// nodes of the current thread match
+    pub fn search(&mut self, query: &str) -> usize {
+        self.query = query.to_string();
+        let matches = self.match_count();
+        if matches > 0 {
+            self.next_match();
+        }
+        matches
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    fn match_count(&self) -> usize {
+        if self.query.is_empty() {
+            return 0;
+        }
+        self.current_records()
+            .iter()
+            .map(|call| {
+                call.root_node
+                    .flatten()
+                    .iter()
+                    .filter(|node| node.name.contains(&self.query))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Jump to the next matching node after the selection, expanding its
+    /// ancestors so it becomes visible; wraps around
+    pub fn next_match(&mut self) {
+        self.jump(false);
+    }
+
+    /// Jump to the previous matching node, wrapping around
+    pub fn prev_match(&mut self) {
+        self.jump(true);
+    }
+
+    fn jump(&mut self, backwards: bool) {
+        if self.query.is_empty() {
+            return;
+        }
+        let mut matches: Vec<(usize, Vec<usize>)> = Vec::new();
+        for (record, call) in self.current_records().iter().enumerate() {
+            collect_matches(&call.root_node, &self.query, record, Vec::new(), &mut matches);
+        }
+        if matches.is_empty() {
+            return;
+        }
+        if backwards {
+            matches.reverse();
+        }
+        let current = self
+            .visible_rows()
+            .get(self.selected)
+            .map(|row| (row.record, row.path.clone()));
+        let after_current = matches.iter().position(|key| Some(key) == current.as_ref());
+        let target = match after_current {
+            Some(position) => matches[(position + 1) % matches.len()].clone(),
+            None => matches[0].clone(),
+        };
+
+        // Expand every ancestor so the target row exists, then select it
+        for ancestors in 0..target.1.len() {
+            self.expanded.insert((target.0, target.1[..ancestors].to_vec()));
+        }
+        if let Some(index) = self
+            .visible_rows()
+            .iter()
+            .position(|row| row.record == target.0 && row.path == target.1)
+        {
+            self.selected = index;
+        }
+    }
+}
+
+fn collect_matches(
+    node: &CallNode,
+    query: &str,
+    record: usize,
+    path: Vec<usize>,
+    matches: &mut Vec<(usize, Vec<usize>)>,
+) {
+    if node.name.contains(query) {
+        matches.push((record, path.clone()));
+    }
+    for (index, child) in node.children.iter().enumerate() {
+        let mut child_path = path.clone();
+        child_path.push(index);
+        collect_matches(child, query, record, child_path, matches);
+    }
+}
+
+fn short_file(file: &str) -> &str {
+    file.split('/').next_back().unwrap_or(file)
+}
+
+fn pretty(value: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// The terminal half of the viewer: raw-mode setup, event loop, drawing.
+/// Kept thin — all decisions live in [`ViewerState`].
+mod tui {
+    use super::ViewerState;
+    use anyhow::Result;
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+    use crossterm::terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    };
+    use ratatui::backend::CrosstermBackend;
+    use ratatui::layout::{Constraint, Direction, Layout};
+    use ratatui::style::{Modifier, Style};
+    use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+    use ratatui::Terminal;
+
+    /// What the keyboard currently controls
+    enum InputMode {
+        Browse,
+        /// Typing a search query after `/`
+        Search(String),
+    }
+
+    pub fn run(mut state: ViewerState) -> Result<()> {
+        enable_raw_mode()?;
+        crossterm::execute!(std::io::stdout(), EnterAlternateScreen)?;
+        let result = event_loop(&mut state);
+        // Always restore the terminal, even when the loop failed
+        let _ = crossterm::execute!(std::io::stdout(), LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+        result
+    }
+
+    fn event_loop(state: &mut ViewerState) -> Result<()> {
+        let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+        let mut mode = InputMode::Browse;
+        let mut status = String::new();
+
+        loop {
+            draw(&mut terminal, state, &mode, &status)?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match &mut mode {
+                InputMode::Search(buffer) => match key.code {
+                    KeyCode::Enter => {
+                        let matches = state.search(&buffer.clone());
+                        status = format!("{} match(es) for {:?}", matches, state.query());
+                        mode = InputMode::Browse;
+                    }
+                    KeyCode::Esc => mode = InputMode::Browse,
+                    KeyCode::Backspace => {
+                        buffer.pop();
+                    }
+                    KeyCode::Char(c) => buffer.push(c),
+                    _ => {}
+                },
+                InputMode::Browse => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Down | KeyCode::Char('j') => state.move_down(),
+                    KeyCode::Up | KeyCode::Char('k') => state.move_up(),
+                    KeyCode::Enter | KeyCode::Char(' ') => state.toggle_expand(),
+                    KeyCode::Tab => {
+                        state.next_thread();
+                        status.clear();
+                    }
+                    KeyCode::Char('/') => mode = InputMode::Search(String::new()),
+                    KeyCode::Char('n') => state.next_match(),
+                    KeyCode::Char('N') => state.prev_match(),
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    fn draw(
+        terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+        state: &ViewerState,
+        mode: &InputMode,
+        status: &str,
+    ) -> Result<()> {
+        terminal.draw(|frame| {
+            let vertical = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)])
+                .split(frame.size());
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(vertical[0]);
+
+            let items: Vec<ListItem> = state
+                .visible_rows()
+                .into_iter()
+                .map(|row| ListItem::new(format!("{}{}", "  ".repeat(row.depth), row.label)))
+                .collect();
+            let threads = state.thread_ids().len();
+            let title = format!(
+                "Calls — thread {} ({} of {})",
+                state.current_thread_id(),
+                state
+                    .thread_ids()
+                    .iter()
+                    .position(|id| *id == state.current_thread_id())
+                    .unwrap_or(0)
+                    + 1,
+                threads,
+            );
+            let list = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            let mut list_state = ListState::default();
+            list_state.select(Some(state.selected_index()));
+            frame.render_stateful_widget(list, panes[0], &mut list_state);
+
+            let detail = Paragraph::new(state.detail())
+                .block(Block::default().borders(Borders::ALL).title("Detail"))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(detail, panes[1]);
+
+            let footer = match mode {
+                InputMode::Search(buffer) => format!("search: {}_", buffer),
+                InputMode::Browse => {
+                    if status.is_empty() {
+                        "q quit  j/k move  Enter expand  Tab thread  / search  n/N match".to_string()
+                    } else {
+                        status.to_string()
+                    }
+                }
+            };
+            frame.render_widget(Paragraph::new(footer), vertical[1]);
+        })?;
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@ use std::path::PathBuf;
 mod commands;
 mod utils;
 
-use commands::{instrument, revert, list_traced, setup, clean, run_flow};
+use commands::{analyze, convert, diff, filter, import, instrument, merge, query, report, redact, revert, list_traced, setup, clean, run_flow, selftest, view};
 use utils::config::PropagationConfig;
 
 #[derive(Parser)]
@@ -22,8 +22,8 @@ enum Commands {
     /// Add tracing instrumentation to a specific function
     Instrument {
         /// Path to the Rust source file
-        #[arg(short, long)]
-        file: PathBuf,
+        #[arg(short, long, required_unless_present_any = ["from_check_json", "module"])]
+        file: Option<PathBuf>,
         
         /// Name(s) of the function(s) to instrument (ignored when --all is used)
         #[arg(short = 'n', long)]
@@ -32,7 +32,21 @@ enum Commands {
         /// Instrument all functions in the file
         #[arg(long, conflicts_with = "function")]
         all: bool,
-        
+
+        /// Instrument functions at warning/error locations from
+        /// `cargo check --message-format=json` output
+        #[arg(long, conflicts_with_all = ["file", "function", "all"], value_name = "DIAGNOSTICS_JSON")]
+        from_check_json: Option<PathBuf>,
+
+        /// Instrument every function in the module at this path,
+        /// submodules included (e.g. `crate::parser::lexer`)
+        #[arg(short = 'm', long, conflicts_with_all = ["file", "function", "all", "from_check_json"], value_name = "MODULE_PATH")]
+        module: Option<String>,
+
+        /// Project directory searched when --module is used
+        #[arg(short = 'd', long, default_value = ".", requires = "module")]
+        project_dir: PathBuf,
+
         /// Path for trace output file
         #[arg(short, long)]
         trace_output: Option<PathBuf>,
@@ -54,6 +68,143 @@ enum Commands {
         user_code_only: bool,
     },
     
+    /// Analyze a captured trace file
+    Analyze {
+        /// Path to the trace file to analyze
+        trace_file: PathBuf,
+
+        /// Report observed input/output JSON types per traced function
+        /// instead of the statistics table
+        #[arg(long)]
+        type_report: bool,
+
+        /// Show only the first N functions of the statistics table
+        #[arg(long, default_value_t = 10, value_name = "N")]
+        top: usize,
+
+        /// Statistic the table is sorted by, descending
+        #[arg(long, value_parser = ["calls", "time", "depth", "size"], default_value = "calls")]
+        sort: String,
+    },
+
+    /// Browse a captured trace file interactively
+    View {
+        /// Path to the trace file to view
+        trace_file: PathBuf,
+    },
+
+    /// Convert a trace file for standard visualization tooling
+    Convert {
+        /// Path to the trace file to convert
+        trace_file: PathBuf,
+
+        /// Target format
+        #[arg(long, value_parser = ["folded", "chrome", "otlp"])]
+        to: String,
+
+        /// Path for the converted output file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Compare two trace files call by call
+    Diff {
+        /// Trace file from the baseline build
+        old_file: PathBuf,
+
+        /// Trace file from the changed build
+        new_file: PathBuf,
+    },
+
+    /// Slice a trace file down to matching records and subtrees
+    Filter {
+        /// Path to the trace file to filter
+        trace_file: PathBuf,
+
+        /// Keep records whose tree contains a call matching this glob
+        /// (repeatable; `*` and `?` wildcards)
+        #[arg(long = "function", value_name = "GLOB")]
+        function: Vec<String>,
+
+        /// Keep only records captured on this thread ID
+        #[arg(long)]
+        thread: Option<String>,
+
+        /// Keep only records at or after this RFC 3339 timestamp
+        #[arg(long, value_name = "TIMESTAMP")]
+        since: Option<String>,
+
+        /// Keep only records at or before this RFC 3339 timestamp
+        #[arg(long, value_name = "TIMESTAMP")]
+        until: Option<String>,
+
+        /// Prune call trees below this depth (root is depth 1)
+        #[arg(long, value_name = "N")]
+        max_depth: Option<usize>,
+
+        /// Path for the filtered output file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Combine multiple trace files into one
+    Merge {
+        /// Trace files to merge, at least two
+        #[arg(required = true, num_args = 2..)]
+        inputs: Vec<PathBuf>,
+
+        /// Path for the merged output file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Extract JSON fragments from a trace file with a selector
+    Query {
+        /// Path to the trace file to query
+        trace_file: PathBuf,
+
+        /// Selector, e.g. 'calls[?root_node.name="handle_request"].inputs'
+        selector: String,
+    },
+
+    /// Render a trace file as a self-contained HTML report
+    Report {
+        /// Path to the trace file to render
+        trace_file: PathBuf,
+
+        /// Path for the HTML output file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Import a third-party trace file into the rustforger trace format
+    Import {
+        /// Path to the external trace file
+        input: PathBuf,
+
+        /// Source format of the input file
+        #[arg(long, value_parser = ["chrome", "otlp-json"])]
+        format: String,
+
+        /// Path for the converted trace file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Apply privacy redaction rules to an existing trace file
+    Redact {
+        /// Path to the trace file to redact
+        trace_file: PathBuf,
+
+        /// Path to the TOML rules file
+        #[arg(long)]
+        rules: PathBuf,
+
+        /// Path for the redacted output file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
     /// Remove all tracing instrumentation from files
     Revert {
         /// Path to file or directory to process
@@ -101,6 +252,17 @@ enum Commands {
         project_dir: PathBuf,
     },
     
+    /// Run an end-to-end smoke test of the toolchain against a sample project
+    Selftest {
+        /// Path to the trace tool root directory
+        #[arg(short, long)]
+        trace_tool_path: Option<PathBuf>,
+
+        /// Keep the temporary sample project for inspection
+        #[arg(long)]
+        keep: bool,
+    },
+
     /// Execute complete trace flow: setup, instrument, run, and optionally clean
     RunFlow {
         /// Test project directory (where the main executable runs)
@@ -157,21 +319,24 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Instrument { 
-            file, 
-            function, 
+        Commands::Instrument {
+            file,
+            function,
             all,
-            trace_output, 
-            propagate, 
-            max_depth, 
-            exclude, 
-            user_code_only 
+            from_check_json,
+            module,
+            project_dir,
+            trace_output,
+            propagate,
+            max_depth,
+            exclude,
+            user_code_only
         } => {
             // Validate arguments
-            if !all && function.is_empty() {
-                anyhow::bail!("Either --function or --all must be specified");
+            if from_check_json.is_none() && module.is_none() && !all && function.is_empty() {
+                anyhow::bail!("Either --function, --all, --module, or --from-check-json must be specified");
             }
-            
+
             let propagation_config = if propagate {
                 Some(PropagationConfig {
                     enabled: true,
@@ -182,18 +347,87 @@ fn main() -> Result<()> {
             } else {
                 None
             };
-            
-            if all {
-                instrument::run_all(&file, trace_output.as_deref(), propagation_config)
-                    .with_context(|| format!("Failed to instrument all functions in file: {}", 
-                                            file.display()))?;
+
+            if let Some(module_path) = module {
+                instrument::run_module(&project_dir, &module_path, trace_output.as_deref(), propagation_config)
+                    .with_context(|| format!("Failed to instrument module: {}", module_path))?;
+            } else if let Some(diagnostics_path) = from_check_json {
+                instrument::run_from_check_json(&diagnostics_path, trace_output.as_deref(), propagation_config)
+                    .with_context(|| format!("Failed to instrument from diagnostics file: {}",
+                                            diagnostics_path.display()))?;
             } else {
-                instrument::run_multiple(&file, &function, trace_output.as_deref(), propagation_config)
-                    .with_context(|| format!("Failed to instrument functions {:?} in file: {}", 
-                                            function, file.display()))?;
+                let file = file.expect("clap guarantees --file when --from-check-json is absent");
+                if all {
+                    instrument::run_all(&file, trace_output.as_deref(), propagation_config)
+                        .with_context(|| format!("Failed to instrument all functions in file: {}",
+                                                file.display()))?;
+                } else {
+                    instrument::run_multiple(&file, &function, trace_output.as_deref(), propagation_config)
+                        .with_context(|| format!("Failed to instrument functions {:?} in file: {}",
+                                                function, file.display()))?;
+                }
             }
         }
         
+        Commands::Analyze { trace_file, type_report, top, sort } => {
+            let sort = analyze::SortKey::parse(&sort)?;
+            analyze::run(&trace_file, type_report, top, sort)
+                .with_context(|| format!("Failed to analyze trace file: {}", trace_file.display()))?;
+        }
+
+        Commands::View { trace_file } => {
+            view::run(&trace_file)
+                .with_context(|| format!("Failed to view trace file: {}", trace_file.display()))?;
+        }
+
+        Commands::Convert { trace_file, to, output } => {
+            convert::run(&trace_file, &to, &output)
+                .with_context(|| format!("Failed to convert trace file: {}", trace_file.display()))?;
+        }
+
+        Commands::Diff { old_file, new_file } => {
+            diff::run(&old_file, &new_file)
+                .with_context(|| format!("Failed to diff trace files: {} vs {}",
+                                        old_file.display(), new_file.display()))?;
+        }
+
+        Commands::Filter { trace_file, function, thread, since, until, max_depth, output } => {
+            let options = filter::FilterOptions {
+                functions: function,
+                thread,
+                since,
+                until,
+                max_depth,
+            };
+            filter::run(&trace_file, &options, &output)
+                .with_context(|| format!("Failed to filter trace file: {}", trace_file.display()))?;
+        }
+
+        Commands::Merge { inputs, output } => {
+            merge::run(&inputs, &output)
+                .with_context(|| "Failed to merge trace files")?;
+        }
+
+        Commands::Query { trace_file, selector } => {
+            query::run(&trace_file, &selector)
+                .with_context(|| format!("Failed to query trace file: {}", trace_file.display()))?;
+        }
+
+        Commands::Report { trace_file, output } => {
+            report::run(&trace_file, &output)
+                .with_context(|| format!("Failed to render report for: {}", trace_file.display()))?;
+        }
+
+        Commands::Import { input, format, output } => {
+            import::run(&input, &format, &output)
+                .with_context(|| format!("Failed to import trace file: {}", input.display()))?;
+        }
+
+        Commands::Redact { trace_file, rules, output } => {
+            redact::run(&trace_file, &rules, &output)
+                .with_context(|| format!("Failed to redact trace file: {}", trace_file.display()))?;
+        }
+
         Commands::Revert { path } => {
             revert::run(&path)
                 .with_context(|| format!("Failed to revert tracing in: {}", path.display()))?;
@@ -227,6 +461,11 @@ fn main() -> Result<()> {
                                         project_dir.display()))?;
         }
         
+        Commands::Selftest { trace_tool_path, keep } => {
+            selftest::run(trace_tool_path.as_deref(), keep)
+                .with_context(|| "Selftest failed")?;
+        }
+
         Commands::RunFlow {
             test_project,
             target_project,
@@ -43,14 +43,14 @@ pub fn find_project_root(start_path: &Path) -> Result<PathBuf> {
 
 /// Check if file is a Rust source file
 pub fn is_rust_file(path: &Path) -> bool {
-    path.extension().map_or(false, |ext| ext == "rs")
+    path.extension().is_some_and(|ext| ext == "rs")
 }
 
 /// Check if directory should be skipped during traversal
 pub fn should_skip_directory(path: &Path) -> bool {
     path.file_name()
         .and_then(|name| name.to_str())
-        .map_or(false, |name| {
+        .is_some_and(|name| {
             matches!(name, "target" | ".git" | "node_modules" | ".vscode")
         })
 }
@@ -0,0 +1,84 @@
+//! On-disk cache of per-file function indexes.
+//!
+//! Collecting every function in a large file just to rank similarity
+//! suggestions is wasteful when the same file is hit across repeated CLI
+//! invocations. The index is keyed by a hash of the source text, so any edit
+//! to the file invalidates its cached entry automatically.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single function entry in the cached index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedFunction {
+    /// Complete function specification (e.g., "CollectLifetimes::new")
+    pub full_name: String,
+    /// Owning type for impl methods, `None` for standalone functions
+    pub type_name: Option<String>,
+}
+
+/// Cached function index for one source file
+#[derive(Debug, Serialize, Deserialize)]
+struct FunctionIndex {
+    /// Hash of the source text the index was built from
+    source_hash: String,
+    functions: Vec<IndexedFunction>,
+}
+
+/// Hash source text for cache keying (FNV-1a, stable across builds)
+pub fn source_hash(source: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in source.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Load the cached index for a file if it matches the given source hash
+pub fn load(file_path: &Path, expected_hash: &str) -> Option<Vec<IndexedFunction>> {
+    let content = fs::read_to_string(index_path(file_path)?).ok()?;
+    let index: FunctionIndex = serde_json::from_str(&content).ok()?;
+    if index.source_hash == expected_hash {
+        Some(index.functions)
+    } else {
+        None
+    }
+}
+
+/// Store a freshly built index; failures are ignored since the cache is
+/// purely an optimization
+pub fn store(file_path: &Path, source_hash: &str, functions: Vec<IndexedFunction>) {
+    let _ = try_store(file_path, source_hash, functions);
+}
+
+fn try_store(file_path: &Path, source_hash: &str, functions: Vec<IndexedFunction>) -> Result<()> {
+    let index_path = index_path(file_path)
+        .ok_or_else(|| anyhow::anyhow!("Cannot derive index path for: {}", file_path.display()))?;
+    if let Some(parent) = index_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let index = FunctionIndex {
+        source_hash: source_hash.to_string(),
+        functions,
+    };
+    fs::write(&index_path, serde_json::to_string(&index)?)?;
+    Ok(())
+}
+
+/// Location of the cache entry for a file: one JSON file per source path,
+/// named by a hash of the absolute path
+fn index_path(file_path: &Path) -> Option<PathBuf> {
+    let absolute = file_path
+        .canonicalize()
+        .unwrap_or_else(|_| file_path.to_path_buf());
+    let key = source_hash(&absolute.to_string_lossy());
+    Some(cache_dir().join(format!("{}.json", key)))
+}
+
+/// Directory holding all cached indexes
+fn cache_dir() -> PathBuf {
+    std::env::temp_dir().join("rustforger-index")
+}
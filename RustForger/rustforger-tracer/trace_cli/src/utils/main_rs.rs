@@ -156,10 +156,9 @@ fn find_mod_insert_position(lines: &[&str]) -> usize {
         } else {
             last_use + 1
         }
-    } else if let Some(first_non_use) = first_non_use_line {
-        first_non_use
     } else {
-        0 // Insert at the beginning if no suitable position found
+        // Insert at the first non-use item, or at the beginning if none found
+        first_non_use_line.unwrap_or_default()
     }
 }
 
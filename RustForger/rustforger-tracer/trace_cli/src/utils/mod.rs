@@ -1,4 +1,5 @@
 pub mod fs;
+pub mod function_index;
 pub mod cargo;
 pub mod config;
 pub mod main_rs;
@@ -0,0 +1,134 @@
+//! Tests for trace file analysis functionality
+
+use anyhow::Result;
+use serde_json::json;
+use trace_cli::commands::analyze::SortKey;
+
+mod common;
+use common::TestFixture;
+
+/// Test the type report over a small captured trace
+#[tokio::test]
+async fn type_report_infers_schemas() -> Result<()> {
+    let entries = vec![
+        json!({
+            "timestamp_utc": "2024-01-01T00:00:00Z",
+            "thread_id": "ThreadId(1)",
+            "root_node": {"call_id": 1, "name": "add", "file": "src/lib.rs", "line": 3, "children": []},
+            "inputs": {"a": 1, "b": 2},
+            "output": 3
+        }),
+        json!({
+            "timestamp_utc": "2024-01-01T00:00:01Z",
+            "thread_id": "ThreadId(1)",
+            "root_node": {"call_id": 2, "name": "add", "file": "src/lib.rs", "line": 3, "children": []},
+            "inputs": {"a": 4, "b": "oops"},
+            "output": null
+        }),
+    ];
+
+    let report = trace_cli::commands::analyze::generate_type_report(&entries);
+
+    assert!(report.contains("add (2 call(s))"), "Should count calls per function");
+    assert!(report.contains("a: number (2)"), "Should report input key types");
+    assert!(report.contains("b: number (1), string (1)"), "Should report mixed types");
+    assert!(report.contains("null rate: 50%"), "Should report output null rate");
+
+    Ok(())
+}
+
+/// Test running analyze against a trace file on disk
+#[tokio::test]
+async fn analyze_trace_file() -> Result<()> {
+    let fixture = TestFixture::new()?;
+
+    let trace = r#"[{
+        "timestamp_utc": "2024-01-01T00:00:00Z",
+        "thread_id": "ThreadId(1)",
+        "root_node": {"call_id": 1, "name": "work", "file": "src/main.rs", "line": 1, "children": []},
+        "inputs": {},
+        "output": "done"
+    }]"#;
+    let trace_file = fixture.create_rust_file("trace.json", trace)?;
+
+    let result = trace_cli::commands::analyze::run(&trace_file, true, 10, SortKey::Calls);
+    assert!(result.is_ok(), "Analysis should succeed: {:?}", result);
+
+    // Without --type-report the statistics table is the default mode
+    let result = trace_cli::commands::analyze::run(&trace_file, false, 10, SortKey::Calls);
+    assert!(result.is_ok(), "Default analysis should succeed: {:?}", result);
+
+    Ok(())
+}
+
+/// Build a record with a given function name, duration and tree depth
+fn record(name: &str, duration_ns: u64, depth: usize) -> trace_common::schema::CallData {
+    let mut node = json!({"name": name, "file": "src/lib.rs", "line": 3, "children": []});
+    for _ in 1..depth {
+        node = json!({"name": name, "file": "src/lib.rs", "line": 3, "children": [node]});
+    }
+    serde_json::from_value(json!({
+        "timestamp_utc": "2024-01-01T00:00:00Z",
+        "thread_id": "ThreadId(1)",
+        "root_node": node,
+        "inputs": {},
+        "output": null,
+        "duration_ns": duration_ns,
+    }))
+    .unwrap()
+}
+
+/// Test the per-function statistics table
+#[tokio::test]
+async fn stats_report_aggregates_per_function() -> Result<()> {
+    let records = vec![
+        record("frequent", 1_000_000, 1),
+        record("frequent", 3_000_000, 1),
+        record("deep_and_slow", 10_000_000, 4),
+    ];
+
+    let report = trace_cli::commands::analyze::generate_stats_report(&records, 10, SortKey::Calls);
+
+    let lines: Vec<&str> = report.lines().collect();
+    assert!(lines[0].contains("3 record(s), 2 function(s)"));
+    assert!(lines[3].starts_with("frequent"), "calls sort puts frequent first:
+{report}");
+    assert!(lines[3].contains("2"), "call count");
+    assert!(lines[3].contains("4.000"), "total ms");
+    assert!(lines[3].contains("2.000"), "avg ms");
+    assert!(lines[4].contains("10.000"), "deep_and_slow total ms");
+
+    let by_time = trace_cli::commands::analyze::generate_stats_report(&records, 10, SortKey::Time);
+    assert!(by_time.lines().nth(3).unwrap().starts_with("deep_and_slow"));
+
+    let by_depth = trace_cli::commands::analyze::generate_stats_report(&records, 10, SortKey::Depth);
+    assert!(by_depth.lines().nth(3).unwrap().starts_with("deep_and_slow"));
+
+    Ok(())
+}
+
+/// Test that --top truncates the table
+#[tokio::test]
+async fn stats_report_honors_top() -> Result<()> {
+    let records = vec![record("a", 1, 1), record("b", 1, 1), record("c", 1, 1)];
+
+    let report = trace_cli::commands::analyze::generate_stats_report(&records, 2, SortKey::Calls);
+    assert!(report.contains("... 1 more function(s) omitted"), "{report}");
+
+    Ok(())
+}
+
+/// Test records without durations
+#[tokio::test]
+async fn stats_report_handles_missing_durations() -> Result<()> {
+    let mut untimed = record("legacy", 0, 1);
+    untimed.duration_ns = None;
+
+    let report = trace_cli::commands::analyze::generate_stats_report(&[untimed], 10, SortKey::Time);
+    let row = report.lines().nth(3).unwrap();
+    assert!(row.starts_with("legacy"));
+    assert!(row.contains('-'), "untimed rows show a dash:
+{report}");
+
+    Ok(())
+}
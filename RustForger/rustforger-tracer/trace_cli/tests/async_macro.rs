@@ -0,0 +1,61 @@
+//! Tests for `#[rustforger_trace]` on async functions
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+async fn async_add(a: i32, b: i32) -> i32 {
+    tokio::task::yield_now().await;
+    a + b
+}
+
+#[rustforger_trace]
+async fn async_outer(x: i32) -> i32 {
+    async_add(x, 1).await * 2
+}
+
+#[rustforger_trace]
+async fn async_early_return(x: i32) -> Result<i32, String> {
+    if x < 0 {
+        return Err("negative".to_string());
+    }
+    tokio::task::yield_now().await;
+    Ok(x + 1)
+}
+
+#[rustforger_trace(propagate)]
+async fn async_orchestrate(x: i32) -> i32 {
+    let base = async_add(x, 1).await;
+    base * 2
+}
+
+#[tokio::test]
+async fn awaited_calls_are_propagated() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(async_orchestrate(4).await, 10);
+
+    // The child span brackets the await's completion, so `async_add`
+    // nests under its call-site span inside `async_orchestrate`
+    tracer.assert_call_path(&["async_orchestrate", "async_add"]);
+}
+
+#[tokio::test]
+async fn async_functions_record_on_completion() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(async_outer(4).await, 10);
+
+    tracer.assert_called("async_outer");
+    tracer.assert_called("async_add");
+}
+
+#[tokio::test]
+async fn async_early_return_still_records() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(async_early_return(-1).await, Err("negative".to_string()));
+    assert_eq!(async_early_return(1).await, Ok(2));
+
+    tracer.assert_call_count("async_early_return", 2);
+}
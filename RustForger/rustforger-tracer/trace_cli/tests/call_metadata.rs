@@ -0,0 +1,98 @@
+//! Tests for module path and column capture in call metadata
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+fn located(x: i32) -> i32 {
+    x * 2
+}
+
+#[test]
+fn instrumented_calls_record_module_path_and_column() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(located(3), 6);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "located")
+        .expect("located call should be recorded");
+
+    assert_eq!(record["root_node"]["module_path"], "call_metadata");
+    assert!(
+        record["root_node"]["column"].as_u64().is_some(),
+        "column should be recorded: {record}"
+    );
+}
+
+#[test]
+fn manual_spans_omit_module_path_and_column() {
+    let tracer = CapturedTracer::capture();
+
+    let guard = trace_runtime::tracer::interface::span_dynamic("by_hand", file!(), line!());
+    trace_runtime::tracer::interface::record_top_level_call(
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+    );
+    drop(guard);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "by_hand")
+        .expect("manual span should be recorded");
+
+    // The fields are skipped during serialization when absent
+    assert!(record["root_node"]["module_path"].is_null());
+    assert!(record["root_node"]["column"].is_null());
+}
+
+mod twin {
+    use trace_runtime::trace_macro::rustforger_trace;
+
+    #[rustforger_trace]
+    pub fn located(x: i32) -> i32 {
+        x * 2
+    }
+}
+
+#[test]
+fn call_site_ids_are_stable_and_distinguish_duplicate_names() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(located(1), 2);
+    assert_eq!(located(2), 4);
+    assert_eq!(twin::located(3), 6);
+
+    let calls = tracer.calls();
+    let ids: Vec<u64> = calls
+        .iter()
+        .filter(|record| record["root_node"]["name"] == "located")
+        .map(|record| record["root_node"]["call_site_id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 3);
+
+    // Repeated calls through the same site share an ID; the identically
+    // named function in the other module gets its own
+    assert_eq!(ids[0], ids[1]);
+    assert_ne!(ids[0], ids[2]);
+}
+
+#[test]
+fn call_site_ids_can_be_recomputed_from_the_recorded_metadata() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(located(4), 8);
+
+    let calls = tracer.calls();
+    let node = &calls[0]["root_node"];
+    let expected = trace_runtime::tracer::interface::call_site_hash(
+        node["name"].as_str().unwrap(),
+        node["file"].as_str().unwrap(),
+        node["line"].as_u64().unwrap() as u32,
+        node["column"].as_u64().unwrap() as u32,
+    );
+    assert_eq!(node["call_site_id"], expected);
+}
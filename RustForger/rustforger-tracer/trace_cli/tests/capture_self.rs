@@ -0,0 +1,60 @@
+//! Tests for the `capture_self` attribute argument
+
+use serde::Serialize;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[derive(Serialize)]
+struct Account {
+    balance: i64,
+}
+
+#[rustforger_trace(capture_self)]
+impl Account {
+    fn deposit(&mut self, amount: i64) -> i64 {
+        self.balance += amount;
+        self.balance
+    }
+
+    fn balance(&self) -> i64 {
+        self.balance
+    }
+}
+
+#[test]
+fn mut_receiver_is_snapshotted_before_and_after() {
+    let tracer = CapturedTracer::capture();
+
+    let mut account = Account { balance: 100 };
+    assert_eq!(account.deposit(50), 150);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "Account::deposit")
+        .expect("deposit call should be recorded");
+
+    // Entry state lands next to the other arguments, exit state next to
+    // the return value
+    assert_eq!(record["inputs"]["self"]["balance"], 100);
+    assert_eq!(record["inputs"]["amount"], 50);
+    assert_eq!(record["output"]["result"], 150);
+    assert_eq!(record["output"]["self_after"]["balance"], 150);
+}
+
+#[test]
+fn shared_receiver_is_snapshotted_on_entry_only() {
+    let tracer = CapturedTracer::capture();
+
+    let account = Account { balance: 7 };
+    assert_eq!(account.balance(), 7);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "Account::balance")
+        .expect("balance call should be recorded");
+
+    assert_eq!(record["inputs"]["self"]["balance"], 7);
+    assert_eq!(record["output"], 7);
+}
@@ -0,0 +1,59 @@
+//! Tests for the catch_panics macro option
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace(catch_panics)]
+fn explodes(input: i32) -> i32 {
+    if input < 0 {
+        panic!("negative input: {}", input);
+    }
+    input * 2
+}
+
+#[rustforger_trace]
+fn after_panic(x: i32) -> i32 {
+    x + 1
+}
+
+#[test]
+fn panic_payload_is_recorded_and_reraised() {
+    let tracer = CapturedTracer::capture();
+
+    let caught = std::panic::catch_unwind(|| explodes(-1));
+    assert!(caught.is_err(), "panic must still propagate");
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "explodes")
+        .expect("panicking call recorded");
+    assert_eq!(record["output"]["panic"], "negative input: -1");
+    assert_eq!(record["output"]["failed"], true);
+}
+
+#[test]
+fn call_stack_stays_balanced_after_panic() {
+    let tracer = CapturedTracer::capture();
+
+    let _ = std::panic::catch_unwind(|| explodes(-7));
+    assert_eq!(after_panic(1), 2);
+
+    // The next traced call must be recorded as a fresh root, not as a
+    // child left dangling under the panicked frame
+    tracer.assert_call_path(&["after_panic"]);
+}
+
+#[test]
+fn successful_calls_record_normally() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(explodes(21), 42);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "explodes")
+        .expect("call recorded");
+    assert_eq!(record["output"], 42);
+}
@@ -0,0 +1,77 @@
+//! Tests for `#[cfg]` passthrough and the `debug_only` option
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+// The cfg attribute survives expansion, so this copy is compiled out and
+// the plain fallback below is the one that runs
+#[rustforger_trace]
+#[cfg(any())]
+fn pick(x: i32) -> i32 {
+    x
+}
+
+#[cfg(not(any()))]
+fn pick(x: i32) -> i32 {
+    x + 1
+}
+
+#[rustforger_trace(debug_only)]
+fn debug_gated(x: i32) -> i32 {
+    x * 2
+}
+
+struct Meter {
+    total: i32,
+}
+
+impl Meter {
+    fn new() -> Self {
+        Meter { total: 0 }
+    }
+}
+
+#[rustforger_trace(debug_only)]
+impl Meter {
+    fn add(&mut self, amount: i32) -> i32 {
+        self.total += amount;
+        self.total
+    }
+}
+
+#[test]
+fn cfg_attributes_on_the_function_are_preserved() {
+    let tracer = CapturedTracer::capture();
+
+    // The instrumented copy was cfg'd away entirely
+    assert_eq!(pick(1), 2);
+
+    assert!(tracer.calls().is_empty());
+}
+
+#[test]
+fn debug_only_functions_are_traced_under_debug_assertions() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(debug_gated(4), 8);
+
+    // Test builds carry debug assertions, so the instrumented copy runs;
+    // the release half of the dual emission is exercised at compile time
+    if cfg!(debug_assertions) {
+        tracer.assert_call_count("debug_gated", 1);
+    } else {
+        assert!(tracer.calls().is_empty());
+    }
+}
+
+#[test]
+fn debug_only_methods_compile_in_both_halves() {
+    let tracer = CapturedTracer::capture();
+
+    let mut meter = Meter::new();
+    assert_eq!(meter.add(5), 5);
+
+    if cfg!(debug_assertions) {
+        tracer.assert_call_count("Meter::add", 1);
+    }
+}
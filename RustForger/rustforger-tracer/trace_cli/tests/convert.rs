@@ -0,0 +1,92 @@
+//! Tests for trace export to visualization formats
+
+use anyhow::Result;
+use serde_json::json;
+
+mod common;
+use common::TestFixture;
+
+fn records() -> Vec<trace_common::schema::CallData> {
+    let entry = json!({
+        "timestamp_utc": "2024-01-01T00:00:00Z",
+        "thread_id": "ThreadId(1)",
+        "trace_id": "0123456789abcdef0123456789abcdef",
+        "root_node": {
+            "name": "outer", "file": "src/lib.rs", "line": 3,
+            "span_id": "00000000000000aa",
+            "children": [
+                {"name": "first", "file": "src/lib.rs", "line": 9,
+                 "span_id": "00000000000000bb", "children": []},
+                {"name": "second", "file": "src/lib.rs", "line": 21,
+                 "span_id": "00000000000000cc", "children": []},
+            ],
+        },
+        "inputs": {},
+        "output": null,
+        "duration_ns": 8_000_000,
+    });
+    vec![
+        serde_json::from_value(entry.clone()).unwrap(),
+        serde_json::from_value(entry).unwrap(),
+    ]
+}
+
+#[test]
+fn folded_stacks_aggregate_call_counts() {
+    let folded = trace_cli::commands::convert::to_folded(&records());
+
+    let lines: Vec<&str> = folded.lines().collect();
+    assert_eq!(lines, [
+        "outer 2",
+        "outer;first 2",
+        "outer;second 2",
+    ]);
+}
+
+#[test]
+fn chrome_events_nest_children_inside_the_root_interval() {
+    let chrome = trace_cli::commands::convert::to_chrome(&records());
+
+    let events = chrome["traceEvents"].as_array().unwrap();
+    assert_eq!(events.len(), 6);
+    let root = &events[0];
+    assert_eq!(root["ph"], "X");
+    assert_eq!(root["name"], "outer");
+    assert_eq!(root["dur"], 8_000);
+
+    let first = &events[1];
+    assert_eq!(first["dur"], 4_000, "children split the root evenly");
+    assert!(first["ts"].as_u64().unwrap() >= root["ts"].as_u64().unwrap());
+    let second = &events[2];
+    assert_eq!(second["ts"].as_u64().unwrap() - first["ts"].as_u64().unwrap(), 4_000);
+}
+
+#[test]
+fn otlp_spans_keep_recorded_correlation_ids() {
+    let otlp = trace_cli::commands::convert::to_otlp(&records());
+
+    let spans = otlp["resourceSpans"][0]["scopeSpans"][0]["spans"].as_array().unwrap();
+    assert_eq!(spans.len(), 6);
+    assert_eq!(spans[0]["traceId"], "0123456789abcdef0123456789abcdef");
+    assert_eq!(spans[0]["spanId"], "00000000000000aa");
+    assert!(spans[0].get("parentSpanId").is_none(), "roots have no parent");
+    assert_eq!(spans[1]["parentSpanId"], "00000000000000aa");
+    assert_eq!(spans[2]["spanId"], "00000000000000cc");
+}
+
+/// Test the full command against a file on disk
+#[test]
+fn convert_writes_the_requested_format() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let trace = serde_json::to_string(&records())?;
+    let trace_file = fixture.create_rust_file("trace.json", &trace)?;
+
+    let output = fixture.path().join("out.folded");
+    trace_cli::commands::convert::run(&trace_file, "folded", &output)?;
+    assert!(std::fs::read_to_string(&output)?.contains("outer;first 2"));
+
+    let bad = trace_cli::commands::convert::run(&trace_file, "svg", &output);
+    assert!(bad.is_err(), "unknown targets are rejected");
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Tests for trace/span correlation IDs on recorded calls
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+use trace_runtime::tracer::interface::set_trace_id;
+
+#[rustforger_trace(propagate)]
+fn outer() -> i32 {
+    inner() + 1
+}
+
+#[rustforger_trace]
+fn inner() -> i32 {
+    41
+}
+
+fn is_hex(id: &str, len: usize) -> bool {
+    id.len() == len && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[test]
+fn records_carry_a_shared_trace_id_and_distinct_span_ids() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(outer(), 42);
+    assert_eq!(outer(), 42);
+
+    let calls = tracer.calls();
+    let first_trace_id = calls[0]["trace_id"].as_str().unwrap();
+    assert!(is_hex(first_trace_id, 32), "trace id: {first_trace_id}");
+    for record in &calls {
+        assert_eq!(record["trace_id"], first_trace_id, "one process, one trace");
+    }
+
+    let outer_records: Vec<_> = calls
+        .iter()
+        .filter(|record| record["root_node"]["name"] == "outer")
+        .collect();
+    assert_eq!(outer_records.len(), 2);
+
+    let mut span_ids = Vec::new();
+    for record in outer_records {
+        let root = &record["root_node"];
+        for node in [root, &root["children"][0]] {
+            let span_id = node["span_id"].as_str().unwrap();
+            assert!(is_hex(span_id, 16), "span id: {span_id}");
+            span_ids.push(span_id.to_string());
+        }
+    }
+    span_ids.sort();
+    span_ids.dedup();
+    assert_eq!(span_ids.len(), 4, "every span gets its own ID");
+}
+
+#[test]
+fn an_adopted_trace_id_is_stamped_on_records() {
+    let tracer = CapturedTracer::capture();
+    set_trace_id("0123456789abcdef0123456789abcdef");
+
+    let _ = inner();
+
+    let calls = tracer.calls();
+    assert_eq!(calls[0]["trace_id"], "0123456789abcdef0123456789abcdef");
+}
@@ -0,0 +1,45 @@
+//! Tests for the `crate_path` attribute redirecting generated paths
+//!
+//! The generated code must reach the runtime solely through the renamed
+//! root, so this file aliases the crate instead of `use`-ing it.
+
+extern crate trace_runtime as my_facade;
+
+use my_facade::test_support::CapturedTracer;
+use my_facade::trace_macro::rustforger_trace;
+
+#[rustforger_trace(crate_path = "my_facade")]
+fn behind_facade(x: i32) -> i32 {
+    x * 3
+}
+
+#[rustforger_trace(propagate, crate_path = "my_facade")]
+fn facade_orchestrate(x: i32) -> i32 {
+    behind_facade(x) + 1
+}
+
+#[test]
+fn generated_code_uses_the_renamed_root() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(behind_facade(2), 6);
+
+    tracer.assert_call_count("behind_facade", 1);
+    let calls = tracer.calls();
+    assert_eq!(calls[0]["inputs"]["x"], 2);
+    assert_eq!(calls[0]["output"], 6);
+}
+
+#[test]
+fn propagation_also_goes_through_the_renamed_root() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(facade_orchestrate(3), 10);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "facade_orchestrate")
+        .expect("facade_orchestrate call should be recorded");
+    assert_eq!(record["root_node"]["children"][0]["name"], "behind_facade");
+}
@@ -0,0 +1,38 @@
+//! Tests for per-argument serializer overrides
+
+use serde_json::Value;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+/// Stand-in for a handle that implements neither Serialize nor Debug
+struct Conn {
+    url: String,
+}
+
+fn summarize_conn(conn: &Conn) -> Value {
+    Value::String(format!("conn:{}", conn.url))
+}
+
+#[rustforger_trace(serialize(conn = "summarize_conn"))]
+fn run_query(conn: Conn, query: String) -> usize {
+    conn.url.len() + query.len()
+}
+
+#[test]
+fn custom_serializer_is_applied_to_named_arg() {
+    let tracer = CapturedTracer::capture();
+
+    let conn = Conn {
+        url: "db://localhost".to_string(),
+    };
+    assert_eq!(run_query(conn, "select 1".to_string()), 22);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "run_query")
+        .expect("run_query call should be recorded");
+
+    assert_eq!(record["inputs"]["conn"], "conn:db://localhost");
+    assert_eq!(record["inputs"]["query"], "select 1");
+}
@@ -0,0 +1,141 @@
+//! Platform default-path resolution tests for auto-save
+//!
+//! These mutate process-global environment variables, so every test takes a
+//! shared lock to keep the assertions deterministic under parallel execution.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use trace_runtime::tracer::interface;
+use trace_runtime::tracer::{AutoSaveConfig, TRACE_OUTPUT_ENV};
+
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Snapshot and clear the variables that influence path resolution,
+/// restoring them on drop
+struct EnvGuard {
+    saved: Vec<(&'static str, Option<String>)>,
+}
+
+impl EnvGuard {
+    fn new() -> Self {
+        let vars = [
+            TRACE_OUTPUT_ENV,
+            "XDG_STATE_HOME",
+            "XDG_DATA_HOME",
+            "APPDATA",
+            "HOME",
+        ];
+        let saved = vars
+            .iter()
+            .map(|name| {
+                let value = std::env::var(name).ok();
+                std::env::remove_var(name);
+                (*name, value)
+            })
+            .collect();
+        Self { saved }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        let _ = interface::clear_default_path_resolver();
+        for (name, value) in &self.saved {
+            match value {
+                Some(value) => std::env::set_var(name, value),
+                None => std::env::remove_var(name),
+            }
+        }
+    }
+}
+
+#[test]
+fn env_override_beats_everything() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    let _env = EnvGuard::new();
+
+    std::env::set_var(TRACE_OUTPUT_ENV, "/tmp/explicit_trace.json");
+    interface::set_default_path_resolver(|| PathBuf::from("/tmp/resolver_trace.json")).unwrap();
+
+    assert_eq!(
+        AutoSaveConfig::default_path(),
+        PathBuf::from("/tmp/explicit_trace.json")
+    );
+}
+
+#[test]
+fn registered_resolver_beats_platform_directories() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    let _env = EnvGuard::new();
+
+    std::env::set_var("HOME", "/home/tester");
+    interface::set_default_path_resolver(|| PathBuf::from("/var/traces/embedded.json")).unwrap();
+
+    assert_eq!(
+        AutoSaveConfig::default_path(),
+        PathBuf::from("/var/traces/embedded.json")
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn linux_prefers_xdg_state_home() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    let _env = EnvGuard::new();
+
+    std::env::set_var("HOME", "/home/tester");
+    std::env::set_var("XDG_DATA_HOME", "/home/tester/.local/share");
+    std::env::set_var("XDG_STATE_HOME", "/home/tester/.local/state");
+
+    assert_eq!(
+        AutoSaveConfig::default_path(),
+        PathBuf::from("/home/tester/.local/state/rust-tracer/trace_output.json")
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn linux_falls_back_to_xdg_data_home_then_home() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    let _env = EnvGuard::new();
+
+    std::env::set_var("HOME", "/home/tester");
+    std::env::set_var("XDG_DATA_HOME", "/home/tester/.local/share");
+    assert_eq!(
+        AutoSaveConfig::default_path(),
+        PathBuf::from("/home/tester/.local/share/rust-tracer/trace_output.json")
+    );
+
+    std::env::remove_var("XDG_DATA_HOME");
+    assert_eq!(
+        AutoSaveConfig::default_path(),
+        PathBuf::from("/home/tester/.local/share/rust-tracer/trace_output.json")
+    );
+}
+
+#[cfg(target_os = "macos")]
+#[test]
+fn macos_uses_application_support() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    let _env = EnvGuard::new();
+
+    std::env::set_var("HOME", "/Users/tester");
+    assert_eq!(
+        AutoSaveConfig::default_path(),
+        PathBuf::from("/Users/tester/Library/Application Support/rust-tracer/trace_output.json")
+    );
+}
+
+#[cfg(target_os = "windows")]
+#[test]
+fn windows_uses_appdata() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    let _env = EnvGuard::new();
+
+    std::env::set_var("APPDATA", r"C:\Users\tester\AppData\Roaming");
+    assert_eq!(
+        AutoSaveConfig::default_path(),
+        PathBuf::from(r"C:\Users\tester\AppData\Roaming\rust-tracer\trace_output.json")
+    );
+}
@@ -0,0 +1,72 @@
+//! Tests for the trace diff command
+
+use serde_json::json;
+use trace_cli::commands::diff::generate_diff_report;
+
+fn record(name: &str, inputs: serde_json::Value, output: serde_json::Value) -> trace_common::schema::CallData {
+    serde_json::from_value(json!({
+        "timestamp_utc": "2024-01-01T00:00:00Z",
+        "thread_id": "ThreadId(1)",
+        "root_node": {"name": name, "file": "src/lib.rs", "line": 3, "children": []},
+        "inputs": inputs,
+        "output": output,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn identical_traces_report_no_differences() {
+    let records = vec![record("add", json!({"a": 1}), json!(2))];
+
+    let report = generate_diff_report(&records, &records);
+    assert!(report.contains("No differences found."), "{report}");
+    assert!(report.contains("0 changed, 0 added, 0 removed, 1 unchanged"));
+}
+
+#[test]
+fn changed_inputs_and_outputs_are_reported_per_path() {
+    let old = vec![record("add", json!({"a": 1, "b": 2}), json!(3))];
+    let new = vec![record("add", json!({"a": 1, "b": 5}), json!(6))];
+
+    let report = generate_diff_report(&old, &new);
+    assert!(report.contains("~ add #0"), "{report}");
+    assert!(report.contains("inputs.b: 2 -> 5"));
+    assert!(report.contains("output: 3 -> 6"));
+    assert!(report.contains("1 changed, 0 added, 0 removed, 0 unchanged"));
+}
+
+#[test]
+fn extra_and_missing_calls_are_added_and_removed() {
+    let old = vec![
+        record("add", json!({}), json!(null)),
+        record("legacy", json!({}), json!(null)),
+    ];
+    let new = vec![
+        record("add", json!({}), json!(null)),
+        record("add", json!({}), json!(null)),
+    ];
+
+    let report = generate_diff_report(&old, &new);
+    assert!(report.contains("+ add #1 (added)"), "{report}");
+    assert!(report.contains("- legacy #0 (removed)"));
+    assert!(report.contains("0 changed, 1 added, 1 removed, 1 unchanged"));
+}
+
+#[test]
+fn tree_shape_changes_name_the_first_divergence() {
+    let mut old = record("outer", json!({}), json!(null));
+    old.root_node.children = vec![serde_json::from_value(json!(
+        {"name": "parse", "file": "src/lib.rs", "line": 9, "children": []}
+    ))
+    .unwrap()];
+    let mut new = old.clone();
+    new.root_node.children[0].name = "parse_v2".to_string();
+    new.root_node.children.push(serde_json::from_value(json!(
+        {"name": "validate", "file": "src/lib.rs", "line": 20, "children": []}
+    ))
+    .unwrap());
+
+    let report = generate_diff_report(&[old], &[new]);
+    assert!(report.contains("tree: 2 -> 3 call(s)"), "{report}");
+    assert!(report.contains("first divergence: parse -> parse_v2"));
+}
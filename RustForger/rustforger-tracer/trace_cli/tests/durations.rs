@@ -0,0 +1,73 @@
+//! Tests for call durations measured by the macro expansion
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+fn sleepy(ms: u64) -> u64 {
+    std::thread::sleep(std::time::Duration::from_millis(ms));
+    ms
+}
+
+#[rustforger_trace(min_duration_ms = 10)]
+fn maybe_slow(ms: u64) -> u64 {
+    std::thread::sleep(std::time::Duration::from_millis(ms));
+    ms
+}
+
+#[test]
+fn fast_calls_below_threshold_are_not_recorded() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(maybe_slow(0), 0);
+
+    assert_eq!(tracer.call_count("maybe_slow"), 0);
+}
+
+#[test]
+fn slow_calls_above_threshold_are_recorded() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(maybe_slow(25), 25);
+
+    tracer.assert_call_count("maybe_slow", 1);
+}
+
+#[test]
+fn macro_records_elapsed_wall_time() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(sleepy(20), 20);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "sleepy")
+        .expect("sleepy call recorded");
+
+    let duration_ns = record["duration_ns"].as_u64().expect("duration recorded");
+    assert!(
+        duration_ns >= 20_000_000,
+        "slept 20ms but recorded {}ns",
+        duration_ns
+    );
+}
+
+#[test]
+fn manual_records_have_no_duration() {
+    let tracer = CapturedTracer::capture();
+
+    let guard = trace_runtime::tracer::interface::span_dynamic("manual", file!(), line!());
+    trace_runtime::tracer::interface::record_top_level_call(
+        serde_json::Value::Null,
+        serde_json::Value::Null,
+    );
+    drop(guard);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "manual")
+        .expect("manual call recorded");
+    assert!(record.get("duration_ns").is_none());
+}
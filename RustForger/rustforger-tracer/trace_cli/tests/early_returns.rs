@@ -0,0 +1,75 @@
+//! Tests that early `return` and `?` exits still record output
+//!
+//! The expansion evaluates the body inside an inner closure, so every exit
+//! path funnels through the recording epilogue instead of skipping it.
+
+use serde_json::Value;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+fn validate(len: usize) -> Result<usize, String> {
+    if len == 0 {
+        return Err("empty input".to_string());
+    }
+    Ok(len)
+}
+
+#[rustforger_trace]
+fn parse_and_double(raw: &str) -> Result<i32, String> {
+    let parsed = raw.parse::<i32>().map_err(|e| e.to_string())?;
+    Ok(parsed * 2)
+}
+
+#[rustforger_trace]
+fn clamp_or_bail(x: i32) -> i32 {
+    if x < 0 {
+        return 0;
+    }
+    x.min(100)
+}
+
+fn outputs_of(tracer: &CapturedTracer, fn_name: &str) -> Vec<Value> {
+    tracer
+        .calls()
+        .into_iter()
+        .filter(|record| record["root_node"]["name"] == fn_name)
+        .map(|record| record["output"].clone())
+        .collect()
+}
+
+#[test]
+fn early_return_err_is_recorded() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(validate(0), Err("empty input".to_string()));
+    assert_eq!(validate(3), Ok(3));
+
+    let outputs = outputs_of(&tracer, "validate");
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[0]["failed"], true);
+    assert_eq!(outputs[1]["ok"], 3);
+}
+
+#[test]
+fn question_mark_exit_is_recorded() {
+    let tracer = CapturedTracer::capture();
+
+    assert!(parse_and_double("oops").is_err());
+    assert_eq!(parse_and_double("21"), Ok(42));
+
+    let outputs = outputs_of(&tracer, "parse_and_double");
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs[0]["failed"], true);
+    assert_eq!(outputs[1]["ok"], 42);
+}
+
+#[test]
+fn early_return_value_is_recorded() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(clamp_or_bail(-5), 0);
+
+    let outputs = outputs_of(&tracer, "clamp_or_bail");
+    assert_eq!(outputs, vec![Value::from(0)]);
+}
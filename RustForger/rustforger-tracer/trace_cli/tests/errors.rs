@@ -0,0 +1,34 @@
+//! Tests for error conversion between the crates
+
+use trace_runtime::tracer::TraceError;
+
+#[test]
+fn runtime_errors_convert_into_the_shared_type() {
+    let cases: Vec<(TraceError, &str)> = vec![
+        (TraceError::LockPoisoned, "Configuration error"),
+        (TraceError::TracingSetup("no subscriber".to_string()), "Configuration error"),
+        (
+            TraceError::Io(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied")),
+            "IO error",
+        ),
+    ];
+
+    for (err, expected_prefix) in cases {
+        let shared: trace_common::Error = err.into();
+        assert!(
+            shared.to_string().starts_with(expected_prefix),
+            "{shared} should start with {expected_prefix}"
+        );
+    }
+}
+
+#[test]
+fn anyhow_contexts_absorb_the_shared_type() {
+    fn fails() -> anyhow::Result<()> {
+        Err(trace_common::Error::Config("bad".to_string()))?;
+        Ok(())
+    }
+
+    let message = format!("{:#}", fails().unwrap_err());
+    assert!(message.contains("Configuration error: bad"));
+}
@@ -0,0 +1,40 @@
+//! Tests for the trace_block! / trace_closure! expression macros
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::{trace_block, trace_closure};
+
+#[test]
+fn trace_block_records_and_yields_value() {
+    let tracer = CapturedTracer::capture();
+
+    let x = trace_block!("parse step", {
+        let base = 21;
+        base * 2
+    });
+    assert_eq!(x, 42);
+
+    tracer.assert_call_count("parse step", 1);
+}
+
+#[test]
+fn trace_closure_records_existing_callable() {
+    let tracer = CapturedTracer::capture();
+
+    let parse = || "header".len();
+    let len = trace_closure!("parse header", parse);
+    assert_eq!(len, 6);
+
+    tracer.assert_call_count("parse header", 1);
+}
+
+#[test]
+fn nested_blocks_form_a_call_path() {
+    let tracer = CapturedTracer::capture();
+
+    let total = trace_block!("outer phase", {
+        trace_block!("inner phase", { 1 + 2 })
+    });
+    assert_eq!(total, 3);
+
+    tracer.assert_call_path(&["outer phase", "inner phase"]);
+}
@@ -0,0 +1,108 @@
+//! Tests for the trace filter command
+
+use anyhow::Result;
+use serde_json::json;
+use trace_cli::commands::filter::{apply_filters, FilterOptions};
+
+mod common;
+use common::TestFixture;
+
+fn record(name: &str, thread: &str, timestamp: &str) -> trace_common::schema::CallData {
+    serde_json::from_value(json!({
+        "timestamp_utc": timestamp,
+        "thread_id": thread,
+        "root_node": {
+            "name": name, "file": "src/lib.rs", "line": 3,
+            "children": [
+                {"name": "inner_step", "file": "src/lib.rs", "line": 9, "children": [
+                    {"name": "leaf", "file": "src/lib.rs", "line": 15, "children": []},
+                ]},
+            ],
+        },
+        "inputs": {},
+        "output": null,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn function_globs_match_anywhere_in_the_tree() -> Result<()> {
+    let records = vec![
+        record("parse_json", "ThreadId(1)", "2024-01-01T00:00:00Z"),
+        record("render", "ThreadId(1)", "2024-01-01T00:00:01Z"),
+    ];
+
+    let options = FilterOptions { functions: vec!["parse*".to_string()], ..Default::default() };
+    let kept = apply_filters(records.clone(), &options)?;
+    assert_eq!(kept.len(), 1);
+    assert_eq!(kept[0].root_node.name, "parse_json");
+
+    // A glob matching only a nested call still keeps the whole record
+    let options = FilterOptions { functions: vec!["leaf".to_string()], ..Default::default() };
+    assert_eq!(apply_filters(records, &options)?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn thread_and_time_windows_select_records() -> Result<()> {
+    let records = vec![
+        record("a", "ThreadId(1)", "2024-01-01T00:00:00Z"),
+        record("b", "ThreadId(2)", "2024-01-01T00:00:05Z"),
+        record("c", "ThreadId(1)", "2024-01-01T00:00:10Z"),
+    ];
+
+    let options = FilterOptions { thread: Some("ThreadId(1)".to_string()), ..Default::default() };
+    assert_eq!(apply_filters(records.clone(), &options)?.len(), 2);
+
+    let options = FilterOptions {
+        since: Some("2024-01-01T00:00:03Z".to_string()),
+        until: Some("2024-01-01T00:00:07Z".to_string()),
+        ..Default::default()
+    };
+    let kept = apply_filters(records.clone(), &options)?;
+    assert_eq!(kept.len(), 1);
+    assert_eq!(kept[0].root_node.name, "b");
+
+    let options = FilterOptions { since: Some("not a time".to_string()), ..Default::default() };
+    assert!(apply_filters(records, &options).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn max_depth_prunes_surviving_trees() -> Result<()> {
+    let records = vec![record("outer", "ThreadId(1)", "2024-01-01T00:00:00Z")];
+
+    let options = FilterOptions { max_depth: Some(2), ..Default::default() };
+    let kept = apply_filters(records, &options)?;
+    assert_eq!(kept[0].root_node.depth(), 2);
+    assert!(kept[0].root_node.children[0].children.is_empty());
+
+    Ok(())
+}
+
+/// Test the full command preserves the header on disk
+#[test]
+fn filter_keeps_the_file_loadable_with_its_header() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let trace = json!([
+        {"schema_version": 1, "tool_version": "0.1.0", "start_time": "2024-01-01T00:00:00Z",
+         "hostname": "test", "cmdline": []},
+        record("keep_me", "ThreadId(1)", "2024-01-01T00:00:00Z"),
+        record("drop_me", "ThreadId(1)", "2024-01-01T00:00:01Z"),
+    ]);
+    let trace_file = fixture.create_rust_file("trace.json", &trace.to_string())?;
+
+    let options = FilterOptions { functions: vec!["keep_me".to_string()], ..Default::default() };
+    let output = fixture.path().join("filtered.json");
+    trace_cli::commands::filter::run(&trace_file, &options, &output)?;
+
+    let document: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    let filtered = trace_common::schema::TraceFile::from_value(document)?;
+    assert_eq!(filtered.header.schema_version, 1);
+    assert_eq!(filtered.records.len(), 1);
+    assert_eq!(filtered.records[0].root_node.name, "keep_me");
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Tests for per-function runtime enable/disable switches
+
+use trace_runtime::trace_macro::rustforger_trace;
+use trace_runtime::tracer::interface;
+
+#[rustforger_trace]
+fn noisy(x: i32) -> i32 {
+    x + 1
+}
+
+// Each test toggles its own function name so parallel execution cannot
+// race on the shared disabled set.
+#[test]
+fn disable_and_enable_round_trip() {
+    assert!(interface::function_enabled("noisy_toggle"));
+
+    interface::disable_function("noisy_toggle").unwrap();
+    assert!(!interface::function_enabled("noisy_toggle"));
+    assert!(interface::function_enabled("other_fn"));
+
+    interface::enable_function("noisy_toggle").unwrap();
+    assert!(interface::function_enabled("noisy_toggle"));
+}
+
+#[test]
+fn disabled_function_still_returns_its_value() {
+    interface::disable_function("noisy").unwrap();
+    assert_eq!(noisy(41), 42);
+    interface::enable_function("noisy").unwrap();
+}
+
+#[test]
+fn disabled_span_is_inactive() {
+    interface::disable_function("quiet_region").unwrap();
+    let guard = interface::span_dynamic("quiet_region", file!(), line!());
+    assert!(!guard.is_active());
+    drop(guard);
+    interface::enable_function("quiet_region").unwrap();
+}
@@ -0,0 +1,48 @@
+//! Tests for concrete type recording on generic functions
+
+use serde::Serialize;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+fn pair_up<A: Serialize, B: Serialize>(left: A, right: B) -> (A, B) {
+    (left, right)
+}
+
+#[rustforger_trace]
+fn plain(x: i32) -> i32 {
+    x
+}
+
+#[test]
+fn generic_parameters_record_their_concrete_types() {
+    let tracer = CapturedTracer::capture();
+
+    let _ = pair_up(1u32, "x".to_string());
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "pair_up")
+        .expect("pair_up call should be recorded");
+
+    assert_eq!(record["inputs"]["__generics"]["A"], "u32");
+    assert_eq!(record["inputs"]["__generics"]["B"], "alloc::string::String");
+    assert_eq!(record["inputs"]["left"], 1);
+    assert_eq!(record["inputs"]["right"], "x");
+}
+
+#[test]
+fn non_generic_functions_get_no_generics_key() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(plain(5), 5);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "plain")
+        .expect("plain call should be recorded");
+
+    assert!(record["inputs"].get("__generics").is_none());
+}
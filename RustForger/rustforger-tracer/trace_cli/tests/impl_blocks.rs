@@ -0,0 +1,65 @@
+//! Tests for `#[rustforger_trace]` on impl blocks and traits
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+struct Counter {
+    value: i32,
+}
+
+#[rustforger_trace]
+impl Counter {
+    fn new(start: i32) -> Self {
+        Counter { value: start }
+    }
+
+    fn add(&mut self, amount: i32) -> i32 {
+        self.value += amount;
+        self.value
+    }
+
+    fn get(&self) -> i32 {
+        self.value
+    }
+}
+
+#[rustforger_trace]
+trait Describe {
+    fn name(&self) -> String;
+
+    fn describe(&self) -> String {
+        format!("<{}>", self.name())
+    }
+}
+
+impl Describe for Counter {
+    fn name(&self) -> String {
+        "counter".to_string()
+    }
+}
+
+#[test]
+fn impl_methods_record_qualified_names() {
+    let tracer = CapturedTracer::capture();
+
+    let mut counter = Counter::new(10);
+    assert_eq!(counter.add(5), 15);
+    assert_eq!(counter.get(), 15);
+
+    tracer.assert_called("Counter::new");
+    tracer.assert_called("Counter::add");
+    tracer.assert_called("Counter::get");
+}
+
+#[test]
+fn trait_default_bodies_are_instrumented() {
+    let tracer = CapturedTracer::capture();
+
+    let counter = Counter::new(0);
+    assert_eq!(counter.describe(), "<counter>");
+
+    // Default bodies resolve the concrete Self type at runtime
+    tracer.assert_called("Counter::describe");
+    // Required methods without default bodies are left untouched
+    assert_eq!(tracer.call_count("Counter::name"), 0);
+}
@@ -0,0 +1,67 @@
+//! Tests for importing external trace formats
+
+use anyhow::Result;
+use serde_json::Value;
+
+mod common;
+use common::TestFixture;
+
+/// Test converting a Chrome trace with nested B/E events
+#[tokio::test]
+async fn import_chrome_trace() -> Result<()> {
+    let fixture = TestFixture::new()?;
+
+    let chrome = r#"{"traceEvents": [
+        {"ph": "B", "name": "outer", "ts": 1000000, "pid": 1, "tid": 1},
+        {"ph": "X", "name": "inner", "ts": 1000100, "dur": 50, "pid": 1, "tid": 1},
+        {"ph": "E", "ts": 1000500, "pid": 1, "tid": 1}
+    ]}"#;
+    let input = fixture.create_rust_file("chrome.json", chrome)?;
+    let output = fixture.path().join("converted.json");
+
+    trace_cli::commands::import::run(&input, "chrome", &output)?;
+
+    let converted: Vec<Value> = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    assert_eq!(converted.len(), 1, "Nested events should collapse to one entry");
+    assert_eq!(converted[0]["root_node"]["name"], "outer");
+    assert_eq!(converted[0]["root_node"]["children"][0]["name"], "inner");
+    assert_eq!(converted[0]["thread_id"], "chrome:1:1");
+
+    Ok(())
+}
+
+/// Test converting an OTLP/JSON span export with a parent/child pair
+#[tokio::test]
+async fn import_otlp_json_trace() -> Result<()> {
+    let fixture = TestFixture::new()?;
+
+    let otlp = r#"{"resourceSpans": [{"scopeSpans": [{"spans": [
+        {"traceId": "abc", "spanId": "01", "name": "root_span", "startTimeUnixNano": "1000000000"},
+        {"traceId": "abc", "spanId": "02", "parentSpanId": "01", "name": "child_span", "startTimeUnixNano": "1000000500"}
+    ]}]}]}"#;
+    let input = fixture.create_rust_file("otlp.json", otlp)?;
+    let output = fixture.path().join("converted.json");
+
+    trace_cli::commands::import::run(&input, "otlp-json", &output)?;
+
+    let converted: Vec<Value> = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    assert_eq!(converted.len(), 1, "Child span should nest under its parent");
+    assert_eq!(converted[0]["root_node"]["name"], "root_span");
+    assert_eq!(converted[0]["root_node"]["children"][0]["name"], "child_span");
+
+    Ok(())
+}
+
+/// Test that an unknown format is rejected
+#[tokio::test]
+async fn import_rejects_unknown_format() -> Result<()> {
+    let fixture = TestFixture::new()?;
+
+    let input = fixture.create_rust_file("trace.json", "[]")?;
+    let output = fixture.path().join("converted.json");
+
+    let result = trace_cli::commands::import::run(&input, "jaeger", &output);
+    assert!(result.is_err(), "Unknown formats should be rejected");
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! Tests for the `level` attribute argument and the runtime level filter
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+use trace_runtime::tracer::interface::{set_min_level, TraceLevel};
+
+#[rustforger_trace(level = "debug")]
+fn chatty(x: i32) -> i32 {
+    x
+}
+
+#[rustforger_trace(level = "warn")]
+fn important(x: i32) -> i32 {
+    x
+}
+
+#[rustforger_trace]
+fn regular(x: i32) -> i32 {
+    x
+}
+
+#[test]
+fn min_level_drops_spans_below_it() {
+    let tracer = CapturedTracer::capture();
+
+    set_min_level(TraceLevel::Info);
+    chatty(1);
+    regular(2);
+    important(3);
+    set_min_level(TraceLevel::Trace);
+
+    // Debug-level spans fall under the Info bound; info (the default) and
+    // warn spans pass
+    assert_eq!(tracer.call_count("chatty"), 0);
+    tracer.assert_call_count("regular", 1);
+    tracer.assert_call_count("important", 1);
+}
+
+#[test]
+fn default_min_level_records_everything() {
+    let tracer = CapturedTracer::capture();
+
+    chatty(1);
+    regular(2);
+    important(3);
+
+    tracer.assert_call_count("chatty", 1);
+    tracer.assert_call_count("regular", 1);
+    tracer.assert_call_count("important", 1);
+}
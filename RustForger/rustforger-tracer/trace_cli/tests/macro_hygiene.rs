@@ -0,0 +1,45 @@
+//! Regression tests for macro expansion hygiene
+//!
+//! The `#[rustforger_trace]` expansion introduces locals such as `__result`
+//! and `__trace_inputs`. These tests define user code that shadows those
+//! names to make sure the expansion neither collides with nor captures them.
+
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+fn shadows_result(x: i32) -> i32 {
+    let __result = x * 2;
+    __result + 1
+}
+
+#[rustforger_trace]
+fn shadows_trace_locals(label: String) -> String {
+    let __trace_inputs = "user-owned";
+    let __trace_guard = label.len();
+    let __trace_output = format!("{}:{}", __trace_inputs, __trace_guard);
+    format!("{}-{}", label, __trace_output)
+}
+
+#[rustforger_trace]
+fn shadows_in_nested_scope(x: i32) -> i32 {
+    let doubled = {
+        let __result = x;
+        __result * 2
+    };
+    doubled + 1
+}
+
+#[test]
+fn user_result_binding_is_not_captured() {
+    assert_eq!(shadows_result(10), 21);
+}
+
+#[test]
+fn user_trace_locals_are_not_captured() {
+    assert_eq!(shadows_trace_locals("abc".to_string()), "abc-user-owned:3");
+}
+
+#[test]
+fn nested_scope_shadowing_works() {
+    assert_eq!(shadows_in_nested_scope(7), 15);
+}
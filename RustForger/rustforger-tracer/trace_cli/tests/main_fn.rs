@@ -0,0 +1,57 @@
+//! Tests for the `fn main` wrapper with guaranteed flush
+//!
+//! This test mutates the trace output environment variable, so it stays
+//! alone in its own file.
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::tracer::TRACE_OUTPUT_ENV;
+
+mod app {
+    use trace_runtime::trace_macro::rustforger_trace;
+
+    #[rustforger_trace]
+    pub fn helper(x: i32) -> i32 {
+        x + 1
+    }
+
+    #[rustforger_trace]
+    pub fn main() -> Result<i32, String> {
+        Ok(helper(2))
+    }
+}
+
+#[test]
+fn main_wrapper_finalizes_to_the_default_path() {
+    let tracer = CapturedTracer::capture();
+
+    let path = std::env::temp_dir().join("rustforger_main_fn_test.json");
+    let _ = std::fs::remove_file(&path);
+    std::env::set_var(TRACE_OUTPUT_ENV, &path);
+
+    let result = app::main();
+
+    std::env::remove_var(TRACE_OUTPUT_ENV);
+    assert_eq!(result, Ok(3));
+
+    let contents = std::fs::read_to_string(&path).expect("finalized trace file");
+    let document: serde_json::Value = serde_json::from_str(&contents).expect("valid JSON");
+    let records = document.as_array().expect("header plus records");
+
+    // The helper call made it to disk; main itself is a wrapper, not a
+    // recorded call
+    assert!(
+        records
+            .iter()
+            .any(|record| record["root_node"]["name"] == "helper"),
+        "{document}"
+    );
+    assert!(
+        !records
+            .iter()
+            .any(|record| record["root_node"]["name"] == "main"),
+        "{document}"
+    );
+
+    let _ = std::fs::remove_file(&path);
+    drop(tracer);
+}
@@ -0,0 +1,73 @@
+//! Tests for the trace merge command
+
+use anyhow::Result;
+use serde_json::json;
+use trace_cli::commands::merge::merge_records;
+
+mod common;
+use common::TestFixture;
+
+fn record(name: &str, timestamp: &str) -> trace_common::schema::CallData {
+    serde_json::from_value(json!({
+        "timestamp_utc": timestamp,
+        "thread_id": "ThreadId(1)",
+        "root_node": {"name": name, "file": "src/lib.rs", "line": 3, "children": []},
+        "inputs": {},
+        "output": null,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn merged_records_interleave_by_timestamp() {
+    let merged = merge_records(vec![
+        ("run_a".to_string(), vec![
+            record("a_first", "2024-01-01T00:00:00Z"),
+            record("a_second", "2024-01-01T00:00:10Z"),
+        ]),
+        ("run_b".to_string(), vec![record("b_only", "2024-01-01T00:00:05Z")]),
+    ]);
+
+    let names: Vec<&str> = merged.iter().map(|r| r.root_node.name.as_str()).collect();
+    assert_eq!(names, ["a_first", "b_only", "a_second"]);
+}
+
+#[test]
+fn records_are_tagged_with_their_source_and_thread_prefix() {
+    let merged = merge_records(vec![
+        ("run_a".to_string(), vec![record("f", "2024-01-01T00:00:00Z")]),
+        ("run_b".to_string(), vec![record("f", "2024-01-01T00:00:00Z")]),
+    ]);
+
+    assert_eq!(merged[0].thread_id, "run_a:ThreadId(1)");
+    assert_eq!(merged[1].thread_id, "run_b:ThreadId(1)");
+    assert_eq!(merged[0].root_node.tags["source_file"], "run_a");
+    assert_eq!(merged[1].root_node.tags["source_file"], "run_b");
+}
+
+/// Test the full command writes a loadable merged file
+#[test]
+fn merge_writes_a_loadable_trace() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let a = fixture.create_rust_file(
+        "a.json",
+        &json!([record("from_a", "2024-01-01T00:00:01Z")]).to_string(),
+    )?;
+    let b = fixture.create_rust_file(
+        "b.json",
+        &json!([record("from_b", "2024-01-01T00:00:00Z")]).to_string(),
+    )?;
+
+    let output = fixture.path().join("merged.json");
+    trace_cli::commands::merge::run(&[a, b], &output)?;
+
+    let document: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    let merged = trace_common::schema::TraceFile::from_value(document)?;
+    assert_eq!(merged.records.len(), 2);
+    assert_eq!(merged.records[0].root_node.name, "from_b", "ordered by time");
+
+    // A single input is rejected
+    assert!(trace_cli::commands::merge::run(std::slice::from_ref(&output), &output).is_err());
+
+    Ok(())
+}
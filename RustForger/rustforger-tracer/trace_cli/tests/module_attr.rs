@@ -0,0 +1,64 @@
+//! Tests for `#[rustforger_trace]` applied to a whole module
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+mod pipeline {
+    pub fn stage_one(x: i32) -> i32 {
+        x + 1
+    }
+
+    pub struct Gauge {
+        pub reading: i32,
+    }
+
+    impl Gauge {
+        pub fn bump(&mut self) -> i32 {
+            self.reading += 1;
+            self.reading
+        }
+    }
+
+    pub mod inner {
+        pub fn stage_two(x: i32) -> i32 {
+            x * 2
+        }
+    }
+
+    // An explicitly configured function keeps its own attribute
+    #[super::rustforger_trace(no_inputs)]
+    pub fn opaque_stage(secret: i32) -> i32 {
+        secret
+    }
+}
+
+#[test]
+fn every_function_in_the_module_is_instrumented() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(pipeline::stage_one(1), 2);
+    assert_eq!(pipeline::inner::stage_two(2), 4);
+    let mut gauge = pipeline::Gauge { reading: 0 };
+    assert_eq!(gauge.bump(), 1);
+
+    tracer.assert_call_count("stage_one", 1);
+    tracer.assert_call_count("stage_two", 1);
+    tracer.assert_call_count("Gauge::bump", 1);
+}
+
+#[test]
+fn explicitly_attributed_items_keep_their_own_configuration() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(pipeline::opaque_stage(99), 99);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "opaque_stage")
+        .expect("opaque_stage call should be recorded");
+
+    // A double expansion would have captured the inputs
+    assert_eq!(record["inputs"], "<omitted>");
+}
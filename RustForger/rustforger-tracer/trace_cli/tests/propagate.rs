@@ -0,0 +1,155 @@
+//! Tests for propagate mode wrapping calls inside the traced body
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+struct Widget {
+    value: i32,
+}
+
+impl Widget {
+    fn doubled(&self) -> i32 {
+        self.value * 2
+    }
+}
+
+fn helper_value(x: i32) -> Widget {
+    Widget { value: x + 1 }
+}
+
+#[rustforger_trace(propagate)]
+fn orchestrate(x: i32) -> i32 {
+    let widget = helper_value(x);
+    widget.doubled()
+}
+
+#[rustforger_trace(propagate)]
+fn chained(x: i32) -> i32 {
+    helper_value(x).doubled()
+}
+
+#[rustforger_trace(propagate)]
+fn loop_and_match(count: i32) -> i32 {
+    let mut total = 0;
+    for i in 0..count {
+        let widget = helper_value(i);
+        total += widget.value;
+    }
+    match total {
+        0 => 0,
+        other => {
+            let widget = helper_value(other);
+            widget.value
+        }
+    }
+}
+
+#[rustforger_trace(propagate)]
+fn through_closure(x: i32) -> i32 {
+    let add_one = |v: i32| {
+        let widget = helper_value(v);
+        widget.value
+    };
+    add_one(x)
+}
+
+#[rustforger_trace(propagate, exclude("helper_"))]
+fn curated(x: i32) -> i32 {
+    let widget = helper_value(x);
+    widget.doubled()
+}
+
+#[rustforger_trace(propagate, max_depth = 1)]
+fn shallow(x: i32) -> i32 {
+    let widget = helper_value(x);
+    widget.doubled()
+}
+
+#[rustforger_trace(propagate, max_depth = 2)]
+fn depth_two(x: i32) -> i32 {
+    helper_value(x).doubled()
+}
+
+#[test]
+fn excluded_patterns_keep_matching_calls_untraced() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(curated(4), 10);
+
+    // `helper_value` matches the exclude pattern and gets no child span;
+    // the method call is still propagated
+    tracer.assert_call_path(&["curated", "doubled"]);
+    assert_eq!(tracer.call_count("helper_value"), 0);
+}
+
+#[test]
+fn max_depth_one_keeps_children_untraced() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(shallow(4), 10);
+
+    tracer.assert_call_count("shallow", 1);
+    assert_eq!(tracer.call_count("helper_value"), 0);
+    assert_eq!(tracer.call_count("doubled"), 0);
+}
+
+#[test]
+fn max_depth_two_cuts_nesting_beyond_bound() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(depth_two(4), 10);
+
+    // `doubled` opens at depth 2; the chained `helper_value` call inside
+    // its receiver would be depth 3 and is left untraced
+    tracer.assert_call_path(&["depth_two", "doubled"]);
+    assert_eq!(tracer.call_count("helper_value"), 0);
+}
+
+#[test]
+fn free_function_calls_become_child_spans() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(orchestrate(4), 10);
+
+    tracer.assert_call_path(&["orchestrate", "helper_value"]);
+}
+
+#[test]
+fn method_calls_become_child_spans() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(orchestrate(4), 10);
+
+    tracer.assert_call_path(&["orchestrate", "doubled"]);
+}
+
+#[test]
+fn calls_inside_loops_and_match_arms_are_propagated() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(loop_and_match(2), 4);
+
+    tracer.assert_call_path(&["loop_and_match", "helper_value"]);
+    // Two loop iterations plus the match arm
+    tracer.assert_call_count("helper_value", 3);
+}
+
+#[test]
+fn calls_inside_closures_are_propagated() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(through_closure(4), 5);
+
+    // The closure invocation itself is wrapped too, so the propagated call
+    // nests under it
+    tracer.assert_call_path(&["through_closure", "add_one", "helper_value"]);
+}
+
+#[test]
+fn chained_receivers_nest_spans() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(chained(4), 10);
+
+    tracer.assert_call_path(&["chained", "doubled", "helper_value"]);
+}
@@ -0,0 +1,62 @@
+//! Tests for the trace query selector DSL
+
+use serde_json::json;
+use trace_cli::commands::query::select;
+
+fn calls() -> serde_json::Value {
+    json!([
+        {
+            "timestamp_utc": "2024-01-01T00:00:00Z",
+            "thread_id": "ThreadId(1)",
+            "root_node": {"name": "handle_request", "file": "src/server.rs", "line": 10,
+                          "children": [{"name": "parse", "file": "src/parse.rs", "line": 5, "children": []}]},
+            "inputs": {"path": "/health"},
+            "output": 200,
+        },
+        {
+            "timestamp_utc": "2024-01-01T00:00:01Z",
+            "thread_id": "ThreadId(2)",
+            "root_node": {"name": "background_job", "file": "src/job.rs", "line": 1, "children": []},
+            "inputs": {},
+            "output": null,
+        },
+    ])
+}
+
+#[test]
+fn filters_select_records_by_nested_fields() {
+    let matches = select(&calls(), r#"calls[?root_node.name="handle_request"].inputs"#).unwrap();
+    assert_eq!(matches, vec![json!({"path": "/health"})]);
+
+    // Unquoted strings and JSON literals both work as filter values
+    let matches = select(&calls(), "calls[?thread_id=ThreadId(2)].root_node.name").unwrap();
+    assert_eq!(matches, vec![json!("background_job")]);
+
+    let matches = select(&calls(), "calls[?output=200].output").unwrap();
+    assert_eq!(matches, vec![json!(200)]);
+}
+
+#[test]
+fn indices_and_wildcards_walk_arrays() {
+    let matches = select(&calls(), "calls[0].root_node.children[*].name").unwrap();
+    assert_eq!(matches, vec![json!("parse")]);
+
+    let matches = select(&calls(), "calls[*].root_node.name").unwrap();
+    assert_eq!(matches, vec![json!("handle_request"), json!("background_job")]);
+
+    assert!(select(&calls(), "calls[9].output").unwrap().is_empty());
+}
+
+#[test]
+fn jsonpath_style_spelling_is_accepted() {
+    let matches = select(&calls(), r#"$.calls[?(@.root_node.name=="handle_request")].output"#).unwrap();
+    assert_eq!(matches, vec![json!(200)]);
+}
+
+#[test]
+fn malformed_selectors_are_rejected() {
+    assert!(select(&calls(), "records[0]").is_err(), "must start with calls");
+    assert!(select(&calls(), "calls[").is_err(), "unclosed bracket");
+    assert!(select(&calls(), "calls[?broken]").is_err(), "filter needs =");
+    assert!(select(&calls(), "calls..name").is_err(), "empty field");
+}
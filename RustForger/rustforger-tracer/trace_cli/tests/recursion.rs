@@ -0,0 +1,52 @@
+//! Tests for the `once_per_stack` recursion guard
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace(once_per_stack)]
+fn fib(n: u64) -> u64 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+#[rustforger_trace]
+fn countdown(n: u64) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        countdown(n - 1)
+    }
+}
+
+#[test]
+fn recursive_calls_record_only_the_outermost_invocation() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(fib(10), 55);
+
+    // One record, one node: the inner invocations never open spans
+    tracer.assert_call_count("fib", 1);
+    let calls = tracer.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0]["inputs"]["n"], 10);
+    assert_eq!(calls[0]["output"], 55);
+}
+
+#[test]
+fn unguarded_recursion_still_nests_as_before() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(countdown(3), 0);
+
+    // Every invocation opens its own span and the outermost record holds
+    // the whole chain
+    let calls = tracer.calls();
+    let outermost = calls
+        .iter()
+        .find(|record| record["inputs"]["n"] == 3)
+        .expect("outermost countdown call should be recorded");
+    assert_eq!(outermost["root_node"]["children"][0]["name"], "countdown");
+}
@@ -0,0 +1,47 @@
+//! Tests for runtime-side redaction applied before records reach a sink
+
+use trace_common::redact::RedactionRules;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+use trace_runtime::tracer::interface::{clear_redaction_rules, set_redaction_rules};
+
+#[rustforger_trace]
+fn login(username: String, password: String) -> String {
+    format!("{username}:{password}")
+}
+
+#[test]
+fn configured_rules_redact_inputs_and_outputs_on_record() {
+    let tracer = CapturedTracer::capture();
+    set_redaction_rules(
+        RedactionRules::new()
+            .with_key("password")
+            .with_value_pattern("hunter[0-9]")
+            .unwrap(),
+    );
+
+    let combined = login("alice".to_string(), "hunter2".to_string());
+    assert_eq!(combined, "alice:hunter2");
+
+    clear_redaction_rules();
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "login")
+        .expect("login call should be recorded");
+
+    assert_eq!(record["inputs"]["username"], "alice");
+    assert_eq!(record["inputs"]["password"], "<redacted>");
+    assert_eq!(record["output"], "alice:<redacted>");
+}
+
+#[test]
+fn without_rules_values_are_recorded_verbatim() {
+    let tracer = CapturedTracer::capture();
+
+    let _ = login("bob".to_string(), "opensesame".to_string());
+
+    let calls = tracer.calls();
+    assert_eq!(calls[0]["inputs"]["password"], "opensesame");
+}
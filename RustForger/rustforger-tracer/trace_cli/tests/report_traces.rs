@@ -0,0 +1,61 @@
+//! Tests for HTML report generation
+
+use anyhow::Result;
+use serde_json::json;
+use trace_cli::commands::report::generate_html;
+
+mod common;
+use common::TestFixture;
+
+fn records() -> Vec<trace_common::schema::CallData> {
+    vec![serde_json::from_value(json!({
+        "timestamp_utc": "2024-01-01T00:00:00Z",
+        "thread_id": "ThreadId(1)",
+        "root_node": {
+            "name": "handle<T> & co", "file": "src/server.rs", "line": 10,
+            "children": [{"name": "parse", "file": "src/parse.rs", "line": 5, "children": []}],
+        },
+        "inputs": {"path": "/health"},
+        "output": 200,
+        "duration_ns": 2_000_000,
+    }))
+    .unwrap()]
+}
+
+#[test]
+fn the_page_is_self_contained_with_tree_and_summary() {
+    let html = generate_html(&records());
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(!html.contains("http://") && !html.contains("https://"), "no external resources");
+    assert!(html.contains("<details open><summary>handle&lt;T&gt; &amp; co"));
+    assert!(html.contains("<details><summary>parse"), "children start collapsed");
+    assert!(html.contains("2.000"), "summary table shows total ms");
+    assert!(html.contains("&quot;path&quot;: &quot;/health&quot;"), "inputs are escaped");
+    assert!(html.contains("id=\"search\""), "search box is present");
+}
+
+#[test]
+fn markup_in_recorded_values_cannot_escape_into_the_page() {
+    let mut records = records();
+    records[0].inputs = json!({"payload": "<script>alert(1)</script>"});
+
+    let html = generate_html(&records);
+    assert!(!html.contains("<script>alert"), "recorded values are escaped");
+}
+
+/// Test the full command writes the report to disk
+#[test]
+fn report_writes_the_html_file() -> Result<()> {
+    let fixture = TestFixture::new()?;
+    let trace = serde_json::to_string(&records())?;
+    let trace_file = fixture.create_rust_file("trace.json", &trace)?;
+
+    let output = fixture.path().join("report.html");
+    trace_cli::commands::report::run(&trace_file, &output)?;
+
+    let html = std::fs::read_to_string(&output)?;
+    assert!(html.contains("1 recorded call(s)"));
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Tests for distinct Ok/Err capture on Result-returning functions
+
+use serde_json::Value;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[derive(Debug, PartialEq)]
+struct FlakyError {
+    code: i32,
+}
+
+impl std::fmt::Display for FlakyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "flaky (code {})", self.code)
+    }
+}
+
+#[rustforger_trace]
+fn flaky() -> Result<i32, FlakyError> {
+    Err(FlakyError { code: 7 })
+}
+
+#[rustforger_trace]
+fn checked_div(a: i32, b: i32) -> Result<i32, String> {
+    if b == 0 {
+        Err("division by zero".to_string())
+    } else {
+        Ok(a / b)
+    }
+}
+
+fn outputs_of(tracer: &CapturedTracer, fn_name: &str) -> Vec<Value> {
+    tracer
+        .calls()
+        .into_iter()
+        .filter(|record| record["root_node"]["name"] == fn_name)
+        .map(|record| record["output"].clone())
+        .collect()
+}
+
+#[test]
+fn ok_and_err_are_recorded_distinctly() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(checked_div(10, 2), Ok(5));
+    assert_eq!(checked_div(1, 0), Err("division by zero".to_string()));
+
+    let outputs = outputs_of(&tracer, "checked_div");
+    assert_eq!(outputs.len(), 2);
+
+    assert_eq!(outputs[0]["ok"], 5);
+    assert!(outputs[0].get("failed").is_none());
+
+    assert_eq!(outputs[1]["err"], "division by zero");
+    assert_eq!(outputs[1]["failed"], true);
+}
+
+#[test]
+fn non_serialize_errors_capture_type_and_display() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(flaky(), Err(FlakyError { code: 7 }));
+
+    let outputs = outputs_of(&tracer, "flaky");
+    assert_eq!(outputs.len(), 1);
+
+    assert!(
+        outputs[0]["err"]["type"]
+            .as_str()
+            .unwrap()
+            .contains("FlakyError"),
+        "{:?}",
+        outputs[0]
+    );
+    assert_eq!(outputs[0]["err"]["display"], "flaky (code 7)");
+    assert_eq!(outputs[0]["failed"], true);
+}
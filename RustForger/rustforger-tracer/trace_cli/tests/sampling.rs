@@ -0,0 +1,22 @@
+//! Tests for the per-function sample attribute
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace(sample = 0.25)]
+fn hot_path(x: u64) -> u64 {
+    x + 1
+}
+
+#[test]
+fn only_every_nth_call_is_recorded() {
+    let tracer = CapturedTracer::capture();
+
+    for i in 0..8 {
+        assert_eq!(hot_path(i), i + 1);
+    }
+
+    // sample = 0.25 keeps every 4th call: the counter starts at 0, so
+    // calls 0 and 4 of this batch are recorded
+    tracer.assert_call_count("hot_path", 2);
+}
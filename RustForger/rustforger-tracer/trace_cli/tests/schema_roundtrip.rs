@@ -0,0 +1,47 @@
+//! Tests that runtime output round-trips through the canonical schema
+
+use trace_common::schema::CallData;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace(propagate)]
+fn outer(x: i32) -> i32 {
+    inner(x) + 1
+}
+
+#[rustforger_trace]
+fn inner(x: i32) -> i32 {
+    x * 2
+}
+
+#[test]
+fn recorded_calls_round_trip_through_the_canonical_schema() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(outer(3), 7);
+
+    for raw in tracer.calls() {
+        let typed: CallData =
+            serde_json::from_value(raw.clone()).expect("runtime output matches the schema");
+        let reserialized = serde_json::to_value(&typed).expect("schema serializes");
+        assert_eq!(raw, reserialized, "round trip must be lossless");
+    }
+}
+
+#[test]
+fn the_typed_view_exposes_the_call_tree() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(outer(2), 5);
+
+    let calls = tracer.calls();
+    let record: CallData = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "outer")
+        .map(|raw| serde_json::from_value(raw.clone()).unwrap())
+        .expect("outer call should be recorded");
+
+    assert_eq!(record.root_node.name, "outer");
+    assert_eq!(record.root_node.children[0].name, "inner");
+    assert!(record.duration_ns.is_some());
+}
@@ -0,0 +1,65 @@
+//! Tests for autoref-specialized argument/output serialization
+//!
+//! User types implementing `Serialize` are captured as real JSON without
+//! appearing on any allowlist; types that don't implement it fall back to
+//! the `<unserializable: ...>` placeholder.
+
+use serde::Serialize;
+use serde_json::json;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[derive(Serialize, Clone)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct RawHandle {
+    #[allow(dead_code)]
+    fd: i32,
+}
+
+#[rustforger_trace]
+fn translate(point: Point, dx: i32) -> Point {
+    Point {
+        x: point.x + dx,
+        y: point.y,
+    }
+}
+
+#[rustforger_trace]
+fn close_handle(handle: RawHandle) -> bool {
+    handle.fd >= 0
+}
+
+#[test]
+fn user_serialize_types_are_captured_as_json() {
+    let tracer = CapturedTracer::capture();
+
+    let moved = translate(Point { x: 1, y: 2 }, 3);
+    assert_eq!(moved.x, 4);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "translate")
+        .expect("translate call recorded");
+    assert_eq!(record["inputs"]["point"], json!({ "x": 1, "y": 2 }));
+    assert_eq!(record["output"], json!({ "x": 4, "y": 2 }));
+}
+
+#[test]
+fn non_serialize_types_fall_back_to_placeholder() {
+    let tracer = CapturedTracer::capture();
+
+    assert!(close_handle(RawHandle { fd: 3 }));
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "close_handle")
+        .expect("close_handle call recorded");
+    let placeholder = record["inputs"]["handle"].as_str().expect("placeholder string");
+    assert!(placeholder.starts_with("<unserializable:"), "got {placeholder}");
+}
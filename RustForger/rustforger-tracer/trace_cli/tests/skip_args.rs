@@ -0,0 +1,67 @@
+//! Tests for the `skip(...)`, `no_inputs` and `no_output` attribute arguments
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace(skip(password))]
+fn login(username: String, password: String) -> bool {
+    !username.is_empty() && !password.is_empty()
+}
+
+#[rustforger_trace(no_inputs)]
+fn ingest(payload: Vec<u8>) -> usize {
+    payload.len()
+}
+
+#[rustforger_trace(no_output)]
+fn export(count: usize) -> Vec<u8> {
+    vec![0; count]
+}
+
+#[test]
+fn skipped_args_become_placeholders() {
+    let tracer = CapturedTracer::capture();
+
+    assert!(login("alice".to_string(), "hunter2".to_string()));
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "login")
+        .expect("login call should be recorded");
+
+    assert_eq!(record["inputs"]["username"], "alice");
+    assert_eq!(record["inputs"]["password"], "<skipped>");
+}
+
+#[test]
+fn no_inputs_suppresses_the_whole_input_side() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(ingest(vec![1, 2, 3]), 3);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "ingest")
+        .expect("ingest call should be recorded");
+
+    assert_eq!(record["inputs"], "<omitted>");
+    assert_eq!(record["output"], 3);
+}
+
+#[test]
+fn no_output_suppresses_the_whole_output_side() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(export(4).len(), 4);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "export")
+        .expect("export call should be recorded");
+
+    assert_eq!(record["inputs"]["count"], 4);
+    assert_eq!(record["output"], "<omitted>");
+}
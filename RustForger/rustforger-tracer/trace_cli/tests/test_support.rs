@@ -0,0 +1,38 @@
+//! Tests for the trace_runtime test-support harness
+
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+fn outer_work(x: i32) -> i32 {
+    inner_work(x) + 1
+}
+
+#[rustforger_trace]
+fn inner_work(x: i32) -> i32 {
+    x * 2
+}
+
+#[test]
+fn captures_calls_and_matches_tree() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(outer_work(5), 11);
+
+    tracer.assert_called("outer_work");
+    tracer.assert_called("inner_work");
+    tracer.assert_call_count("outer_work", 1);
+    tracer.assert_call_path(&["outer_work", "inner_work"]);
+}
+
+#[test]
+fn captures_are_isolated() {
+    {
+        let tracer = CapturedTracer::capture();
+        inner_work(1);
+        tracer.assert_call_count("inner_work", 1);
+    }
+
+    let tracer = CapturedTracer::capture();
+    assert_eq!(tracer.call_count("inner_work"), 0);
+}
@@ -0,0 +1,52 @@
+//! Tests for propagate instrumentation across thread spawns
+
+use serde_json::Value;
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+
+#[rustforger_trace]
+fn work_item(x: i32) -> i32 {
+    x * 2
+}
+
+#[rustforger_trace(propagate)]
+fn spawner(x: i32) -> i32 {
+    let handle = std::thread::spawn(move || {
+        let doubled = work_item(x);
+        doubled + 1
+    });
+    handle.join().unwrap()
+}
+
+fn find_child<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node["children"]
+        .as_array()?
+        .iter()
+        .find(|child| child["name"] == name)
+}
+
+#[test]
+fn spawned_work_is_linked_to_the_spawning_call() {
+    let tracer = CapturedTracer::capture();
+
+    assert_eq!(spawner(3), 7);
+
+    let calls = tracer.calls();
+    let record = calls
+        .iter()
+        .find(|record| record["root_node"]["name"] == "spawner")
+        .expect("spawner call should be recorded");
+
+    // The spawned thread's work sits inside the spawner's call tree
+    // instead of forming a disconnected root
+    let spawn_node = find_child(&record["root_node"], "spawner::spawn")
+        .expect("spawn link node should be attached to the spawner");
+    assert_eq!(
+        spawn_node["tags"]["spawned_from_call_id"],
+        record["root_node"]["call_id"]
+    );
+    assert!(
+        find_child(spawn_node, "work_item").is_some(),
+        "spawned closure's calls should land under the spawn node: {record}"
+    );
+}
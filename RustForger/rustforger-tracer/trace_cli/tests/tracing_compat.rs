@@ -0,0 +1,85 @@
+//! Tests for the tracing_compat attribute option
+//!
+//! A minimal capturing subscriber is enough to verify that instrumented
+//! functions emit a real tracing span carrying the function name and the
+//! serialized inputs/output as fields.
+
+use std::sync::{Arc, Mutex};
+use trace_runtime::test_support::CapturedTracer;
+use trace_runtime::trace_macro::rustforger_trace;
+use trace_runtime::tracing;
+
+#[rustforger_trace(tracing_compat)]
+fn spanned_add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[derive(Clone, Default)]
+struct CapturingSubscriber {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+struct FieldCollector<'a> {
+    lines: &'a mut Vec<String>,
+}
+
+impl tracing::field::Visit for FieldCollector<'_> {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.lines.push(format!("{}={:?}", field.name(), value));
+    }
+}
+
+impl tracing::Subscriber for CapturingSubscriber {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+        metadata.target() == "rustforger_trace"
+    }
+
+    fn new_span(&self, attrs: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let mut lines = self.lines.lock().unwrap();
+        lines.push(format!("span={}", attrs.metadata().name()));
+        attrs.record(&mut FieldCollector { lines: &mut lines });
+        tracing::span::Id::from_u64(lines.len() as u64)
+    }
+
+    fn record(&self, _span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        let mut lines = self.lines.lock().unwrap();
+        values.record(&mut FieldCollector { lines: &mut lines });
+    }
+
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+    fn event(&self, _event: &tracing::Event<'_>) {}
+
+    fn enter(&self, _span: &tracing::span::Id) {}
+
+    fn exit(&self, _span: &tracing::span::Id) {}
+}
+
+#[test]
+fn tracing_compat_emits_span_with_fields() {
+    let tracer = CapturedTracer::capture();
+    let subscriber = CapturingSubscriber::default();
+    let lines = subscriber.lines.clone();
+
+    tracing::subscriber::with_default(subscriber, || {
+        assert_eq!(spanned_add(2, 3), 5);
+    });
+
+    let lines = lines.lock().unwrap();
+    assert!(lines.iter().any(|line| line == "span=traced_call"), "{lines:?}");
+    assert!(
+        lines.iter().any(|line| line.contains("function=") && line.contains("spanned_add")),
+        "{lines:?}"
+    );
+    assert!(
+        lines.iter().any(|line| line.contains("inputs=") && line.contains("\"a\"")),
+        "{lines:?}"
+    );
+    assert!(
+        lines.iter().any(|line| line.contains("output=") && line.contains('5')),
+        "{lines:?}"
+    );
+
+    // The regular trace record is still produced alongside the span
+    tracer.assert_call_count("spanned_add", 1);
+}
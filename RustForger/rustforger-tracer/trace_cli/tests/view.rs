@@ -0,0 +1,93 @@
+//! Tests for the interactive viewer's state model
+
+use serde_json::json;
+use trace_cli::commands::view::ViewerState;
+
+fn records() -> Vec<trace_common::schema::CallData> {
+    let main_thread = json!({
+        "timestamp_utc": "2024-01-01T00:00:00Z",
+        "thread_id": "ThreadId(1)",
+        "root_node": {
+            "name": "handle_request", "file": "src/server.rs", "line": 10,
+            "children": [
+                {"name": "parse", "file": "src/parse.rs", "line": 5, "children": [
+                    {"name": "tokenize", "file": "src/parse.rs", "line": 40, "children": []},
+                ]},
+                {"name": "respond", "file": "src/server.rs", "line": 30, "children": []},
+            ],
+        },
+        "inputs": {"path": "/health"},
+        "output": 200,
+    });
+    let worker_thread = json!({
+        "timestamp_utc": "2024-01-01T00:00:01Z",
+        "thread_id": "ThreadId(2)",
+        "root_node": {"name": "background_job", "file": "src/job.rs", "line": 1, "children": []},
+        "inputs": {},
+        "output": null,
+    });
+    vec![
+        serde_json::from_value(main_thread).unwrap(),
+        serde_json::from_value(worker_thread).unwrap(),
+    ]
+}
+
+#[test]
+fn roots_start_expanded_one_level() {
+    let state = ViewerState::new(records());
+
+    let labels: Vec<String> = state.visible_rows().iter().map(|row| row.label.clone()).collect();
+    assert_eq!(labels.len(), 3, "root plus its direct children: {labels:?}");
+    assert!(labels[0].contains("handle_request (server.rs:10)"));
+    assert!(labels[1].contains("parse"));
+    assert!(labels[2].contains("respond"));
+}
+
+#[test]
+fn expanding_a_child_reveals_grandchildren() {
+    let mut state = ViewerState::new(records());
+
+    state.move_down();
+    state.toggle_expand();
+    let labels: Vec<String> = state.visible_rows().iter().map(|row| row.label.clone()).collect();
+    assert_eq!(labels.len(), 4);
+    assert!(labels[2].contains("tokenize"));
+
+    state.toggle_expand();
+    assert_eq!(state.visible_rows().len(), 3, "toggling again collapses");
+}
+
+#[test]
+fn tab_cycles_threads() {
+    let mut state = ViewerState::new(records());
+    assert_eq!(state.thread_ids(), ["ThreadId(1)", "ThreadId(2)"]);
+    assert_eq!(state.current_thread_id(), "ThreadId(1)");
+
+    state.next_thread();
+    assert_eq!(state.current_thread_id(), "ThreadId(2)");
+    assert!(state.visible_rows()[0].label.contains("background_job"));
+
+    state.next_thread();
+    assert_eq!(state.current_thread_id(), "ThreadId(1)", "wraps around");
+}
+
+#[test]
+fn search_expands_ancestors_and_selects_the_match() {
+    let mut state = ViewerState::new(records());
+
+    let matches = state.search("tokenize");
+    assert_eq!(matches, 1);
+    let rows = state.visible_rows();
+    assert!(rows[state.selected_index()].label.contains("tokenize"));
+    assert!(state.detail().contains("parse.rs:40"));
+}
+
+#[test]
+fn detail_shows_record_values_for_the_root() {
+    let state = ViewerState::new(records());
+
+    let detail = state.detail();
+    assert!(detail.contains("handle_request"));
+    assert!(detail.contains("\"path\": \"/health\""), "{detail}");
+    assert!(detail.contains("200"));
+}
@@ -0,0 +1,66 @@
+//! Binary encodings for trace data, behind the `msgpack` and `cbor`
+//! features.
+//!
+//! The runtime's binary output mode and the CLI's converters both encode
+//! the same types; keeping the helpers here means the two sides cannot
+//! drift apart on encoding options.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Encode any trace data type — [`crate::TraceData`],
+/// [`crate::schema::CallData`], whole record vectors — as MessagePack.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::binary::{from_msgpack, to_msgpack};
+/// use trace_common::TraceData;
+/// use serde_json::json;
+///
+/// let trace = TraceData::new("example_fn", json!({"x": 1}));
+/// let bytes = to_msgpack(&trace).unwrap();
+/// let back: TraceData = from_msgpack(&bytes).unwrap();
+/// assert_eq!(back, trace);
+/// ```
+#[cfg(feature = "msgpack")]
+pub fn to_msgpack<T: Serialize>(value: &T) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    // Named field encoding so readers in other languages see maps, not
+    // positional tuples, and field additions stay compatible
+    rmp_serde::encode::to_vec_named(value)
+}
+
+/// Decode trace data previously encoded with [`to_msgpack`].
+#[cfg(feature = "msgpack")]
+pub fn from_msgpack<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, rmp_serde::decode::Error> {
+    rmp_serde::decode::from_slice(bytes)
+}
+
+/// Encode any trace data type as CBOR.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::binary::{from_cbor, to_cbor};
+/// use trace_common::TraceData;
+/// use serde_json::json;
+///
+/// let trace = TraceData::new("example_fn", json!({"x": 1}));
+/// let bytes = to_cbor(&trace).unwrap();
+/// let back: TraceData = from_cbor(&bytes).unwrap();
+/// assert_eq!(back, trace);
+/// ```
+#[cfg(feature = "cbor")]
+pub fn to_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>, ciborium::ser::Error<std::io::Error>> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decode trace data previously encoded with [`to_cbor`].
+#[cfg(feature = "cbor")]
+pub fn from_cbor<T: DeserializeOwned>(
+    bytes: &[u8],
+) -> Result<T, ciborium::de::Error<std::io::Error>> {
+    ciborium::from_reader(bytes)
+}
@@ -0,0 +1,135 @@
+//! Structured diffing of captured trace values.
+//!
+//! Comparing the inputs or outputs of two runs of the same function is how
+//! behavioral regressions get found; this module produces a serializable
+//! diff instead of leaving callers to eyeball two JSON dumps.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A structured difference between two JSON values.
+///
+/// Object and array diffs only list entries that actually differ, so the
+/// diff of two large, mostly equal values stays small.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::diff::{diff, ValueDiff};
+/// use serde_json::json;
+///
+/// let before = json!({"count": 1, "name": "a"});
+/// let after = json!({"count": 2, "name": "a"});
+///
+/// match diff(&before, &after) {
+///     ValueDiff::Object { entries } => {
+///         assert_eq!(entries.len(), 1);
+///         assert!(entries.contains_key("count"));
+///     }
+///     other => panic!("expected an object diff, got {other:?}"),
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValueDiff {
+    /// The two values are equal
+    Unchanged,
+    /// A leaf-level replacement: the values differ and at least one side
+    /// is not a container
+    Changed { before: Value, after: Value },
+    /// Present only on the `after` side
+    Added { value: Value },
+    /// Present only on the `before` side
+    Removed { value: Value },
+    /// Both sides are objects; maps each differing key to its diff
+    Object { entries: BTreeMap<String, ValueDiff> },
+    /// Both sides are arrays; lists each differing index with its diff
+    Array { entries: Vec<ElementDiff> },
+}
+
+/// One differing element in an array diff
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ElementDiff {
+    pub index: usize,
+    #[serde(flatten)]
+    pub diff: ValueDiff,
+}
+
+impl ValueDiff {
+    /// Returns true if the compared values were equal
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, ValueDiff::Unchanged)
+    }
+}
+
+/// Compare two JSON values structurally.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::diff::{diff, ValueDiff};
+/// use serde_json::json;
+///
+/// assert!(diff(&json!(1), &json!(1)).is_unchanged());
+/// assert_eq!(
+///     diff(&json!(1), &json!(2)),
+///     ValueDiff::Changed { before: json!(1), after: json!(2) },
+/// );
+/// ```
+pub fn diff(a: &Value, b: &Value) -> ValueDiff {
+    if a == b {
+        return ValueDiff::Unchanged;
+    }
+
+    match (a, b) {
+        (Value::Object(before), Value::Object(after)) => {
+            let mut entries = BTreeMap::new();
+            for (key, value) in before {
+                match after.get(key) {
+                    Some(other) => {
+                        let entry = diff(value, other);
+                        if !entry.is_unchanged() {
+                            entries.insert(key.clone(), entry);
+                        }
+                    }
+                    None => {
+                        entries.insert(key.clone(), ValueDiff::Removed { value: value.clone() });
+                    }
+                }
+            }
+            for (key, value) in after {
+                if !before.contains_key(key) {
+                    entries.insert(key.clone(), ValueDiff::Added { value: value.clone() });
+                }
+            }
+            ValueDiff::Object { entries }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            let mut entries = Vec::new();
+            for (index, (value, other)) in before.iter().zip(after).enumerate() {
+                let entry = diff(value, other);
+                if !entry.is_unchanged() {
+                    entries.push(ElementDiff { index, diff: entry });
+                }
+            }
+            for (index, value) in before.iter().enumerate().skip(after.len()) {
+                entries.push(ElementDiff {
+                    index,
+                    diff: ValueDiff::Removed { value: value.clone() },
+                });
+            }
+            for (index, value) in after.iter().enumerate().skip(before.len()) {
+                entries.push(ElementDiff {
+                    index,
+                    diff: ValueDiff::Added { value: value.clone() },
+                });
+            }
+            ValueDiff::Array { entries }
+        }
+        _ => ValueDiff::Changed {
+            before: a.clone(),
+            after: b.clone(),
+        },
+    }
+}
@@ -0,0 +1,76 @@
+//! Shared error type for the tracing crates.
+//!
+//! The runtime, the CLI and this crate each grew their own error worlds;
+//! converging on one type lets callers compose the crates behind a single
+//! `?`. `trace_runtime::tracer::TraceError` converts into this type, and
+//! anything `anyhow`-based (the CLI) absorbs it through `std::error::Error`.
+
+use crate::schema::SchemaError;
+
+/// Any error produced while producing, loading or validating traces.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::Error;
+///
+/// fn load(path: &str) -> Result<String, Error> {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// let err = load("/definitely/not/here").unwrap_err();
+/// assert!(matches!(err, Error::Io(_)));
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing trace data failed
+    Io(std::io::Error),
+    /// JSON serialization or deserialization failed
+    Serde(serde_json::Error),
+    /// A trace document did not match (or could not be migrated to) the
+    /// canonical schema
+    Schema(SchemaError),
+    /// Invalid configuration or a broken environment (bad options, a
+    /// poisoned lock, a failed subscriber setup)
+    Config(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "IO error: {}", e),
+            Error::Serde(e) => write!(f, "Serialization error: {}", e),
+            Error::Schema(e) => write!(f, "Schema error: {}", e),
+            Error::Config(e) => write!(f, "Configuration error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Serde(e) => Some(e),
+            Error::Schema(e) => Some(e),
+            Error::Config(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Serde(err)
+    }
+}
+
+impl From<SchemaError> for Error {
+    fn from(err: SchemaError) -> Self {
+        Error::Schema(err)
+    }
+}
@@ -0,0 +1,160 @@
+//! String interning for trace serialization.
+//!
+//! Big traces repeat the same function names and file paths tens of
+//! thousands of times; interning writes each distinct string once and
+//! stores a numeric reference everywhere else. [`intern_strings`] rewrites
+//! a document against a [`StringTable`] before writing, and
+//! [`resolve_strings`] restores it on read.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Object keys whose string values are interned; all are known to repeat
+/// heavily across records
+const INTERNED_KEYS: [&str; 4] = ["name", "file", "module_path", "thread_id"];
+
+/// A deduplicating table of strings, serialized as a plain array.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::intern::StringTable;
+///
+/// let mut table = StringTable::new();
+/// let first = table.intern("src/lib.rs");
+/// let second = table.intern("src/lib.rs");
+/// assert_eq!(first, second);
+/// assert_eq!(table.resolve(first), Some("src/lib.rs"));
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+pub struct StringTable {
+    strings: Vec<String>,
+    index: HashMap<String, u32>,
+}
+
+impl StringTable {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the index for a string, adding it on first sight
+    pub fn intern(&mut self, s: &str) -> u32 {
+        if let Some(&index) = self.index.get(s) {
+            return index;
+        }
+        let index = self.strings.len() as u32;
+        self.strings.push(s.to_string());
+        self.index.insert(s.to_string(), index);
+        index
+    }
+
+    /// Look up the string behind an index
+    pub fn resolve(&self, index: u32) -> Option<&str> {
+        self.strings.get(index as usize).map(String::as_str)
+    }
+
+    /// Number of distinct strings interned so far
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if nothing has been interned
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl From<Vec<String>> for StringTable {
+    fn from(strings: Vec<String>) -> Self {
+        let index = strings
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.clone(), i as u32))
+            .collect();
+        Self { strings, index }
+    }
+}
+
+impl From<StringTable> for Vec<String> {
+    fn from(table: StringTable) -> Self {
+        table.strings
+    }
+}
+
+/// Replace repeated strings in a trace document with table indexes.
+///
+/// Only the values of keys known to repeat (`name`, `file`,
+/// `module_path`, `thread_id`) are interned, so user-captured data is
+/// never rewritten.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::intern::{intern_strings, resolve_strings, StringTable};
+/// use serde_json::json;
+///
+/// let original = json!([
+///     {"name": "f", "file": "a.rs", "inputs": {"name": "f"}},
+///     {"name": "f", "file": "a.rs"},
+/// ]);
+/// let mut document = original.clone();
+/// let mut table = StringTable::new();
+/// intern_strings(&mut document, &mut table);
+///
+/// assert_eq!(document[0]["file"], 0);
+/// assert_eq!(document[1]["name"], 1);
+/// // Nested user data is untouched
+/// assert_eq!(document[0]["inputs"]["name"], "f");
+///
+/// resolve_strings(&mut document, &table);
+/// assert_eq!(document, original);
+/// ```
+pub fn intern_strings(value: &mut Value, table: &mut StringTable) {
+    walk(value, &mut |key, child| {
+        if INTERNED_KEYS.contains(&key) {
+            if let Value::String(s) = child {
+                *child = Value::from(table.intern(s));
+            }
+        }
+    });
+}
+
+/// Restore interned indexes back into their strings; the inverse of
+/// [`intern_strings`]. Indexes the table does not know are left alone.
+pub fn resolve_strings(value: &mut Value, table: &StringTable) {
+    walk(value, &mut |key, child| {
+        if INTERNED_KEYS.contains(&key) {
+            if let Some(index) = child.as_u64() {
+                if let Some(s) = u32::try_from(index).ok().and_then(|i| table.resolve(i)) {
+                    *child = Value::String(s.to_string());
+                }
+            }
+        }
+    });
+}
+
+/// Visit every object entry in record structure, skipping captured user
+/// values (`inputs`, `output`, `args`, `data`, `tags`) whose contents must
+/// round-trip verbatim
+fn walk(value: &mut Value, visit: &mut impl FnMut(&str, &mut Value)) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if matches!(key.as_str(), "inputs" | "output" | "args" | "data" | "tags") {
+                    continue;
+                }
+                visit(key, child);
+                walk(child, visit);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                walk(item, visit);
+            }
+        }
+        _ => {}
+    }
+}
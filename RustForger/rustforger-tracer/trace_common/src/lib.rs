@@ -1,5 +1,21 @@
-use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub mod binary;
+pub mod error;
+
+pub use error::Error;
+
+pub mod diff;
+pub mod intern;
+pub mod reader;
+pub mod redact;
+pub mod render;
+pub mod schema;
+pub mod time;
+pub mod truncate;
+pub mod validate;
 
 /// Trace data structure for function call tracking.
 ///
@@ -17,14 +33,25 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TraceData {
-    /// UTC timestamp when the trace was created
-    pub timestamp: DateTime<Utc>,
+    /// UTC timestamp when the trace was created; an RFC 3339 string when
+    /// built without the default `chrono` feature
+    pub timestamp: time::Timestamp,
     /// Name of the function being traced
     pub function_name: String,
     /// Function arguments as JSON value
     pub args: serde_json::Value,
     /// Optional function result as JSON value
     pub result: Option<serde_json::Value>,
+    /// Optional wall-clock duration of the call in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_micros: Option<u64>,
+    /// Optional error information as JSON value, for calls that failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<serde_json::Value>,
+    /// Arbitrary metadata attached by the caller, e.g. git sha, hostname
+    /// or test name; preserved through all serialization paths
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, serde_json::Value>,
 }
 
 impl TraceData {
@@ -46,10 +73,13 @@ impl TraceData {
     /// ```
     pub fn new(function_name: impl Into<String>, args: serde_json::Value) -> Self {
         Self {
-            timestamp: Utc::now(),
+            timestamp: time::now(),
             function_name: function_name.into(),
             args,
             result: None,
+            duration_micros: None,
+            error: None,
+            metadata: BTreeMap::new(),
         }
     }
 
@@ -81,6 +111,142 @@ impl TraceData {
     pub fn set_result(&mut self, result: serde_json::Value) {
         self.result = Some(result);
     }
+
+    /// Sets the call duration for this trace entry (builder pattern).
+    ///
+    /// # Arguments
+    ///
+    /// * `duration_micros` - Wall-clock duration in microseconds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trace_common::TraceData;
+    /// use serde_json::json;
+    ///
+    /// let trace = TraceData::new("example_fn", json!({"x": 42}))
+    ///     .with_duration_micros(1_500);
+    /// assert_eq!(trace.duration_micros, Some(1_500));
+    /// ```
+    pub fn with_duration_micros(mut self, duration_micros: u64) -> Self {
+        self.duration_micros = Some(duration_micros);
+        self
+    }
+
+    /// Sets the error value for this trace entry (builder pattern).
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - Error information as a JSON value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trace_common::TraceData;
+    /// use serde_json::json;
+    ///
+    /// let trace = TraceData::new("example_fn", json!({"x": 42}))
+    ///     .with_error(json!("connection refused"));
+    /// ```
+    pub fn with_error(mut self, error: serde_json::Value) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Attaches one metadata entry to this trace entry (builder pattern).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Metadata key, e.g. `"git_sha"` or `"test_name"`
+    /// * `value` - Metadata value as a JSON value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trace_common::TraceData;
+    /// use serde_json::json;
+    ///
+    /// let trace = TraceData::new("example_fn", json!({"x": 42}))
+    ///     .with_metadata("git_sha", json!("a1b2c3d"))
+    ///     .with_metadata("test_name", json!("smoke"));
+    /// assert_eq!(trace.metadata["git_sha"], json!("a1b2c3d"));
+    /// ```
+    pub fn with_metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.metadata.insert(key.into(), value);
+        self
+    }
+}
+
+/// Exact number of bytes a JSON value occupies when serialized compactly.
+///
+/// Computed without building the string, so the runtime can enforce byte
+/// budgets and tools can attribute file size without serializing every
+/// record twice.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use trace_common::size_of_value;
+///
+/// let value = json!({"name": "parse", "line": 10});
+/// assert_eq!(size_of_value(&value), serde_json::to_string(&value).unwrap().len());
+/// ```
+pub fn size_of_value(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Null => 4,
+        serde_json::Value::Bool(true) => 4,
+        serde_json::Value::Bool(false) => 5,
+        serde_json::Value::Number(number) => number.to_string().len(),
+        serde_json::Value::String(string) => size_of_string(string),
+        serde_json::Value::Array(elements) => {
+            let separators = elements.len().saturating_sub(1);
+            2 + separators + elements.iter().map(size_of_value).sum::<usize>()
+        }
+        serde_json::Value::Object(entries) => {
+            let separators = entries.len().saturating_sub(1);
+            2 + separators
+                + entries
+                    .iter()
+                    .map(|(key, value)| size_of_string(key) + 1 + size_of_value(value))
+                    .sum::<usize>()
+        }
+    }
+}
+
+/// Serialized length of one JSON string, quotes and escapes included
+fn size_of_string(string: &str) -> usize {
+    2 + string
+        .bytes()
+        .map(|byte| match byte {
+            b'"' | b'\\' | 0x08 | 0x09 | 0x0a | 0x0c | 0x0d => 2,
+            byte if byte < 0x20 => 6,
+            _ => 1,
+        })
+        .sum::<usize>()
+}
+
+/// Total serialized bytes per root function, largest first.
+///
+/// Attributes each record's full size — tree, inputs, output — to the
+/// name of its root call, which is the granularity "what dominates this
+/// file" questions are asked at.
+pub fn size_by_function<'a>(
+    records: impl IntoIterator<Item = &'a schema::CallData>,
+) -> Vec<(String, usize)> {
+    let mut totals: BTreeMap<&str, usize> = BTreeMap::new();
+    for record in records {
+        let size = serde_json::to_value(record)
+            .map(|value| size_of_value(&value))
+            .unwrap_or(0);
+        *totals.entry(record.root_node.name.as_str()).or_default() += size;
+    }
+    let mut totals: Vec<(String, usize)> = totals
+        .into_iter()
+        .map(|(name, size)| (name.to_string(), size))
+        .collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
 }
 
 /// Serializes any value implementing [`Serialize`] trait.
@@ -111,6 +277,142 @@ pub fn serialize_value<T: Serialize>(value: &T) -> serde_json::Value {
     })
 }
 
+/// Autoref-specialization support for [`serialize_any!`]
+///
+/// Method resolution on `(&SerializeProbe(value)).probe_serialize()` prefers
+/// the inherent-style [`ViaSerialize`] impl on the probe itself (one autoref
+/// step) when the probed type implements [`Serialize`], and only falls back
+/// to [`ViaFallback`] on `&SerializeProbe` otherwise. This decides
+/// serializability per call site at compile time without naming types.
+pub mod specialize {
+    use serde::Serialize;
+    use serde_json::Value;
+
+    pub struct SerializeProbe<'a, T>(pub &'a T);
+
+    pub trait ViaSerialize {
+        fn probe_serialize(&self) -> Value;
+    }
+
+    impl<T: Serialize> ViaSerialize for SerializeProbe<'_, T> {
+        fn probe_serialize(&self) -> Value {
+            crate::serialize_value(self.0)
+        }
+    }
+
+    pub trait ViaFallback {
+        fn probe_serialize(&self) -> Value;
+    }
+
+    impl<T> ViaFallback for &SerializeProbe<'_, T> {
+        fn probe_serialize(&self) -> Value {
+            crate::placeholder_for(self.0)
+        }
+    }
+
+    pub struct ErrorProbe<'a, T>(pub &'a T);
+
+    // Three-level autoref dispatch, probed from `&&ErrorProbe`: method
+    // resolution reaches self-param types in the order `&&Probe` (one
+    // deref level up from the call), `&&&Probe` (autoref), `&Probe`
+    // (deref), so the impls below rank Serialize > Display > fallback.
+
+    pub trait ErrViaSerialize {
+        fn probe_error(&self) -> Value;
+    }
+
+    impl<T: Serialize> ErrViaSerialize for &ErrorProbe<'_, T> {
+        fn probe_error(&self) -> Value {
+            crate::serialize_value(self.0)
+        }
+    }
+
+    pub trait ErrViaDisplay {
+        fn probe_error(&self) -> Value;
+    }
+
+    impl<T: std::fmt::Display> ErrViaDisplay for &&ErrorProbe<'_, T> {
+        fn probe_error(&self) -> Value {
+            serde_json::json!({
+                "type": std::any::type_name::<T>(),
+                "display": self.0.to_string(),
+            })
+        }
+    }
+
+    pub trait ErrViaFallback {
+        fn probe_error(&self) -> Value;
+    }
+
+    impl<T> ErrViaFallback for ErrorProbe<'_, T> {
+        fn probe_error(&self) -> Value {
+            serde_json::json!({
+                "type": std::any::type_name::<T>(),
+                "display": crate::placeholder_for(self.0),
+            })
+        }
+    }
+}
+
+/// Serializes a value when its type implements [`Serialize`], producing an
+/// `<unserializable: ...>` placeholder otherwise.
+///
+/// Unlike [`serialize_if_serializable!`] this compiles for any type; the
+/// choice is made by autoref specialization rather than a trait bound.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::serialize_any;
+/// use serde_json::json;
+/// use std::rc::Rc;
+///
+/// assert_eq!(serialize_any!(&42), json!(42));
+/// let opaque = Rc::new(42);
+/// assert!(serialize_any!(&opaque).as_str().unwrap().starts_with("<unserializable:"));
+/// ```
+#[macro_export]
+macro_rules! serialize_any {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::specialize::{ViaFallback as _, ViaSerialize as _};
+        (&$crate::specialize::SerializeProbe($value)).probe_serialize()
+    }};
+}
+
+/// Describes a returned error value as richly as its type allows.
+///
+/// [`Serialize`] errors keep their full structured form; otherwise the
+/// error is captured as `{ "type": ..., "display": ... }` so traces still
+/// show what went wrong even for `E: !Serialize`. Types implementing
+/// neither trait fall back to a placeholder in the `display` slot.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::describe_error;
+///
+/// assert_eq!(describe_error!(&"boom"), serde_json::json!("boom"));
+///
+/// struct Opaque;
+/// impl std::fmt::Display for Opaque {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "opaque failure")
+///     }
+/// }
+/// let described = describe_error!(&Opaque);
+/// assert_eq!(described["display"], "opaque failure");
+/// assert!(described["type"].as_str().unwrap().contains("Opaque"));
+/// ```
+#[macro_export]
+macro_rules! describe_error {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::specialize::{ErrViaDisplay as _, ErrViaFallback as _, ErrViaSerialize as _};
+        (&&$crate::specialize::ErrorProbe($value)).probe_error()
+    }};
+}
+
 /// Generates a placeholder for any type with type information.
 ///
 /// This function creates a JSON string placeholder that includes the type name
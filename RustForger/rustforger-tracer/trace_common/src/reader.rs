@@ -0,0 +1,206 @@
+//! Streaming reader for trace files.
+//!
+//! Iterates [`CallData`] entries lazily from JSON-array or JSONL files, so
+//! tools can walk multi-gigabyte traces without `read_to_string` plus a
+//! full-document parse. Non-record entries (the header, framing lines) are
+//! skipped and a malformed tail — the usual aftermath of a crash mid-write
+//! — simply ends iteration instead of failing the whole read.
+
+use crate::schema::CallData;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Lazy iterator over the records of a trace file.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::reader::TraceReader;
+/// use std::io::Cursor;
+///
+/// let jsonl = concat!(
+///     r#"{"timestamp_utc": "t", "thread_id": "1", "inputs": {}, "output": null, "#,
+///     r#""root_node": {"name": "f", "file": "a.rs", "line": 1, "children": []}}"#,
+///     "\n",
+/// );
+/// let mut reader = TraceReader::new(Cursor::new(jsonl));
+/// assert_eq!(reader.next().unwrap().root_node.name, "f");
+/// assert!(reader.next().is_none());
+/// ```
+pub struct TraceReader<R: Read> {
+    reader: BufReader<R>,
+    format: Format,
+}
+
+enum Format {
+    /// Not yet sniffed; decided on the first byte read
+    Unknown,
+    /// One top-level JSON array holding header and records
+    Array { done: bool },
+    /// One JSON document per line (JSONL / streamed output)
+    Lines,
+}
+
+impl TraceReader<File> {
+    /// Open a trace file for lazy iteration
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> TraceReader<R> {
+    /// Wrap any reader producing trace data in either supported format
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            format: Format::Unknown,
+        }
+    }
+
+    /// Read one byte, or `None` at end of input
+    fn next_byte(&mut self) -> Option<u8> {
+        let mut byte = [0u8; 1];
+        match self.reader.read(&mut byte) {
+            Ok(1) => Some(byte[0]),
+            _ => None,
+        }
+    }
+
+    /// Read past whitespace and element separators to the first byte of
+    /// the next array element, or `None` when the array (or input) ends
+    fn next_element_start(&mut self) -> Option<u8> {
+        loop {
+            match self.next_byte()? {
+                b']' => return None,
+                byte if byte.is_ascii_whitespace() || byte == b',' => continue,
+                byte => return Some(byte),
+            }
+        }
+    }
+
+    /// Extract the raw bytes of one top-level array element, starting from
+    /// its already-read first byte
+    fn read_element(&mut self, first: u8) -> Option<Vec<u8>> {
+        let mut bytes = vec![first];
+        let mut depth = i32::from(matches!(first, b'{' | b'['));
+        let mut in_string = first == b'"';
+        let mut escaped = false;
+
+        if depth == 0 && !in_string {
+            // Scalar element: runs until a delimiter at the top level
+            loop {
+                let Some(byte) = self.next_byte() else {
+                    return Some(bytes);
+                };
+                if byte == b',' || byte == b']' || byte.is_ascii_whitespace() {
+                    return Some(bytes);
+                }
+                bytes.push(byte);
+            }
+        }
+
+        loop {
+            let byte = self.next_byte()?;
+            bytes.push(byte);
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(bytes);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Produce the next record in array mode
+    fn next_from_array(&mut self) -> Option<CallData> {
+        loop {
+            let first = match self.next_element_start() {
+                Some(first) => first,
+                None => {
+                    self.format = Format::Array { done: true };
+                    return None;
+                }
+            };
+            // A tail cut off mid-element ends iteration
+            let bytes = self.read_element(first)?;
+            // Entries that are not records — the header — are skipped
+            if let Ok(record) = serde_json::from_slice(&bytes) {
+                return Some(record);
+            }
+        }
+    }
+
+    /// Produce the next record in line mode
+    fn next_from_lines(&mut self) -> Option<CallData> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) | Err(_) => return None,
+                Ok(_) => {}
+            }
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            // Length-prefix framing lines and header lines fail to parse
+            // as records and are skipped; so is a truncated final line
+            if let Ok(record) = serde_json::from_str(trimmed) {
+                return Some(record);
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for TraceReader<R> {
+    type Item = CallData;
+
+    fn next(&mut self) -> Option<CallData> {
+        match self.format {
+            Format::Unknown => loop {
+                // Sniff the format from the first meaningful byte
+                match self.next_byte() {
+                    None => return None,
+                    Some(byte) if byte.is_ascii_whitespace() => continue,
+                    Some(b'[') => {
+                        self.format = Format::Array { done: false };
+                        return self.next_from_array();
+                    }
+                    Some(first) => {
+                        self.format = Format::Lines;
+                        // The sniffed byte belongs to the first line; read
+                        // the rest of it and parse the two together
+                        let mut line = String::new();
+                        if self.reader.read_line(&mut line).is_err() {
+                            return None;
+                        }
+                        let mut full = (first as char).to_string();
+                        full.push_str(&line);
+                        if let Ok(record) = serde_json::from_str(full.trim()) {
+                            return Some(record);
+                        }
+                        return self.next_from_lines();
+                    }
+                }
+            },
+            Format::Array { done: true } => None,
+            Format::Array { done: false } => self.next_from_array(),
+            Format::Lines => self.next_from_lines(),
+        }
+    }
+}
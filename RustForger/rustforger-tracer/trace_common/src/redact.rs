@@ -0,0 +1,174 @@
+//! Redaction engine for captured trace values.
+//!
+//! Applies key-based and regex-based redaction plus value truncation to
+//! arbitrary JSON values, so already-captured traces can be sanitized before
+//! they are shared outside the team.
+
+use regex::Regex;
+use serde_json::Value;
+
+/// Default replacement string for redacted values
+pub const DEFAULT_REPLACEMENT: &str = "<redacted>";
+
+/// A set of redaction rules applied recursively to a JSON value.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::redact::RedactionRules;
+/// use serde_json::json;
+///
+/// let rules = RedactionRules::new().with_key("password");
+/// let mut value = json!({"user": "alice", "password": "hunter2"});
+/// rules.apply(&mut value);
+/// assert_eq!(value["password"], "<redacted>");
+/// assert_eq!(value["user"], "alice");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RedactionRules {
+    /// Object keys whose values are replaced entirely (case-insensitive)
+    keys: Vec<String>,
+    /// Regex patterns matched against object keys
+    key_patterns: Vec<Regex>,
+    /// Regex patterns whose matches inside string values are replaced
+    value_patterns: Vec<Regex>,
+    /// Replacement text for redacted content
+    replacement: String,
+    /// Truncate string values longer than this many characters
+    max_string_len: Option<usize>,
+}
+
+impl Default for RedactionRules {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            key_patterns: Vec::new(),
+            value_patterns: Vec::new(),
+            replacement: DEFAULT_REPLACEMENT.to_string(),
+            max_string_len: None,
+        }
+    }
+}
+
+impl RedactionRules {
+    /// Create an empty rule set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redact values stored under this exact key (case-insensitive)
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into());
+        self
+    }
+
+    /// Redact values stored under keys matching this regex
+    pub fn with_key_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.key_patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Redact values stored under keys matching this glob, where `*`
+    /// matches any run of characters and `?` matches one (case-insensitive)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trace_common::redact::RedactionRules;
+    /// use serde_json::json;
+    ///
+    /// let rules = RedactionRules::new().with_key_glob("*_token").unwrap();
+    /// let mut value = json!({"session_token": "abc", "count": 2});
+    /// rules.apply(&mut value);
+    /// assert_eq!(value["session_token"], "<redacted>");
+    /// assert_eq!(value["count"], 2);
+    /// ```
+    pub fn with_key_glob(self, glob: &str) -> Result<Self, regex::Error> {
+        self.with_key_pattern(&glob_to_regex(glob))
+    }
+
+    /// Replace matches of this regex inside string values
+    pub fn with_value_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.value_patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Set the replacement text used for redacted content
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = replacement.into();
+        self
+    }
+
+    /// Truncate string values longer than `len` characters
+    pub fn with_max_string_len(mut self, len: usize) -> Self {
+        self.max_string_len = Some(len);
+        self
+    }
+
+    /// Returns true if no rule would ever modify a value
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+            && self.key_patterns.is_empty()
+            && self.value_patterns.is_empty()
+            && self.max_string_len.is_none()
+    }
+
+    /// Apply all rules to a JSON value in place
+    pub fn apply(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    if self.key_matches(key) {
+                        *child = Value::String(self.replacement.clone());
+                    } else {
+                        self.apply(child);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.apply(item);
+                }
+            }
+            Value::String(s) => {
+                for pattern in &self.value_patterns {
+                    if pattern.is_match(s) {
+                        *s = pattern.replace_all(s, self.replacement.as_str()).into_owned();
+                    }
+                }
+                if let Some(max_len) = self.max_string_len {
+                    if s.chars().count() > max_len {
+                        let truncated: String = s.chars().take(max_len).collect();
+                        *s = format!("{}<truncated>", truncated);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn key_matches(&self, key: &str) -> bool {
+        self.keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+            || self.key_patterns.iter().any(|p| p.is_match(key))
+    }
+}
+
+/// Apply a rule set to a JSON value in place; free-function spelling of
+/// [`RedactionRules::apply`] for callers holding the value and rules apart
+pub fn apply(value: &mut Value, rules: &RedactionRules) {
+    rules.apply(value);
+}
+
+/// Translate a key glob into an anchored, case-insensitive regex
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("(?i)^");
+    for ch in glob.chars() {
+        match ch {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            other => pattern.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
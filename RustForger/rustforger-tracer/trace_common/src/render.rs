@@ -0,0 +1,246 @@
+//! Plain-text rendering of recorded call trees.
+//!
+//! Renders a [`CallData`] record into the compact ASCII tree the CLI
+//! preview shows, as a string, so the runtime can log previews on finalize
+//! and external consumers are not forced through the CLI binary.
+
+use crate::schema::{CallData, CallNode};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Options controlling how much of a call tree is rendered.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::render::{ascii_tree, RenderOptions};
+/// use serde_json::json;
+///
+/// let record = serde_json::from_value(json!({
+///     "timestamp_utc": "2023-01-01T12:00:05Z",
+///     "thread_id": "ThreadId(1)",
+///     "root_node": {
+///         "name": "outer", "file": "src/lib.rs", "line": 3,
+///         "children": [{"name": "inner", "file": "src/lib.rs", "line": 9, "children": []}],
+///     },
+///     "inputs": {"x": 1},
+///     "output": 2,
+/// })).unwrap();
+///
+/// let rendered = ascii_tree(&record, &RenderOptions::default());
+/// assert!(rendered.contains("outer (lib.rs:3) [12:00:05]"));
+/// assert!(rendered.contains("└─ inner (lib.rs:9)"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Maximum depth of call tree to render
+    pub max_depth: usize,
+    /// Maximum number of children to show per node
+    pub max_children_per_node: usize,
+    /// Whether to show input/output values
+    pub show_values: bool,
+    /// Maximum length of displayed values
+    pub max_value_length: usize,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 10,
+            max_children_per_node: 10,
+            show_values: true,
+            max_value_length: 200,
+        }
+    }
+}
+
+/// Render one recorded call as a compact ASCII tree.
+///
+/// The first line shows the root call with its location and time of day;
+/// inputs and outputs follow when enabled, then the children as a tree.
+pub fn ascii_tree(call: &CallData, options: &RenderOptions) -> String {
+    let mut out = String::new();
+
+    let location = format_location(&call.root_node.file, call.root_node.line);
+    let time = extract_time_from_timestamp(&call.timestamp_utc);
+    let _ = writeln!(out, "{} {} [{}]", call.root_node.name, location, time);
+
+    if options.show_values {
+        render_values(&mut out, &call.inputs, &call.output, options, "  ");
+    }
+
+    if !call.root_node.children.is_empty() {
+        render_children(&mut out, &call.root_node.children, options, 1, "  ");
+    }
+
+    out
+}
+
+/// Render the call tree recursively
+fn render_children(
+    out: &mut String,
+    children: &[CallNode],
+    options: &RenderOptions,
+    depth: usize,
+    prefix: &str,
+) {
+    if depth > options.max_depth {
+        let _ = writeln!(out, "{}└─ ... (max depth reached)", prefix);
+        return;
+    }
+
+    let display_count = std::cmp::min(options.max_children_per_node, children.len());
+
+    for (i, child) in children.iter().take(display_count).enumerate() {
+        let is_last = i == display_count - 1 && display_count == children.len();
+        let child_prefix = if is_last { "└─" } else { "├─" };
+        let location = format_location(&child.file, child.line);
+
+        let _ = writeln!(out, "{}{} {} {}", prefix, child_prefix, child.name, location);
+
+        if !child.children.is_empty() {
+            let next_prefix = if is_last {
+                format!("{}   ", prefix)
+            } else {
+                format!("{}│  ", prefix)
+            };
+            render_children(out, &child.children, options, depth + 1, &next_prefix);
+        }
+    }
+
+    if children.len() > display_count {
+        let omitted = children.len() - display_count;
+        let _ = writeln!(out, "{}└─ ... ({} more children omitted)", prefix, omitted);
+    }
+}
+
+/// Render input and output values in a compact format
+fn render_values(out: &mut String, inputs: &Value, output: &Value, options: &RenderOptions, prefix: &str) {
+    if !inputs.is_null() && !is_empty_object(inputs) {
+        let input_str = format_value(inputs, options.max_value_length);
+        let _ = writeln!(out, "{}in:  {}", prefix, input_str);
+    }
+
+    if !output.is_null() {
+        let output_str = format_value(output, options.max_value_length);
+        let _ = writeln!(out, "{}out: {}", prefix, output_str);
+    }
+}
+
+/// Format a JSON value for compact display
+pub fn format_value(value: &Value, max_length: usize) -> String {
+    let formatted = match value {
+        Value::String(s) => {
+            if s.starts_with("<unserializable:") || s.starts_with("<debug:") {
+                // Extract type name from unserializable placeholders
+                extract_type_from_placeholder(s)
+            } else {
+                format!("\"{}\"", s)
+            }
+        }
+        Value::Object(obj) => {
+            if obj.is_empty() {
+                "{}".to_string()
+            } else {
+                let keys: Vec<String> = obj.keys().take(3).cloned().collect();
+                if keys.len() == obj.len() {
+                    format!("{{{}}}", keys.join(", "))
+                } else {
+                    format!("{{{}, ...}}", keys.join(", "))
+                }
+            }
+        }
+        Value::Array(arr) => {
+            if arr.is_empty() {
+                "[]".to_string()
+            } else {
+                format!("[{} items]", arr.len())
+            }
+        }
+        _ => value.to_string(),
+    };
+
+    // Truncate if too long
+    if formatted.len() > max_length {
+        format!("{}...", &formatted[..max_length.saturating_sub(3)])
+    } else {
+        formatted
+    }
+}
+
+/// Extract time portion from ISO timestamp
+fn extract_time_from_timestamp(timestamp: &str) -> String {
+    if let Some(time_part) = timestamp.split('T').nth(1) {
+        if let Some(time_without_tz) = time_part.split('+').next().or_else(|| time_part.split('Z').next()) {
+            // Return HH:MM:SS format
+            if time_without_tz.len() >= 8 {
+                return time_without_tz[..8].to_string();
+            }
+        }
+    }
+    timestamp.to_string() // Fallback to full timestamp
+}
+
+/// Format file location for compact display
+fn format_location(file: &str, line: u32) -> String {
+    if let Some(filename) = file.split('/').next_back() {
+        format!("({}:{})", filename, line)
+    } else {
+        format!("({}:{})", file, line)
+    }
+}
+
+/// Extract type name from unserializable placeholder
+fn extract_type_from_placeholder(placeholder: &str) -> String {
+    if placeholder.starts_with("<unserializable:") {
+        if let Some(type_part) = placeholder.strip_prefix("<unserializable: ").and_then(|s| s.strip_suffix(">")) {
+            format!("<{}>", simplify_type_name(type_part))
+        } else {
+            "<unserializable>".to_string()
+        }
+    } else if placeholder.starts_with("<debug:") {
+        if let Some(debug_part) = placeholder.strip_prefix("<debug: ") {
+            if let Some(type_part) = debug_part.split(" = ").next() {
+                format!("<{}>", simplify_type_name(type_part))
+            } else {
+                "<debug>".to_string()
+            }
+        } else {
+            "<debug>".to_string()
+        }
+    } else {
+        placeholder.to_string()
+    }
+}
+
+/// Simplify Rust type names for display
+fn simplify_type_name(type_name: &str) -> String {
+    // Common type simplifications
+    let simplified = type_name
+        .replace("alloc::string::String", "String")
+        .replace("alloc::vec::Vec", "Vec")
+        .replace("std::collections::hash::map::HashMap", "HashMap")
+        .replace("core::result::Result", "Result")
+        .replace("core::option::Option", "Option")
+        .replace("alloc::rc::Rc", "Rc")
+        .replace("std::sync::", "");
+
+    // If still too long, take just the last part
+    if simplified.len() > 30 {
+        if let Some(last_part) = simplified.split("::").last() {
+            last_part.to_string()
+        } else {
+            simplified
+        }
+    } else {
+        simplified
+    }
+}
+
+/// Check if a JSON value is an empty object
+fn is_empty_object(value: &Value) -> bool {
+    match value {
+        Value::Object(obj) => obj.is_empty(),
+        _ => false,
+    }
+}
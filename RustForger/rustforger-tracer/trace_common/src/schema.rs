@@ -0,0 +1,514 @@
+//! Canonical serde schema for recorded trace files.
+//!
+//! The runtime builds call trees behind mutexes while calls are in flight
+//! and serializes them into this shape; the CLI and external tools read
+//! trace files back through these plain, round-trippable structs. Fields
+//! added over time carry `#[serde(default)]` so older trace files still
+//! deserialize.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the trace file format written by the current tree
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Generate a random 128-bit trace ID as 32 lowercase hex characters —
+/// the OpenTelemetry wire format — for joining traces across processes
+/// and with external telemetry.
+///
+/// # Examples
+///
+/// ```
+/// let id = trace_common::schema::generate_trace_id();
+/// assert_eq!(id.len(), 32);
+/// assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+/// ```
+pub fn generate_trace_id() -> String {
+    let bits = (u128::from(random_bits()) << 64) | u128::from(random_bits());
+    format!("{:032x}", bits)
+}
+
+/// Generate a random 64-bit span ID as 16 lowercase hex characters
+pub fn generate_span_id() -> String {
+    format!("{:016x}", random_bits())
+}
+
+/// Random bits from the standard library's per-instance hasher seeds; not
+/// cryptographic, but collision-resistant enough for correlation IDs
+/// without pulling in a dependency. Never zero, which OpenTelemetry
+/// treats as an invalid ID.
+fn random_bits() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    loop {
+        let bits = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        if bits != 0 {
+            return bits;
+        }
+    }
+}
+
+/// One node in a recorded call tree.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::schema::CallNode;
+///
+/// let json = r#"{"name": "parse", "file": "src/lib.rs", "line": 10, "children": []}"#;
+/// let node: CallNode = serde_json::from_str(json).unwrap();
+/// assert_eq!(node.name, "parse");
+/// assert!(node.children.is_empty());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct CallNode {
+    /// Process-wide unique ID for this call; absent in old trace files
+    #[serde(default)]
+    pub call_id: u64,
+    pub name: String,
+    /// Module path of the traced function, when recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module_path: Option<String>,
+    pub file: String,
+    pub line: u32,
+    /// Column of the call site, when recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    /// Stable hash of name + file + line + column; absent in old files
+    #[serde(default)]
+    pub call_site_id: u64,
+    /// OpenTelemetry-compatible span ID (16 lowercase hex characters);
+    /// absent in files written before correlation IDs existed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
+    /// Resolved backtrace captured for orphan calls, when enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backtrace: Option<String>,
+    /// Serialized call-site arguments for propagated child calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Value>,
+    /// Ad-hoc checkpoint events recorded while this call was active
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<CheckpointEvent>,
+    /// Key/value metadata attached via the tagging interface
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub tags: serde_json::Map<String, Value>,
+    #[serde(default)]
+    pub children: Vec<CallNode>,
+}
+
+impl CallNode {
+    /// Depth of the tree rooted here; a leaf has depth 1
+    pub fn depth(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(CallNode::depth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Total number of nodes in the tree rooted here, including this one
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(CallNode::count).sum::<usize>()
+    }
+
+    /// First node with this name, searching depth-first from here
+    pub fn find(&self, name: &str) -> Option<&CallNode> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(name))
+    }
+
+    /// Every node in the tree rooted here, in depth-first pre-order
+    pub fn flatten(&self) -> Vec<&CallNode> {
+        let mut nodes = Vec::with_capacity(self.count());
+        self.collect_into(&mut nodes);
+        nodes
+    }
+
+    fn collect_into<'a>(&'a self, nodes: &mut Vec<&'a CallNode>) {
+        nodes.push(self);
+        for child in &self.children {
+            child.collect_into(nodes);
+        }
+    }
+}
+
+/// Builder for call trees, for tests and converters that assemble nodes by
+/// hand instead of recording them.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::schema::CallTree;
+///
+/// let tree = CallTree::root("outer")
+///     .at("src/lib.rs", 3)
+///     .child(CallTree::root("inner"))
+///     .build();
+///
+/// assert_eq!(tree.depth(), 2);
+/// assert_eq!(tree.count(), 2);
+/// assert_eq!(tree.find("inner").unwrap().name, "inner");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CallTree {
+    node: CallNode,
+}
+
+impl CallTree {
+    /// Start a tree (or subtree) rooted at a call with this name
+    pub fn root(name: impl Into<String>) -> Self {
+        Self {
+            node: CallNode {
+                call_id: 0,
+                name: name.into(),
+                module_path: None,
+                file: String::new(),
+                line: 0,
+                column: None,
+                call_site_id: 0,
+                span_id: None,
+                backtrace: None,
+                args: None,
+                events: Vec::new(),
+                tags: serde_json::Map::new(),
+                children: Vec::new(),
+            },
+        }
+    }
+
+    /// Set the source location of this call
+    pub fn at(mut self, file: impl Into<String>, line: u32) -> Self {
+        self.node.file = file.into();
+        self.node.line = line;
+        self
+    }
+
+    /// Set the recorded call-site arguments of this call
+    pub fn args(mut self, args: Value) -> Self {
+        self.node.args = Some(args);
+        self
+    }
+
+    /// Append a child subtree
+    pub fn child(mut self, child: CallTree) -> Self {
+        self.node.children.push(child.build());
+        self
+    }
+
+    /// Finish building and return the node
+    pub fn build(self) -> CallNode {
+        self.node
+    }
+}
+
+/// An ad-hoc checkpoint recorded mid-function.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct CheckpointEvent {
+    pub timestamp_utc: String,
+    pub name: String,
+    pub data: Value,
+}
+
+/// One complete recorded call: the tree plus top-level inputs and output.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::schema::CallData;
+/// use serde_json::json;
+///
+/// let json = json!({
+///     "timestamp_utc": "2023-01-01T12:00:00Z",
+///     "thread_id": "ThreadId(1)",
+///     "root_node": {"name": "add", "file": "src/lib.rs", "line": 3, "children": []},
+///     "inputs": {"x": 1},
+///     "output": 2,
+/// });
+/// let record: CallData = serde_json::from_value(json).unwrap();
+/// assert_eq!(record.root_node.name, "add");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct CallData {
+    pub timestamp_utc: String,
+    pub thread_id: String,
+    /// OpenTelemetry-compatible trace ID (32 lowercase hex characters)
+    /// shared by every record of one traced session; absent in old files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Tokio task ID, when captured inside a task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    /// Name of the runtime worker thread the call ran on, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_name: Option<String>,
+    pub root_node: CallNode,
+    pub inputs: Value,
+    pub output: Value,
+    /// Wall time spent in the call body, in nanoseconds; absent for
+    /// records from callers that do not measure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ns: Option<u64>,
+}
+
+/// Metadata header written as the first element of a trace file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct TraceHeader {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub tool_version: String,
+    #[serde(default)]
+    pub start_time: String,
+    #[serde(default)]
+    pub hostname: String,
+    #[serde(default)]
+    pub cmdline: Vec<String>,
+}
+
+impl Default for TraceHeader {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            tool_version: String::new(),
+            start_time: String::new(),
+            hostname: String::new(),
+            cmdline: Vec::new(),
+        }
+    }
+}
+
+/// A fully loaded trace file: header plus every recorded call.
+///
+/// Loading goes through [`migrate`], so files written by older versions of
+/// the format come out in the current shape instead of failing to parse.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::schema::TraceFile;
+/// use serde_json::json;
+///
+/// // A pre-header file: just a bare array of records
+/// let old = json!([{
+///     "timestamp_utc": "2023-01-01T12:00:00Z",
+///     "thread_id": "ThreadId(1)",
+///     "root_node": {"name": "add", "file": "src/lib.rs", "line": 3, "children": []},
+///     "inputs": {},
+///     "output": null,
+/// }]);
+/// let file = TraceFile::from_value(old).unwrap();
+/// assert_eq!(file.records.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceFile {
+    pub header: TraceHeader,
+    pub records: Vec<CallData>,
+}
+
+impl TraceFile {
+    /// Detect which format version a raw trace document was written in.
+    ///
+    /// Version 0 is the original bare array of records; from version 1 on
+    /// the first array element is a header carrying `schema_version`.
+    pub fn detect_version(value: &Value) -> u32 {
+        value
+            .as_array()
+            .and_then(|entries| entries.first())
+            .and_then(|first| first.get("schema_version"))
+            .and_then(Value::as_u64)
+            .map(|version| version as u32)
+            .unwrap_or(0)
+    }
+
+    /// Load a raw trace document, migrating from whatever version it was
+    /// written in.
+    pub fn from_value(value: Value) -> Result<Self, SchemaError> {
+        let version = Self::detect_version(&value);
+        migrate(value, version)
+    }
+}
+
+/// Error produced when a trace document cannot be loaded or migrated.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The document claims a format version this build does not know
+    UnsupportedVersion(u32),
+    /// The document does not match the expected shape for its version
+    Malformed(String),
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::UnsupportedVersion(version) => {
+                write!(f, "unsupported trace schema version {version}")
+            }
+            SchemaError::Malformed(reason) => write!(f, "malformed trace document: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Migrate a raw trace document from `from_version` into the current
+/// [`TraceFile`] shape.
+///
+/// Each supported version gets an explicit arm here; bumping
+/// [`SCHEMA_VERSION`] without teaching this function the old shape is the
+/// bug this function exists to prevent.
+pub fn migrate(value: Value, from_version: u32) -> Result<TraceFile, SchemaError> {
+    let Value::Array(entries) = value else {
+        return Err(SchemaError::Malformed(
+            "expected a top-level JSON array".to_string(),
+        ));
+    };
+
+    match from_version {
+        // Bare array of records, written before the header existed; the
+        // synthesized header marks the file as migrated rather than
+        // claiming tool metadata it never had
+        0 => Ok(TraceFile {
+            header: TraceHeader::default(),
+            records: parse_records(entries.into_iter(), 0)?,
+        }),
+        1 => {
+            let mut entries = entries.into_iter();
+            let header = entries
+                .next()
+                .ok_or_else(|| SchemaError::Malformed("missing header element".to_string()))?;
+            let header: TraceHeader = serde_json::from_value(header)
+                .map_err(|e| SchemaError::Malformed(format!("bad header: {e}")))?;
+            Ok(TraceFile {
+                header,
+                records: parse_records(entries, 1)?,
+            })
+        }
+        version => Err(SchemaError::UnsupportedVersion(version)),
+    }
+}
+
+fn parse_records(
+    entries: impl Iterator<Item = Value>,
+    offset: usize,
+) -> Result<Vec<CallData>, SchemaError> {
+    entries
+        .enumerate()
+        .map(|(index, entry)| {
+            serde_json::from_value(entry).map_err(|e| {
+                SchemaError::Malformed(format!("bad record at index {}: {e}", index + offset))
+            })
+        })
+        .collect()
+}
+
+/// One element of a trace file's top-level array: the header comes first,
+/// every later element is a recorded call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum TraceEntry {
+    Header(Box<TraceHeader>),
+    Call(Box<CallData>),
+}
+
+/// Generate a JSON Schema document describing the trace file format.
+///
+/// The document covers the whole file — the top-level array, the header
+/// and every record field — so CI validators and external tools can check
+/// runtime output mechanically instead of coding against examples.
+///
+/// # Examples
+///
+/// ```
+/// let schema = trace_common::schema::json_schema();
+/// assert_eq!(schema["type"], "array");
+/// assert!(schema["definitions"]["CallNode"].is_object());
+/// ```
+pub fn json_schema() -> Value {
+    /// Newtype standing in for the file's top-level array shape
+    #[derive(JsonSchema)]
+    #[schemars(rename = "TraceFile")]
+    #[allow(dead_code)]
+    struct TraceFileDocument(Vec<TraceEntry>);
+
+    serde_json::to_value(schemars::schema_for!(TraceFileDocument))
+        .expect("generated schema is valid JSON")
+}
+
+/// One incremental event in a streaming trace protocol.
+///
+/// The array-of-[`CallData`] file format only holds calls that completed;
+/// a sink emitting these events as they happen can also represent calls
+/// still in flight when the process died, and a reader can rebuild the
+/// call tree from the `Enter`/`Exit` pairs it did receive.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::schema::TraceEvent;
+///
+/// let event = TraceEvent::Enter {
+///     call_id: 7,
+///     parent_id: None,
+///     name: "parse".to_string(),
+///     file: "src/lib.rs".to_string(),
+///     line: 10,
+///     thread_id: "ThreadId(1)".to_string(),
+///     timestamp_utc: "2023-01-01T12:00:00Z".to_string(),
+/// };
+/// let json = serde_json::to_value(&event).unwrap();
+/// assert_eq!(json["event"], "enter");
+/// assert_eq!(json["call_id"], 7);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// A new tracing session began; carries the file header metadata
+    SessionStart(Box<TraceHeader>),
+    /// A traced call was entered
+    Enter {
+        call_id: u64,
+        /// The enclosing call, or `None` for a top-level entry
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_id: Option<u64>,
+        name: String,
+        file: String,
+        line: u32,
+        thread_id: String,
+        timestamp_utc: String,
+    },
+    /// A traced call returned; a call without a matching `Exit` was still
+    /// running when the stream ended
+    Exit {
+        call_id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output: Option<Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ns: Option<u64>,
+    },
+    /// An ad-hoc checkpoint fired inside a running call
+    Checkpoint {
+        call_id: u64,
+        #[serde(flatten)]
+        checkpoint: CheckpointEvent,
+    },
+    /// A complete record, for sinks that batch finished calls
+    Record(Box<CallData>),
+}
+
+impl TraceEvent {
+    /// The call this event belongs to, if it concerns a single call
+    pub fn call_id(&self) -> Option<u64> {
+        match self {
+            TraceEvent::Enter { call_id, .. }
+            | TraceEvent::Exit { call_id, .. }
+            | TraceEvent::Checkpoint { call_id, .. } => Some(*call_id),
+            TraceEvent::Record(record) => Some(record.root_node.call_id),
+            TraceEvent::SessionStart(_) => None,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Timestamp representation, with and without `chrono`.
+//!
+//! Projects that embed this crate in build scripts or minimal targets pay
+//! for `chrono` mostly in compile time. The default `chrono` feature keeps
+//! the rich `DateTime<Utc>` type; building with
+//! `default-features = false` swaps [`Timestamp`] for a plain RFC 3339
+//! string produced from `std::time::SystemTime`, with second precision.
+//! Both serialize to the same wire format.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The type `TraceData` stores its creation time as
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// The type `TraceData` stores its creation time as
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = String;
+
+/// The current UTC time as a [`Timestamp`]
+#[cfg(feature = "chrono")]
+pub fn now() -> Timestamp {
+    chrono::Utc::now()
+}
+
+/// The current UTC time as a [`Timestamp`]
+#[cfg(not(feature = "chrono"))]
+pub fn now() -> Timestamp {
+    system_time_to_rfc3339(SystemTime::now())
+}
+
+/// Format a `SystemTime` as an RFC 3339 UTC timestamp with second
+/// precision, without going through `chrono`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use trace_common::time::system_time_to_rfc3339;
+///
+/// let time = UNIX_EPOCH + Duration::from_secs(1_672_574_405);
+/// assert_eq!(system_time_to_rfc3339(time), "2023-01-01T12:00:05Z");
+/// ```
+pub fn system_time_to_rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let in_day = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        in_day / 3_600,
+        (in_day % 3_600) / 60,
+        in_day % 60,
+    )
+}
+
+/// Convert days since the Unix epoch into a civil (year, month, day);
+/// Howard Hinnant's `civil_from_days` algorithm
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
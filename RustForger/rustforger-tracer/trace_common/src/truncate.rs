@@ -0,0 +1,138 @@
+//! Shared value truncation for captured trace data.
+//!
+//! Both the runtime (when recording) and the CLI (when displaying) need to
+//! cap oversized values; this module is the single implementation so the
+//! `<truncated>` markers look the same everywhere.
+
+use serde_json::Value;
+
+/// Marker appended to strings cut short by truncation
+pub const TRUNCATION_MARKER: &str = "<truncated>";
+
+/// Limits applied by [`truncate_value`]; unset limits leave that
+/// dimension untouched.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::truncate::{truncate_value, TruncateLimits};
+/// use serde_json::json;
+///
+/// let limits = TruncateLimits::new().with_max_string_len(4);
+/// let mut value = json!({"note": "abcdefgh"});
+/// truncate_value(&mut value, &limits);
+/// assert_eq!(value["note"], "abcd<truncated>");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TruncateLimits {
+    /// Longest string kept, in characters
+    max_string_len: Option<usize>,
+    /// Most elements kept per array
+    max_array_elements: Option<usize>,
+    /// Deepest nesting kept; containers below this become a marker
+    max_depth: Option<usize>,
+}
+
+impl TruncateLimits {
+    /// Create a limit set that truncates nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most `len` characters of each string
+    pub fn with_max_string_len(mut self, len: usize) -> Self {
+        self.max_string_len = Some(len);
+        self
+    }
+
+    /// Keep at most `count` elements of each array
+    pub fn with_max_array_elements(mut self, count: usize) -> Self {
+        self.max_array_elements = Some(count);
+        self
+    }
+
+    /// Replace containers nested more than `depth` levels deep
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Returns true if no limit would ever modify a value
+    pub fn is_empty(&self) -> bool {
+        self.max_string_len.is_none()
+            && self.max_array_elements.is_none()
+            && self.max_depth.is_none()
+    }
+}
+
+/// Truncate a JSON value in place according to `limits`.
+///
+/// Oversized strings keep their prefix and gain a `<truncated>` suffix;
+/// oversized arrays keep their leading elements plus a marker telling how
+/// many were dropped; containers nested deeper than the depth limit are
+/// replaced wholesale with a marker.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::truncate::{truncate_value, TruncateLimits};
+/// use serde_json::json;
+///
+/// let limits = TruncateLimits::new().with_max_array_elements(2);
+/// let mut value = json!([1, 2, 3, 4]);
+/// truncate_value(&mut value, &limits);
+/// assert_eq!(value, json!([1, 2, "<truncated: 2 more elements>"]));
+/// ```
+pub fn truncate_value(value: &mut Value, limits: &TruncateLimits) {
+    truncate_at_depth(value, limits, 0);
+}
+
+fn truncate_at_depth(value: &mut Value, limits: &TruncateLimits, depth: usize) {
+    match value {
+        Value::String(s) => {
+            if let Some(max_len) = limits.max_string_len {
+                if s.chars().count() > max_len {
+                    let truncated: String = s.chars().take(max_len).collect();
+                    *s = format!("{}{}", truncated, TRUNCATION_MARKER);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if exceeds_depth(limits, depth) {
+                *value = depth_marker();
+                return;
+            }
+            if let Some(max_elements) = limits.max_array_elements {
+                if items.len() > max_elements {
+                    let dropped = items.len() - max_elements;
+                    items.truncate(max_elements);
+                    items.push(Value::String(format!(
+                        "<truncated: {} more elements>",
+                        dropped
+                    )));
+                }
+            }
+            for item in items {
+                truncate_at_depth(item, limits, depth + 1);
+            }
+        }
+        Value::Object(map) => {
+            if exceeds_depth(limits, depth) {
+                *value = depth_marker();
+                return;
+            }
+            for (_, item) in map.iter_mut() {
+                truncate_at_depth(item, limits, depth + 1);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn exceeds_depth(limits: &TruncateLimits, depth: usize) -> bool {
+    limits.max_depth.is_some_and(|max_depth| depth >= max_depth)
+}
+
+fn depth_marker() -> Value {
+    Value::String("<truncated: max depth>".to_string())
+}
@@ -0,0 +1,206 @@
+//! Structural validation of trace files.
+//!
+//! The CLI's inspection commands and CI pipelines both want to answer "is
+//! this trace file well-formed?" without writing the checks twice; this
+//! module runs them as a library call and returns structured findings
+//! instead of printing or failing on the first problem.
+
+use crate::schema::{CallData, TraceFile, SCHEMA_VERSION};
+use crate::Error;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// How serious one finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Suspicious but loadable, e.g. timestamps that go backwards
+    Warning,
+    /// The file (or one record) violates the format
+    Error,
+}
+
+/// One problem found in a trace document
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Index of the offending record, counted over records only (the
+    /// header is not a record); `None` for file-level findings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record: Option<usize>,
+    pub message: String,
+}
+
+/// The outcome of validating one trace document.
+///
+/// # Examples
+///
+/// ```
+/// use trace_common::validate::validate_value;
+/// use serde_json::json;
+///
+/// let report = validate_value(&json!([
+///     {"timestamp_utc": "2023-01-01T12:00:00Z", "thread_id": "1",
+///      "root_node": {"name": "f", "file": "a.rs", "line": 1, "children": []},
+///      "inputs": {}, "output": null},
+/// ]));
+/// assert!(report.is_valid());
+/// assert_eq!(report.record_count, 1);
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    /// Format version the document was written in
+    pub schema_version: u32,
+    /// Number of records the document holds
+    pub record_count: usize,
+    pub findings: Vec<Finding>,
+}
+
+impl ValidationReport {
+    /// True when no error-severity finding was produced; warnings do not
+    /// make a file invalid
+    pub fn is_valid(&self) -> bool {
+        !self
+            .findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+    }
+
+    fn push(&mut self, severity: Severity, record: Option<usize>, message: String) {
+        self.findings.push(Finding {
+            severity,
+            record,
+            message,
+        });
+    }
+}
+
+/// Validate the trace file at `path`.
+///
+/// IO and JSON parse failures are returned as [`Error`]; everything the
+/// document itself gets wrong comes back as findings in the report.
+pub fn validate_file(path: impl AsRef<Path>) -> Result<ValidationReport, Error> {
+    let content = std::fs::read_to_string(path)?;
+    let document: Value = serde_json::from_str(&content)?;
+    Ok(validate_value(&document))
+}
+
+/// Validate an already parsed trace document
+pub fn validate_value(document: &Value) -> ValidationReport {
+    let mut report = ValidationReport {
+        schema_version: TraceFile::detect_version(document),
+        record_count: 0,
+        findings: Vec::new(),
+    };
+
+    let Some(entries) = document.as_array() else {
+        report.push(
+            Severity::Error,
+            None,
+            "expected a top-level JSON array".to_string(),
+        );
+        return report;
+    };
+
+    if report.schema_version > SCHEMA_VERSION {
+        report.push(
+            Severity::Error,
+            None,
+            format!(
+                "unsupported schema version {} (this build reads up to {})",
+                report.schema_version, SCHEMA_VERSION
+            ),
+        );
+        return report;
+    }
+
+    // From version 1 on the first element is the header
+    let records = if report.schema_version >= 1 {
+        let (header, records) = entries.split_first().expect("version detection saw it");
+        if let Err(e) = serde_json::from_value::<crate::schema::TraceHeader>(header.clone()) {
+            report.push(Severity::Error, None, format!("bad header: {e}"));
+        }
+        records
+    } else {
+        entries.as_slice()
+    };
+
+    report.record_count = records.len();
+    let mut previous_timestamp = None;
+
+    for (index, entry) in records.iter().enumerate() {
+        let record: CallData = match serde_json::from_value(entry.clone()) {
+            Ok(record) => record,
+            Err(e) => {
+                report.push(Severity::Error, Some(index), format!("bad record: {e}"));
+                continue;
+            }
+        };
+
+        match timestamp_key(&record.timestamp_utc) {
+            Some(timestamp) => {
+                if previous_timestamp
+                    .as_ref()
+                    .is_some_and(|previous| &timestamp < previous)
+                {
+                    report.push(
+                        Severity::Warning,
+                        Some(index),
+                        format!(
+                            "timestamp {} is earlier than the previous record's",
+                            record.timestamp_utc
+                        ),
+                    );
+                }
+                previous_timestamp = Some(timestamp);
+            }
+            None => report.push(
+                Severity::Warning,
+                Some(index),
+                format!("unparseable timestamp {:?}", record.timestamp_utc),
+            ),
+        }
+
+        check_tree(&record, index, &mut report);
+    }
+
+    report
+}
+
+/// Tree integrity checks on one record's call tree
+fn check_tree(record: &CallData, index: usize, report: &mut ValidationReport) {
+    let mut seen_call_ids = HashSet::new();
+    for node in record.root_node.flatten() {
+        if node.name.is_empty() {
+            report.push(
+                Severity::Error,
+                Some(index),
+                format!("node with call_id {} has an empty name", node.call_id),
+            );
+        }
+        // call_id 0 is the pre-ID default and may repeat in old files
+        if node.call_id != 0 && !seen_call_ids.insert(node.call_id) {
+            report.push(
+                Severity::Error,
+                Some(index),
+                format!("call_id {} appears more than once in the tree", node.call_id),
+            );
+        }
+    }
+}
+
+/// An ordering key for one record's timestamp
+#[cfg(feature = "chrono")]
+fn timestamp_key(raw: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    chrono::DateTime::parse_from_rfc3339(raw).ok()
+}
+
+/// Without `chrono` the strings themselves are compared; RFC 3339
+/// timestamps sharing an offset (ours are always UTC) order
+/// lexicographically
+#[cfg(not(feature = "chrono"))]
+fn timestamp_key(raw: &str) -> Option<String> {
+    (!raw.is_empty()).then(|| raw.to_string())
+}
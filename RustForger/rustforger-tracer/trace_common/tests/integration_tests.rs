@@ -8,7 +8,6 @@ use std::rc::Rc;
 
 use chrono::{DateTime, TimeZone, Utc};
 use serde::Serialize;
-use serde_json;
 
 use trace_common::*;
 
@@ -80,12 +79,19 @@ mod trace_data_tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
     fn serialization_roundtrip() {
         let trace = TraceData {
             timestamp: Utc.with_ymd_and_hms(2023, 1, 1, 12, 0, 0).unwrap(),
             function_name: "test_fn".to_string(),
             args: serde_json::json!({"x": 1}),
             result: Some(serde_json::json!({"y": 2})),
+            duration_micros: Some(1_250),
+            error: None,
+            metadata: std::collections::BTreeMap::from([(
+                "git_sha".to_string(),
+                serde_json::json!("a1b2c3d"),
+            )]),
         };
 
         let serialized = serde_json::to_string(&trace).unwrap();
@@ -93,6 +99,46 @@ mod trace_data_tests {
 
         assert_eq!(trace, deserialized);
     }
+
+    #[test]
+    fn builder_sets_duration_and_error() {
+        let trace = TraceData::new("test_function", serde_json::json!({}))
+            .with_duration_micros(42)
+            .with_error(serde_json::json!("boom"));
+
+        assert_eq!(trace.duration_micros, Some(42));
+        assert_eq!(trace.error, Some(serde_json::json!("boom")));
+    }
+
+    #[test]
+    fn builder_attaches_metadata_entries() {
+        let trace = TraceData::new("test_function", serde_json::json!({}))
+            .with_metadata("git_sha", serde_json::json!("a1b2c3d"))
+            .with_metadata("hostname", serde_json::json!("ci-runner-3"));
+
+        assert_eq!(trace.metadata.len(), 2);
+        assert_eq!(trace.metadata["hostname"], serde_json::json!("ci-runner-3"));
+
+        // Empty maps stay off the wire so old readers are unaffected
+        let empty = TraceData::new("test_function", serde_json::json!({}));
+        let serialized = serde_json::to_value(&empty).unwrap();
+        assert!(serialized.get("metadata").is_none());
+    }
+
+    #[test]
+    fn deserializes_entries_without_the_newer_fields() {
+        // Entries written before duration/error existed still round-trip
+        let legacy = r#"{
+            "timestamp": "2023-01-01T12:00:00Z",
+            "function_name": "test_fn",
+            "args": {"x": 1},
+            "result": null
+        }"#;
+
+        let trace: TraceData = serde_json::from_str(legacy).unwrap();
+        assert_eq!(trace.duration_micros, None);
+        assert_eq!(trace.error, None);
+    }
 }
 
 /// Tests for serialization functions and macros
@@ -103,7 +149,7 @@ mod serialization_tests {
     fn serialize_basic_types() {
         // Test primitive types
         assert_eq!(serialize_value(&42i32), serde_json::json!(42));
-        assert_eq!(serialize_value(&3.14f64), serde_json::json!(3.14));
+        assert_eq!(serialize_value(&2.5f64), serde_json::json!(2.5));
         assert_eq!(serialize_value(&true), serde_json::json!(true));
         assert_eq!(serialize_value(&"hello"), serde_json::json!("hello"));
 
@@ -351,3 +397,916 @@ mod reexport_tests {
         assert!(now.timestamp() > 0);
     }
 }
+
+/// Tests for the redaction engine
+mod redaction_tests {
+    use trace_common::redact::RedactionRules;
+
+    #[test]
+    fn redact_by_key() {
+        let rules = RedactionRules::new().with_key("password");
+        let mut value = serde_json::json!({
+            "user": "alice",
+            "password": "hunter2",
+            "nested": {"PASSWORD": "secret"}
+        });
+
+        rules.apply(&mut value);
+
+        assert_eq!(value["password"], "<redacted>");
+        assert_eq!(value["nested"]["PASSWORD"], "<redacted>");
+        assert_eq!(value["user"], "alice");
+    }
+
+    #[test]
+    fn redact_by_key_pattern() {
+        let rules = RedactionRules::new().with_key_pattern("(?i)token").unwrap();
+        let mut value = serde_json::json!({"auth_token": "abc", "count": 2});
+
+        rules.apply(&mut value);
+
+        assert_eq!(value["auth_token"], "<redacted>");
+        assert_eq!(value["count"], 2);
+    }
+
+    #[test]
+    fn redact_by_value_pattern() {
+        let rules = RedactionRules::new()
+            .with_value_pattern(r"\d{16}")
+            .unwrap();
+        let mut value = serde_json::json!({"note": "card 4111111111111111 used"});
+
+        rules.apply(&mut value);
+
+        assert_eq!(value["note"], "card <redacted> used");
+    }
+
+    #[test]
+    fn truncate_long_strings() {
+        let rules = RedactionRules::new().with_max_string_len(4);
+        let mut value = serde_json::json!({"blob": "abcdefgh", "short": "ok"});
+
+        rules.apply(&mut value);
+
+        assert_eq!(value["blob"], "abcd<truncated>");
+        assert_eq!(value["short"], "ok");
+    }
+
+    #[test]
+    fn custom_replacement() {
+        let rules = RedactionRules::new()
+            .with_key("secret")
+            .with_replacement("[hidden]");
+        let mut value = serde_json::json!({"secret": 42});
+
+        rules.apply(&mut value);
+
+        assert_eq!(value["secret"], "[hidden]");
+    }
+
+    #[test]
+    fn empty_rules_detection() {
+        assert!(RedactionRules::new().is_empty());
+        assert!(!RedactionRules::new().with_key("x").is_empty());
+    }
+}
+
+/// Tests for the versioned trace file envelope
+mod schema_tests {
+    use serde_json::json;
+    use trace_common::schema::{migrate, SchemaError, TraceFile, SCHEMA_VERSION};
+
+    fn record() -> serde_json::Value {
+        json!({
+            "timestamp_utc": "2023-01-01T12:00:00Z",
+            "thread_id": "ThreadId(1)",
+            "root_node": {"name": "add", "file": "src/lib.rs", "line": 3, "children": []},
+            "inputs": {"x": 1},
+            "output": 2,
+        })
+    }
+
+    #[test]
+    fn version_is_detected_from_the_first_element() {
+        assert_eq!(TraceFile::detect_version(&json!([record()])), 0);
+        assert_eq!(
+            TraceFile::detect_version(&json!([{"schema_version": 1}, record()])),
+            1
+        );
+    }
+
+    #[test]
+    fn headerless_files_migrate_to_the_current_shape() {
+        let file = TraceFile::from_value(json!([record(), record()])).unwrap();
+
+        assert_eq!(file.header.schema_version, SCHEMA_VERSION);
+        assert_eq!(file.records.len(), 2);
+        assert_eq!(file.records[0].root_node.name, "add");
+    }
+
+    #[test]
+    fn current_files_load_header_and_records() {
+        let header = json!({
+            "schema_version": 1,
+            "tool_version": "0.1.0",
+            "start_time": "2023-01-01T12:00:00Z",
+            "hostname": "box",
+            "cmdline": ["app"],
+        });
+        let file = TraceFile::from_value(json!([header, record()])).unwrap();
+
+        assert_eq!(file.header.tool_version, "0.1.0");
+        assert_eq!(file.records.len(), 1);
+    }
+
+    #[test]
+    fn unknown_versions_are_rejected_not_misread() {
+        let result = migrate(json!([{"schema_version": 99}]), 99);
+        assert!(matches!(result, Err(SchemaError::UnsupportedVersion(99))));
+    }
+
+    #[test]
+    fn malformed_records_report_their_index() {
+        let result = TraceFile::from_value(json!([record(), {"not": "a record"}]));
+        match result {
+            Err(SchemaError::Malformed(reason)) => assert!(reason.contains("index 1"), "{reason}"),
+            other => panic!("expected a malformed error, got {other:?}"),
+        }
+    }
+}
+
+/// Tests for mechanical JSON Schema generation
+mod json_schema_tests {
+    use trace_common::schema::json_schema;
+
+    #[test]
+    fn the_document_describes_the_whole_file_shape() {
+        let schema = json_schema();
+
+        assert_eq!(schema["type"], "array");
+        for definition in ["TraceHeader", "CallData", "CallNode", "CheckpointEvent"] {
+            assert!(
+                schema["definitions"][definition].is_object(),
+                "missing definition for {definition}"
+            );
+        }
+    }
+
+    #[test]
+    fn required_record_fields_are_marked_required() {
+        let schema = json_schema();
+
+        let required = schema["definitions"]["CallData"]["required"]
+            .as_array()
+            .expect("CallData lists required fields");
+        for field in ["timestamp_utc", "thread_id", "root_node", "inputs", "output"] {
+            assert!(required.iter().any(|r| r == field), "{field} should be required");
+        }
+        // Optional fields must not be, or old files would fail validation
+        assert!(!required.iter().any(|r| r == "duration_ns"));
+    }
+}
+
+/// Tests for shared value truncation
+mod truncate_tests {
+    use serde_json::json;
+    use trace_common::truncate::{truncate_value, TruncateLimits};
+
+    #[test]
+    fn long_strings_keep_their_prefix() {
+        let limits = TruncateLimits::new().with_max_string_len(3);
+        let mut value = json!({"a": "abcdef", "b": "ok"});
+
+        truncate_value(&mut value, &limits);
+
+        assert_eq!(value["a"], "abc<truncated>");
+        assert_eq!(value["b"], "ok");
+    }
+
+    #[test]
+    fn long_arrays_report_how_many_were_dropped() {
+        let limits = TruncateLimits::new().with_max_array_elements(2);
+        let mut value = json!({"items": [1, 2, 3, 4, 5]});
+
+        truncate_value(&mut value, &limits);
+
+        assert_eq!(value["items"], json!([1, 2, "<truncated: 3 more elements>"]));
+    }
+
+    #[test]
+    fn deep_nesting_is_cut_at_the_depth_limit() {
+        let limits = TruncateLimits::new().with_max_depth(2);
+        let mut value = json!({"a": {"b": {"c": 1}}});
+
+        truncate_value(&mut value, &limits);
+
+        assert_eq!(value, json!({"a": {"b": "<truncated: max depth>"}}));
+    }
+
+    #[test]
+    fn empty_limits_change_nothing() {
+        let limits = TruncateLimits::new();
+        assert!(limits.is_empty());
+
+        let original = json!({"a": ["x".repeat(10_000)]});
+        let mut value = original.clone();
+        truncate_value(&mut value, &limits);
+
+        assert_eq!(value, original);
+    }
+}
+
+/// Tests for key glob redaction rules
+mod redact_glob_tests {
+    use serde_json::json;
+    use trace_common::redact::{apply, RedactionRules};
+
+    #[test]
+    fn globs_match_whole_keys_case_insensitively() {
+        let rules = RedactionRules::new().with_key_glob("*_key").unwrap();
+        let mut value = json!({
+            "api_key": "secret",
+            "API_KEY": "secret",
+            "key_holder": "public",
+        });
+
+        apply(&mut value, &rules);
+
+        assert_eq!(value["api_key"], "<redacted>");
+        assert_eq!(value["API_KEY"], "<redacted>");
+        assert_eq!(value["key_holder"], "public");
+    }
+
+    #[test]
+    fn question_mark_matches_exactly_one_character() {
+        let rules = RedactionRules::new().with_key_glob("pin?").unwrap();
+        let mut value = json!({"pin1": "1234", "pin": "567", "pin12": "89"});
+
+        apply(&mut value, &rules);
+
+        assert_eq!(value["pin1"], "<redacted>");
+        assert_eq!(value["pin"], "567");
+        assert_eq!(value["pin12"], "89");
+    }
+
+    #[test]
+    fn regex_metacharacters_in_globs_are_literal() {
+        let rules = RedactionRules::new().with_key_glob("a.b").unwrap();
+        let mut value = json!({"a.b": "x", "aXb": "y"});
+
+        apply(&mut value, &rules);
+
+        assert_eq!(value["a.b"], "<redacted>");
+        assert_eq!(value["aXb"], "y");
+    }
+}
+
+/// Tests for structured value diffing
+mod diff_tests {
+    use serde_json::json;
+    use trace_common::diff::{diff, ElementDiff, ValueDiff};
+
+    #[test]
+    fn equal_values_diff_as_unchanged() {
+        let value = json!({"a": [1, {"b": 2}]});
+        assert!(diff(&value, &value.clone()).is_unchanged());
+    }
+
+    #[test]
+    fn object_diffs_cover_changed_added_and_removed_keys() {
+        let before = json!({"kept": 1, "changed": 2, "removed": 3});
+        let after = json!({"kept": 1, "changed": 20, "added": 4});
+
+        let ValueDiff::Object { entries } = diff(&before, &after) else {
+            panic!("expected an object diff");
+        };
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries["changed"],
+            ValueDiff::Changed { before: json!(2), after: json!(20) }
+        );
+        assert_eq!(entries["removed"], ValueDiff::Removed { value: json!(3) });
+        assert_eq!(entries["added"], ValueDiff::Added { value: json!(4) });
+    }
+
+    #[test]
+    fn array_diffs_list_differing_indexes_only() {
+        let before = json!([1, 2, 3]);
+        let after = json!([1, 20, 3, 4]);
+
+        let ValueDiff::Array { entries } = diff(&before, &after) else {
+            panic!("expected an array diff");
+        };
+
+        assert_eq!(
+            entries,
+            vec![
+                ElementDiff {
+                    index: 1,
+                    diff: ValueDiff::Changed { before: json!(2), after: json!(20) },
+                },
+                ElementDiff { index: 3, diff: ValueDiff::Added { value: json!(4) } },
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_serialize_with_a_kind_tag() {
+        let serialized = serde_json::to_value(diff(&json!(1), &json!("x"))).unwrap();
+        assert_eq!(
+            serialized,
+            json!({"kind": "changed", "before": 1, "after": "x"})
+        );
+    }
+
+    #[test]
+    fn nested_differences_stay_nested() {
+        let before = json!({"outer": {"inner": [1, 2]}});
+        let after = json!({"outer": {"inner": [1, 5]}});
+
+        let serialized = serde_json::to_value(diff(&before, &after)).unwrap();
+        assert_eq!(
+            serialized["entries"]["outer"]["entries"]["inner"]["entries"][0],
+            json!({"index": 1, "kind": "changed", "before": 2, "after": 5})
+        );
+    }
+}
+
+/// Tests for the streaming trace reader
+mod reader_tests {
+    use std::io::Cursor;
+    use trace_common::reader::TraceReader;
+
+    fn record_json(name: &str) -> String {
+        format!(
+            r#"{{"timestamp_utc": "t", "thread_id": "1", "inputs": {{}}, "output": null, "root_node": {{"name": "{name}", "file": "a.rs", "line": 1, "children": []}}}}"#
+        )
+    }
+
+    #[test]
+    fn array_files_stream_records_and_skip_the_header() {
+        let document = format!(
+            r#"[{{"schema_version": 1, "tool_version": "x"}}, {}, {}]"#,
+            record_json("first"),
+            record_json("second"),
+        );
+
+        let names: Vec<String> = TraceReader::new(Cursor::new(document))
+            .map(|record| record.root_node.name)
+            .collect();
+
+        assert_eq!(names, ["first", "second"]);
+    }
+
+    #[test]
+    fn jsonl_files_stream_one_record_per_line() {
+        let document = format!("{}\n{}\n", record_json("a"), record_json("b"));
+
+        let names: Vec<String> = TraceReader::new(Cursor::new(document))
+            .map(|record| record.root_node.name)
+            .collect();
+
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    #[test]
+    fn length_prefix_framing_lines_are_skipped() {
+        let payload = record_json("framed");
+        let document = format!("{}\n{}\n", payload.len(), payload);
+
+        let names: Vec<String> = TraceReader::new(Cursor::new(document))
+            .map(|record| record.root_node.name)
+            .collect();
+
+        assert_eq!(names, ["framed"]);
+    }
+
+    #[test]
+    fn a_truncated_tail_ends_iteration_cleanly() {
+        let intact = record_json("intact");
+        let truncated = &record_json("lost")[..40];
+        let document = format!("[{intact}, {truncated}");
+
+        let names: Vec<String> = TraceReader::new(Cursor::new(document))
+            .map(|record| record.root_node.name)
+            .collect();
+
+        assert_eq!(names, ["intact"]);
+    }
+
+    #[test]
+    fn empty_input_yields_nothing() {
+        assert_eq!(TraceReader::new(Cursor::new("")).count(), 0);
+        assert_eq!(TraceReader::new(Cursor::new("[]")).count(), 0);
+    }
+}
+
+/// Tests for the streaming event protocol type
+mod trace_event_tests {
+    use serde_json::json;
+    use trace_common::schema::{CheckpointEvent, TraceEvent};
+
+    #[test]
+    fn events_round_trip_with_their_tag() {
+        let events = vec![
+            TraceEvent::Enter {
+                call_id: 1,
+                parent_id: None,
+                name: "outer".to_string(),
+                file: "src/lib.rs".to_string(),
+                line: 5,
+                thread_id: "ThreadId(1)".to_string(),
+                timestamp_utc: "t0".to_string(),
+            },
+            TraceEvent::Checkpoint {
+                call_id: 1,
+                checkpoint: CheckpointEvent {
+                    timestamp_utc: "t1".to_string(),
+                    name: "halfway".to_string(),
+                    data: json!({"step": 2}),
+                },
+            },
+            TraceEvent::Exit { call_id: 1, output: Some(json!(42)), duration_ns: Some(900) },
+        ];
+
+        for event in events {
+            let serialized = serde_json::to_value(&event).unwrap();
+            assert!(serialized["event"].is_string(), "{serialized}");
+            let back: TraceEvent = serde_json::from_value(serialized).unwrap();
+            assert_eq!(back, event);
+        }
+    }
+
+    #[test]
+    fn an_unmatched_enter_represents_a_call_that_never_returned() {
+        let stream = json!([
+            {"event": "enter", "call_id": 1, "name": "a", "file": "a.rs", "line": 1,
+             "thread_id": "ThreadId(1)", "timestamp_utc": "t0"},
+            {"event": "enter", "call_id": 2, "parent_id": 1, "name": "b", "file": "a.rs",
+             "line": 2, "thread_id": "ThreadId(1)", "timestamp_utc": "t1"},
+            {"event": "exit", "call_id": 2, "output": 7},
+        ]);
+
+        let events: Vec<TraceEvent> = serde_json::from_value(stream).unwrap();
+        let exited: Vec<u64> = events
+            .iter()
+            .filter(|event| matches!(event, TraceEvent::Exit { .. }))
+            .filter_map(TraceEvent::call_id)
+            .collect();
+
+        // Call 1 was still in flight when the stream ended
+        assert_eq!(exited, [2]);
+    }
+
+    #[test]
+    fn checkpoint_payloads_are_flattened() {
+        let event = TraceEvent::Checkpoint {
+            call_id: 3,
+            checkpoint: CheckpointEvent {
+                timestamp_utc: "t".to_string(),
+                name: "mark".to_string(),
+                data: json!(null),
+            },
+        };
+
+        let serialized = serde_json::to_value(&event).unwrap();
+        assert_eq!(serialized["name"], "mark");
+        assert_eq!(serialized["call_id"], 3);
+    }
+}
+
+/// Tests for the binary encodings (run with `--features msgpack,cbor`)
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+mod binary_tests {
+    use serde_json::json;
+    use trace_common::schema::CallData;
+
+    fn sample_record() -> CallData {
+        serde_json::from_value(json!({
+            "timestamp_utc": "2023-01-01T12:00:00Z",
+            "thread_id": "ThreadId(1)",
+            "root_node": {
+                "name": "outer", "file": "src/lib.rs", "line": 3,
+                "children": [{"name": "inner", "file": "src/lib.rs", "line": 9, "children": []}],
+            },
+            "inputs": {"x": 1},
+            "output": 2,
+            "duration_ns": 1500,
+        }))
+        .unwrap()
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn call_data_round_trips_through_msgpack() {
+        use trace_common::binary::{from_msgpack, to_msgpack};
+
+        let record = sample_record();
+        let bytes = to_msgpack(&record).unwrap();
+        let back: CallData = from_msgpack(&bytes).unwrap();
+
+        assert_eq!(back, record);
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn call_data_round_trips_through_cbor() {
+        use trace_common::binary::{from_cbor, to_cbor};
+
+        let record = sample_record();
+        let bytes = to_cbor(&record).unwrap();
+        let back: CallData = from_cbor(&bytes).unwrap();
+
+        assert_eq!(back, record);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_uses_named_fields_for_compatibility() {
+        use trace_common::binary::to_msgpack;
+
+        let bytes = to_msgpack(&sample_record()).unwrap();
+        let as_json: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+
+        assert_eq!(as_json["root_node"]["name"], "outer");
+    }
+}
+
+/// Tests for string interning of repeated names and paths
+mod intern_tests {
+    use serde_json::json;
+    use trace_common::intern::{intern_strings, resolve_strings, StringTable};
+
+    #[test]
+    fn repeated_strings_share_one_table_entry() {
+        let mut table = StringTable::new();
+        let mut document = json!([
+            {"name": "hot_fn", "file": "src/hot.rs"},
+            {"name": "hot_fn", "file": "src/hot.rs"},
+            {"name": "other", "file": "src/hot.rs"},
+        ]);
+
+        intern_strings(&mut document, &mut table);
+
+        assert_eq!(table.len(), 3);
+        assert_eq!(document[0]["name"], document[1]["name"]);
+        assert_eq!(document[0]["file"], document[2]["file"]);
+    }
+
+    #[test]
+    fn interned_documents_resolve_back_to_the_original() {
+        let original = json!({
+            "root_node": {
+                "name": "outer", "file": "a.rs", "line": 1,
+                "children": [{"name": "inner", "file": "a.rs", "line": 2, "children": []}],
+            },
+            "thread_id": "ThreadId(1)",
+            "inputs": {"name": "user data stays"},
+            "output": null,
+        });
+
+        let mut document = original.clone();
+        let mut table = StringTable::new();
+        intern_strings(&mut document, &mut table);
+        assert_ne!(document, original);
+
+        resolve_strings(&mut document, &table);
+        assert_eq!(document, original);
+    }
+
+    #[test]
+    fn the_table_round_trips_as_a_plain_array() {
+        let mut table = StringTable::new();
+        table.intern("a");
+        table.intern("b");
+
+        let serialized = serde_json::to_value(&table).unwrap();
+        assert_eq!(serialized, json!(["a", "b"]));
+
+        let mut restored: StringTable = serde_json::from_value(serialized).unwrap();
+        assert_eq!(restored, table);
+        // Restored tables keep deduplicating against existing entries
+        assert_eq!(restored.intern("a"), 0);
+    }
+}
+
+/// Tests for the call tree builder and combinators
+mod call_tree_tests {
+    use trace_common::schema::CallTree;
+
+    fn sample() -> trace_common::schema::CallNode {
+        CallTree::root("outer")
+            .at("src/lib.rs", 3)
+            .child(
+                CallTree::root("middle")
+                    .at("src/lib.rs", 9)
+                    .child(CallTree::root("leaf_a"))
+                    .child(CallTree::root("leaf_b")),
+            )
+            .child(CallTree::root("leaf_c"))
+            .build()
+    }
+
+    #[test]
+    fn depth_and_count_walk_the_whole_tree() {
+        let tree = sample();
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(tree.count(), 5);
+    }
+
+    #[test]
+    fn find_searches_depth_first() {
+        let tree = sample();
+        assert_eq!(tree.find("leaf_b").unwrap().name, "leaf_b");
+        assert_eq!(tree.find("outer").unwrap().line, 3);
+        assert!(tree.find("missing").is_none());
+    }
+
+    #[test]
+    fn flatten_yields_preorder() {
+        let tree = sample();
+        let names: Vec<&str> = tree.flatten().iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, ["outer", "middle", "leaf_a", "leaf_b", "leaf_c"]);
+    }
+
+    #[test]
+    fn built_trees_serialize_like_recorded_ones() {
+        let serialized = serde_json::to_value(sample()).unwrap();
+        assert_eq!(serialized["children"][0]["children"][0]["name"], "leaf_a");
+        assert_eq!(serialized["file"], "src/lib.rs");
+    }
+}
+
+/// Tests for the shared ASCII tree renderer
+mod render_tests {
+    use serde_json::json;
+    use trace_common::render::{ascii_tree, RenderOptions};
+    use trace_common::schema::CallData;
+
+    fn record() -> CallData {
+        serde_json::from_value(json!({
+            "timestamp_utc": "2023-01-01T12:00:05Z",
+            "thread_id": "ThreadId(1)",
+            "root_node": {
+                "name": "outer", "file": "src/lib.rs", "line": 3,
+                "children": [
+                    {"name": "first", "file": "src/lib.rs", "line": 9, "children": [
+                        {"name": "nested", "file": "src/lib.rs", "line": 15, "children": []},
+                    ]},
+                    {"name": "second", "file": "src/lib.rs", "line": 21, "children": []},
+                ],
+            },
+            "inputs": {"x": 1},
+            "output": 2,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn the_tree_shows_locations_values_and_branches() {
+        let rendered = ascii_tree(&record(), &RenderOptions::default());
+
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "outer (lib.rs:3) [12:00:05]");
+        assert_eq!(lines[1], "  in:  {x}");
+        assert_eq!(lines[2], "  out: 2");
+        assert_eq!(lines[3], "  ├─ first (lib.rs:9)");
+        assert_eq!(lines[4], "  │  └─ nested (lib.rs:15)");
+        assert_eq!(lines[5], "  └─ second (lib.rs:21)");
+    }
+
+    #[test]
+    fn values_can_be_suppressed() {
+        let options = RenderOptions { show_values: false, ..RenderOptions::default() };
+        let rendered = ascii_tree(&record(), &options);
+
+        assert!(!rendered.contains("in:"));
+        assert!(!rendered.contains("out:"));
+    }
+
+    #[test]
+    fn depth_and_child_limits_are_honored() {
+        let options = RenderOptions { max_depth: 1, ..RenderOptions::default() };
+        let rendered = ascii_tree(&record(), &options);
+        assert!(rendered.contains("max depth reached"), "{rendered}");
+
+        let options = RenderOptions { max_children_per_node: 1, ..RenderOptions::default() };
+        let rendered = ascii_tree(&record(), &options);
+        assert!(rendered.contains("1 more children omitted"), "{rendered}");
+    }
+}
+
+/// Tests for the shared error type
+mod error_tests {
+    use std::error::Error as _;
+    use trace_common::schema::SchemaError;
+    use trace_common::Error;
+
+    #[test]
+    fn sources_are_chained() {
+        let io = Error::from(std::io::Error::new(std::io::ErrorKind::NotFound, "gone"));
+        assert!(io.source().unwrap().to_string().contains("gone"));
+
+        let config = Error::Config("bad option".to_string());
+        assert!(config.source().is_none());
+    }
+
+    #[test]
+    fn schema_errors_convert_and_display() {
+        let err = Error::from(SchemaError::UnsupportedVersion(7));
+        assert!(matches!(err, Error::Schema(_)));
+        assert_eq!(err.to_string(), "Schema error: unsupported trace schema version 7");
+    }
+
+    #[test]
+    fn the_question_mark_operator_absorbs_serde_failures() {
+        fn parse() -> Result<trace_common::TraceData, Error> {
+            Ok(serde_json::from_str("not json")?)
+        }
+
+        assert!(matches!(parse(), Err(Error::Serde(_))));
+    }
+}
+
+/// Tests for correlation ID generation
+mod correlation_tests {
+    use trace_common::schema::{generate_span_id, generate_trace_id};
+
+    #[test]
+    fn generated_ids_use_the_opentelemetry_hex_format() {
+        let trace_id = generate_trace_id();
+        assert_eq!(trace_id.len(), 32);
+        assert!(trace_id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(trace_id, "0".repeat(32), "all-zero IDs are invalid");
+
+        let span_id = generate_span_id();
+        assert_eq!(span_id.len(), 16);
+        assert!(span_id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(span_id, "0".repeat(16));
+    }
+
+    #[test]
+    fn successive_ids_are_distinct() {
+        let ids: Vec<String> = (0..32).map(|_| generate_span_id()).collect();
+        let mut deduped = ids.clone();
+        deduped.sort();
+        deduped.dedup();
+        assert_eq!(deduped.len(), ids.len());
+    }
+}
+
+/// Tests for trace file validation
+mod validate_tests {
+    use serde_json::json;
+    use trace_common::validate::{validate_file, validate_value, Severity};
+
+    fn record(timestamp: &str, call_id: u64) -> serde_json::Value {
+        json!({
+            "timestamp_utc": timestamp,
+            "thread_id": "ThreadId(1)",
+            "root_node": {
+                "call_id": call_id, "name": "f", "file": "a.rs", "line": 1,
+                "children": [],
+            },
+            "inputs": {},
+            "output": null,
+        })
+    }
+
+    #[test]
+    fn a_clean_document_produces_no_findings() {
+        let report = validate_value(&json!([
+            record("2023-01-01T12:00:00Z", 1),
+            record("2023-01-01T12:00:01Z", 2),
+        ]));
+
+        assert!(report.is_valid());
+        assert!(report.findings.is_empty());
+        assert_eq!(report.record_count, 2);
+        assert_eq!(report.schema_version, 0);
+    }
+
+    #[test]
+    fn backwards_timestamps_are_a_warning_not_an_error() {
+        let report = validate_value(&json!([
+            record("2023-01-01T12:00:05Z", 1),
+            record("2023-01-01T12:00:00Z", 2),
+        ]));
+
+        assert!(report.is_valid());
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].severity, Severity::Warning);
+        assert_eq!(report.findings[0].record, Some(1));
+    }
+
+    #[test]
+    fn duplicate_call_ids_in_a_tree_are_an_error() {
+        let mut entry = record("2023-01-01T12:00:00Z", 7);
+        entry["root_node"]["children"] = json!([
+            {"call_id": 7, "name": "g", "file": "a.rs", "line": 9, "children": []},
+        ]);
+
+        let report = validate_value(&json!([entry]));
+        assert!(!report.is_valid());
+        assert!(report.findings[0].message.contains("call_id 7"));
+    }
+
+    #[test]
+    fn unsupported_versions_and_malformed_records_are_reported() {
+        let report = validate_value(&json!([{"schema_version": 99}]));
+        assert!(!report.is_valid());
+        assert!(report.findings[0].message.contains("unsupported schema version 99"));
+
+        let report = validate_value(&json!([{"not": "a record"}]));
+        assert!(!report.is_valid());
+        assert_eq!(report.findings[0].record, Some(0));
+    }
+
+    #[test]
+    fn files_are_validated_from_disk() {
+        let path = std::env::temp_dir().join("rustforger_validate_test.json");
+        std::fs::write(&path, json!([record("2023-01-01T12:00:00Z", 1)]).to_string()).unwrap();
+
+        let report = validate_file(&path).unwrap();
+        assert!(report.is_valid());
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = validate_file("/definitely/not/here.json");
+        assert!(matches!(missing, Err(trace_common::Error::Io(_))));
+    }
+}
+
+/// Tests for byte-size estimation
+mod size_tests {
+    use serde_json::json;
+    use trace_common::{size_by_function, size_of_value};
+
+    #[test]
+    fn sizes_match_compact_serialization_exactly() {
+        let values = [
+            json!(null),
+            json!(true),
+            json!(-12.5),
+            json!("plain"),
+            json!("esc \"quotes\" and \\ and \n and \u{1} and \u{e9}"),
+            json!([]),
+            json!({}),
+            json!({"nested": {"list": [1, 2, 3], "flag": false}, "s": "x"}),
+        ];
+
+        for value in values {
+            assert_eq!(
+                size_of_value(&value),
+                serde_json::to_string(&value).unwrap().len(),
+                "size mismatch for {value}"
+            );
+        }
+    }
+
+    #[test]
+    fn sizes_aggregate_per_root_function_largest_first() {
+        let small: trace_common::schema::CallData = serde_json::from_value(json!({
+            "timestamp_utc": "t", "thread_id": "1", "inputs": {}, "output": null,
+            "root_node": {"name": "small_fn", "file": "a.rs", "line": 1, "children": []},
+        }))
+        .unwrap();
+        let mut large = small.clone();
+        large.root_node.name = "large_fn".to_string();
+        large.inputs = json!({"payload": "x".repeat(200)});
+
+        let report = size_by_function([&small, &large, &small]);
+        assert_eq!(report[0].0, "large_fn");
+        assert_eq!(report[1].0, "small_fn");
+        assert!(report[0].1 > report[1].1);
+    }
+}
+
+/// Tests for the chrono-free timestamp path
+mod time_tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use trace_common::time::system_time_to_rfc3339;
+
+    #[test]
+    fn formats_match_the_chrono_wire_format() {
+        let cases = [
+            (0, "1970-01-01T00:00:00Z"),
+            (951_868_800, "2000-03-01T00:00:00Z"),
+            (1_672_574_405, "2023-01-01T12:00:05Z"),
+            (4_107_542_399, "2100-02-28T23:59:59Z"),
+        ];
+
+        for (seconds, expected) in cases {
+            let time = UNIX_EPOCH + Duration::from_secs(seconds);
+            assert_eq!(system_time_to_rfc3339(time), expected);
+        }
+    }
+
+    #[test]
+    fn new_entries_get_a_current_timestamp() {
+        let trace = trace_common::TraceData::new("test_fn", serde_json::json!({}));
+        let serialized = serde_json::to_value(&trace).unwrap();
+        let timestamp = serialized["timestamp"].as_str().unwrap();
+        assert!(timestamp.starts_with("20"), "timestamp: {timestamp}");
+    }
+}
@@ -1,7 +1,6 @@
 // trace_macro/src/lib.rs
 
 use proc_macro::TokenStream;
-use proc_macro2;
 use quote::quote;
 use syn::{parse_macro_input, FnArg, ItemFn, Pat, Type, Expr, Block, Stmt, ExprCall};
 
@@ -12,6 +11,40 @@ struct PropagateConfig {
     #[allow(dead_code)]
     user_code_only: bool,
     max_depth: Option<usize>,
+    min_duration_ms: Option<u64>,
+    level: Option<String>,
+    sample_rate: Option<f64>,
+    no_inputs: bool,
+    no_output: bool,
+    tracing_compat: bool,
+    capture_child_args: bool,
+    capture_self: bool,
+    catch_panics: bool,
+    once_per_stack: bool,
+    skip_args: Vec<String>,
+    custom_serializers: Vec<(String, String)>,
+    crate_path: Option<syn::Path>,
+    debug_only: bool,
+}
+
+impl PropagateConfig {
+    /// Root path generated code uses to reach the runtime crate;
+    /// `crate_path = "my_facade"` swaps in a re-export wrapper
+    fn runtime_root(&self) -> proc_macro2::TokenStream {
+        match &self.crate_path {
+            Some(path) => quote! { ::#path },
+            None => quote! { ::trace_runtime },
+        }
+    }
+
+    /// Root path for the shared helper crate's macros and types; reached
+    /// through the runtime's re-export when `crate_path` is set
+    fn common_root(&self) -> proc_macro2::TokenStream {
+        match &self.crate_path {
+            Some(path) => quote! { ::#path::trace_common },
+            None => quote! { ::trace_common },
+        }
+    }
 }
 
 impl Default for PropagateConfig {
@@ -25,168 +58,252 @@ impl Default for PropagateConfig {
             ],
             user_code_only: true,
             max_depth: None,
+            min_duration_ms: None,
+            level: None,
+            sample_rate: None,
+            no_inputs: false,
+            no_output: false,
+            tracing_compat: false,
+            capture_child_args: false,
+            capture_self: false,
+            catch_panics: false,
+            once_per_stack: false,
+            skip_args: Vec::new(),
+            custom_serializers: Vec::new(),
+            crate_path: None,
+            debug_only: false,
         }
     }
 }
 
-fn parse_attributes(attr: TokenStream) -> PropagateConfig {
-    let attr_str = attr.to_string();
+/// Parse `#[rustforger_trace(...)]` attribute arguments
+///
+/// Accepted grammar, in any order:
+/// - `propagate` (or `propagate = <bool>`)
+/// - `max_depth = N`
+/// - `min_duration_ms = N`
+/// - `level = "trace" | "debug" | "info" | "warn" | "error"`
+/// - `sample = R` with `0 < R <= 1`
+/// - `no_inputs` / `no_output`
+/// - `tracing_compat`
+/// - `capture_args`
+/// - `capture_self`
+/// - `catch_panics`
+/// - `once_per_stack`
+/// - `exclude("pat", ...)` or `exclude = ["pat", ...]`
+/// - `skip(arg, ...)`
+/// - `serialize(arg = "path::to::fn", ...)`
+/// - `crate_path = "my_facade"` (re-export root for generated paths)
+/// - `debug_only` (instrument only under `debug_assertions`)
+///
+/// Anything else is rejected with a compile error pointing at the
+/// offending token, rather than being silently ignored.
+fn parse_attributes(attr: proc_macro2::TokenStream) -> syn::Result<PropagateConfig> {
     let mut config = PropagateConfig::default();
-    
-    if attr_str.contains("propagate") {
-        config.enabled = true;
+    if attr.is_empty() {
+        return Ok(config);
     }
-    
-    if let Some(depth_match) = attr_str.find("max_depth") {
-        if let Some(eq_pos) = attr_str[depth_match..].find('=') {
-            let start = depth_match + eq_pos + 1;
-            if let Some(value_str) = attr_str[start..].split(',').next() {
-                if let Ok(depth) = value_str.trim().parse::<usize>() {
-                    config.max_depth = Some(depth);
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("propagate") {
+            // Bare `propagate` and the `propagate = true` spelling emitted
+            // by the CLI are both accepted
+            if meta.input.peek(syn::Token![=]) {
+                let value: syn::LitBool = meta.value()?.parse()?;
+                config.enabled = value.value();
+            } else {
+                config.enabled = true;
+            }
+            Ok(())
+        } else if meta.path.is_ident("max_depth") {
+            let depth: syn::LitInt = meta.value()?.parse()?;
+            config.max_depth = Some(depth.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("min_duration_ms") {
+            let millis: syn::LitInt = meta.value()?.parse()?;
+            config.min_duration_ms = Some(millis.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("level") {
+            let level: syn::LitStr = meta.value()?.parse()?;
+            match level.value().as_str() {
+                "trace" | "debug" | "info" | "warn" | "error" => {
+                    config.level = Some(level.value());
+                    Ok(())
                 }
+                _ => Err(syn::Error::new(
+                    level.span(),
+                    "expected one of \"trace\", \"debug\", \"info\", \"warn\", \"error\"",
+                )),
             }
+        } else if meta.path.is_ident("sample") {
+            let rate: syn::LitFloat = meta.value()?.parse()?;
+            let value: f64 = rate.base10_parse()?;
+            if !(value > 0.0 && value <= 1.0) {
+                return Err(syn::Error::new(
+                    rate.span(),
+                    "sample rate must be in (0, 1]",
+                ));
+            }
+            config.sample_rate = Some(value);
+            Ok(())
+        } else if meta.path.is_ident("no_inputs") {
+            config.no_inputs = true;
+            Ok(())
+        } else if meta.path.is_ident("no_output") {
+            config.no_output = true;
+            Ok(())
+        } else if meta.path.is_ident("tracing_compat") {
+            config.tracing_compat = true;
+            Ok(())
+        } else if meta.path.is_ident("capture_args") {
+            config.capture_child_args = true;
+            Ok(())
+        } else if meta.path.is_ident("capture_self") {
+            config.capture_self = true;
+            Ok(())
+        } else if meta.path.is_ident("catch_panics") {
+            config.catch_panics = true;
+            Ok(())
+        } else if meta.path.is_ident("once_per_stack") {
+            config.once_per_stack = true;
+            Ok(())
+        } else if meta.path.is_ident("exclude") {
+            for pattern in parse_exclude_patterns(&meta)? {
+                config.exclude_patterns.push(pattern.value());
+            }
+            Ok(())
+        } else if meta.path.is_ident("skip") {
+            meta.parse_nested_meta(|skipped| {
+                config
+                    .skip_args
+                    .push(skipped.path.require_ident()?.to_string());
+                Ok(())
+            })
+        } else if meta.path.is_ident("serialize") {
+            meta.parse_nested_meta(|serializer| {
+                let arg = serializer.path.require_ident()?.to_string();
+                let path: syn::LitStr = serializer.value()?.parse()?;
+                // Reject malformed paths here instead of silently falling
+                // back to the default serializer at the use site
+                syn::parse_str::<syn::Path>(&path.value()).map_err(|_| {
+                    syn::Error::new(path.span(), "expected a function path string")
+                })?;
+                config.custom_serializers.push((arg, path.value()));
+                Ok(())
+            })
+        } else if meta.path.is_ident("debug_only") {
+            config.debug_only = true;
+            Ok(())
+        } else if meta.path.is_ident("crate_path") {
+            let path: syn::LitStr = meta.value()?.parse()?;
+            config.crate_path = Some(syn::parse_str(&path.value()).map_err(|_| {
+                syn::Error::new(path.span(), "expected a crate or module path string")
+            })?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported #[rustforger_trace] option"))
         }
-    }
-    
-    if attr_str.contains("exclude") {
-        if attr_str.contains("std::") {
-            config.exclude_patterns.push("std::".to_string());
-        }
-    }
-    
-    config
-}
-
-fn might_be_serializable(ty: &Type) -> bool {
-    let type_str = quote!(#ty).to_string();
-    
-    const PRIMITIVES: &[&str] = &[
-        "i8", "i16", "i32", "i64", "i128", "isize",
-        "u8", "u16", "u32", "u64", "u128", "usize", 
-        "f32", "f64", "bool", "char", "String"
-    ];
-    
-    // Check for exact primitive matches
-    if PRIMITIVES.contains(&type_str.as_str()) {
-        return true;
-    }
-    
-    // String references
-    if matches!(type_str.as_str(), "&str" | "& str" | "&String" | "& String") {
-        return true;
-    }
-    
-    // Simple references to primitives
-    if let Some(inner) = type_str.strip_prefix('&').map(str::trim) {
-        if PRIMITIVES.contains(&inner) {
-            return true;
-        }
-    }
-    
-    // Arrays and slices of primitives
-    if is_array_of_primitives(&type_str) || is_vec_of_primitives(&type_str) {
-        return true;
-    }
-    
-    // Option of primitives
-    if let Some(inner) = extract_generic_inner(&type_str, "Option") {
-        if PRIMITIVES.contains(&inner.trim()) {
-            return true;
-        }
-    }
-    
-    // Conservative check for simple test types 
-    is_known_serializable_test_type(&type_str)
-}
-
-/// Checks if type string represents an array of primitives
-fn is_array_of_primitives(type_str: &str) -> bool {
-    if let Some(inner) = type_str.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
-        if let Some(element_type) = inner.split(';').next() {
-            return matches!(element_type.trim(), 
-                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
-                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
-                "f32" | "f64" | "bool" | "char"
-            );
-        }
-    }
-    false
-}
-
-/// Checks if type string represents a Vec of primitives
-fn is_vec_of_primitives(type_str: &str) -> bool {
-    if let Some(inner) = extract_generic_inner(type_str, "Vec") {
-        return matches!(inner.trim(), 
-            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
-            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
-            "f32" | "f64" | "bool" | "char" | "String"
-        );
-    }
-    false
-}
-
-/// Extracts the inner type from a generic type like "Vec<T>" -> "T"
-fn extract_generic_inner<'a>(type_str: &'a str, wrapper: &str) -> Option<&'a str> {
-    let prefix = format!("{} <", wrapper);
-    if type_str.starts_with(&prefix) && type_str.ends_with('>') {
-        let start = prefix.len();
-        let end = type_str.len() - 1;
-        return Some(&type_str[start..end]);
-    }
-    None
+    });
+    syn::parse::Parser::parse2(parser, attr)?;
+    Ok(config)
 }
 
-fn is_known_serializable_test_type(type_str: &str) -> bool {
-    if type_str.contains("::") || type_str.contains('<') || type_str.contains('&') {
-        return false;
+/// Accept both `exclude("a", "b")` and `exclude = ["a", "b"]` spellings
+fn parse_exclude_patterns(
+    meta: &syn::meta::ParseNestedMeta,
+) -> syn::Result<Vec<syn::LitStr>> {
+    if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        let patterns =
+            syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(&content)?;
+        return Ok(patterns.into_iter().collect());
     }
-    matches!(type_str, 
-        "Person" | "TestData" | "MySerializableType" |
-        "SerializableStruct" | "SimpleStruct"
-    ) || (type_str.starts_with("Test") && type_str.contains("Serializable"))
-      || (type_str.starts_with("My") && type_str.contains("Serializable"))
+    let list: syn::ExprArray = meta.value()?.parse()?;
+    list.elems
+        .iter()
+        .map(|elem| match elem {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Ok(lit.clone()),
+            other => Err(syn::Error::new_spanned(other, "expected a string literal pattern")),
+        })
+        .collect()
 }
 
-#[allow(dead_code)]
-fn get_return_serialization_method(return_type: &syn::ReturnType) -> proc_macro2::TokenStream {
-    match return_type {
-        syn::ReturnType::Default => {
-            // Unit type () - use placeholder
-            quote! { safe_serialize_any }
-        }
-        syn::ReturnType::Type(_, ty) => {
-            if might_be_serializable(ty) {
-                quote! { serialize_if_serializable }
-            } else {
-                quote! { safe_serialize_any }
-            }
+/// Reference receiver of a method, if any: `Some(true)` for `&mut self`,
+/// `Some(false)` for `&self`; by-value receivers are not snapshotted
+fn reference_receiver(sig: &syn::Signature) -> Option<bool> {
+    match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {
+            Some(receiver.mutability.is_some())
         }
+        _ => None,
     }
 }
 
-fn generate_parameter_records(sig: &syn::Signature) -> Vec<proc_macro2::TokenStream> {
+fn generate_parameter_records(
+    sig: &syn::Signature,
+    config: &PropagateConfig,
+) -> Vec<proc_macro2::TokenStream> {
+    let common_root = config.common_root();
     let mut records = Vec::new();
+
+    // Under `capture_self` the receiver's state at entry is recorded as a
+    // regular `self` input alongside the other arguments
+    if config.capture_self && reference_receiver(sig).is_some() {
+        records.push(quote! {
+            "self" => #common_root::serialize_any!(&*self)
+        });
+    }
     
     for arg in &sig.inputs {
         if let FnArg::Typed(pat_type) = arg {
             if let Pat::Ident(pat_ident) = &*pat_type.pat {
                 let name = &pat_ident.ident;
                 let name_str = name.to_string();
-                let ty = &pat_type.ty;
-                
-                if might_be_serializable(ty) {
-                    records.push(quote! { 
-                        #name_str => ::trace_common::serialize_if_serializable!(&#name)
+
+                // A user-supplied serializer beats both the skip list and
+                // the serialize-or-placeholder specialization
+                let custom_path = config
+                    .custom_serializers
+                    .iter()
+                    .find(|(arg_name, _)| arg_name == &name_str)
+                    .and_then(|(_, path)| syn::parse_str::<syn::Path>(path).ok());
+
+                if let Some(path) = custom_path {
+                    records.push(quote! {
+                        #name_str => #path(&#name)
+                    });
+                } else if config.skip_args.iter().any(|skipped| skipped == &name_str) {
+                    // Explicitly excluded (secrets, giant buffers, handles)
+                    records.push(quote! {
+                        #name_str => ::serde_json::Value::String("<skipped>".to_string())
                     });
                 } else {
-                    records.push(quote! { 
-                        #name_str => ::trace_common::placeholder_for!(&#name)
+                    records.push(quote! {
+                        #name_str => #common_root::serialize_any!(&#name)
                     });
                 }
             }
         }
     }
     
+    // Concrete instantiations of the function's type parameters, recorded
+    // under a reserved `__generics` key so traces of generic code can be
+    // attributed to the instantiation that actually ran
+    let type_params: Vec<&proc_macro2::Ident> =
+        sig.generics.type_params().map(|param| &param.ident).collect();
+    if !type_params.is_empty() {
+        let names: Vec<String> = type_params.iter().map(|ident| ident.to_string()).collect();
+        records.push(quote! {
+            "__generics" => ::serde_json::json!({
+                #(#names: ::std::any::type_name::<#type_params>()),*
+            })
+        });
+    }
+
     records
 }
 
@@ -236,12 +353,21 @@ fn instrument_stmt_with_tracing(stmt: &Stmt, config: &PropagateConfig) -> proc_m
 fn instrument_expr_with_tracing(expr: &Expr, config: &PropagateConfig) -> proc_macro2::TokenStream {
     match expr {
         Expr::Call(call) => {
-            if should_instrument_call(call, config) {
+            if is_thread_spawn_call(call) {
+                instrument_spawn_call_with_tracing(call, config)
+            } else if should_instrument_call(call, config) {
                 instrument_function_call_with_tracing(call, config)
             } else {
                 quote! { #expr }
             }
         }
+        Expr::MethodCall(call) => {
+            if should_instrument_method_call(call, config) {
+                instrument_method_call_with_tracing(call, config)
+            } else {
+                quote! { #expr }
+            }
+        }
         Expr::Block(block_expr) => {
             let instrumented_block = instrument_block_with_tracing(&block_expr.block, config);
             quote! { #instrumented_block }
@@ -267,6 +393,52 @@ fn instrument_expr_with_tracing(expr: &Expr, config: &PropagateConfig) -> proc_m
                 }
             }
         }
+        Expr::ForLoop(for_loop) => {
+            let label = for_loop.label.iter();
+            let pat = &for_loop.pat;
+            let iterable = instrument_expr_with_tracing(&for_loop.expr, config);
+            let body = instrument_block_with_tracing(&for_loop.body, config);
+            quote! { #(#label)* for #pat in #iterable #body }
+        }
+        Expr::While(while_expr) => {
+            let label = while_expr.label.iter();
+            let cond = &while_expr.cond;
+            let body = instrument_block_with_tracing(&while_expr.body, config);
+            quote! { #(#label)* while #cond #body }
+        }
+        Expr::Loop(loop_expr) => {
+            let label = loop_expr.label.iter();
+            let body = instrument_block_with_tracing(&loop_expr.body, config);
+            quote! { #(#label)* loop #body }
+        }
+        Expr::Match(match_expr) => {
+            let scrutinee = instrument_expr_with_tracing(&match_expr.expr, config);
+            let arms = match_expr.arms.iter().map(|arm| {
+                let attrs = &arm.attrs;
+                let pat = &arm.pat;
+                let guard = match &arm.guard {
+                    Some((_, guard_expr)) => quote! { if #guard_expr },
+                    None => quote! {},
+                };
+                let body = instrument_expr_with_tracing(&arm.body, config);
+                quote! { #(#attrs)* #pat #guard => { #body }, }
+            });
+            quote! { match #scrutinee { #(#arms)* } }
+        }
+        Expr::Await(await_expr) => instrument_awaited_call_with_tracing(await_expr, config),
+        Expr::Closure(closure) => {
+            // Rebuild the closure with an instrumented body, keeping its
+            // capture mode, arguments and annotations intact
+            let body = instrument_expr_with_tracing(&closure.body, config);
+            match syn::parse2::<Expr>(body) {
+                Ok(instrumented_body) => {
+                    let mut closure = closure.clone();
+                    *closure.body = instrumented_body;
+                    quote! { #closure }
+                }
+                Err(_) => quote! { #expr },
+            }
+        }
         _ => quote! { #expr }
     }
 }
@@ -301,6 +473,217 @@ fn should_instrument_call(call: &ExprCall, config: &PropagateConfig) -> bool {
     false
 }
 
+fn should_instrument_method_call(call: &syn::ExprMethodCall, config: &PropagateConfig) -> bool {
+    if !config.enabled {
+        return false;
+    }
+    let name = call.method.to_string();
+    for pattern in &config.exclude_patterns {
+        if name.contains(pattern) {
+            return false;
+        }
+    }
+    // Ubiquitous std-ish methods would drown the trace in noise
+    if matches!(
+        name.as_str(),
+        "clone" | "to_string" | "to_owned" | "into" | "as_ref" | "as_mut"
+            | "unwrap" | "expect" | "iter" | "into_iter" | "collect"
+    ) {
+        return false;
+    }
+    !name.starts_with('_') && name.len() >= 3
+}
+
+/// Wrap a method call in a span named after the method; the receiver is
+/// instrumented recursively so chained calls each get their own span
+fn instrument_method_call_with_tracing(
+    call: &syn::ExprMethodCall,
+    config: &PropagateConfig,
+) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    let receiver = instrument_expr_with_tracing(&call.receiver, config);
+    let method = &call.method;
+    let turbofish = call.turbofish.iter();
+    let args = &call.args;
+    let method_name = method.to_string();
+    let guard_ident = hygienic_ident("__trace_guard");
+    if config.capture_child_args {
+        let arg_values = generate_child_arg_values(&call.args);
+        let span = depth_gated_span(
+            quote! {
+                #runtime_root::tracer::interface::span_dynamic_with_args_located(
+                    #method_name,
+                    module_path!(),
+                    file!(),
+                    line!(),
+                    column!(),
+                    ::serde_json::Value::Array(vec![#(#arg_values),*]),
+                )
+            },
+            config,
+        );
+        quote! {
+            {
+                let #guard_ident = #span;
+                #receiver.#method #(#turbofish)* (#args)
+            }
+        }
+    } else {
+        let span = depth_gated_span(
+            quote! { #runtime_root::tracer::interface::span_dynamic_located(#method_name, module_path!(), file!(), line!(), column!()) },
+            config,
+        );
+        quote! {
+            {
+                let #guard_ident = #span;
+                #receiver.#method #(#turbofish)* (#args)
+            }
+        }
+    }
+}
+
+/// Wrap a propagate span expression in a runtime depth check when the
+/// attribute carries `max_depth`; beyond the bound no span is opened and
+/// the call runs untraced
+fn depth_gated_span(
+    span_expr: proc_macro2::TokenStream,
+    config: &PropagateConfig,
+) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    match config.max_depth {
+        Some(limit) => quote! {
+            if #runtime_root::tracer::interface::current_depth() < #limit {
+                ::core::option::Option::Some(#span_expr)
+            } else {
+                ::core::option::Option::None
+            }
+        },
+        None => span_expr,
+    }
+}
+
+/// Wrap an awaited call so the span covers the await's completion, not
+/// just construction of the future; the guard lives inside the block and
+/// drops once the awaited value is ready
+fn instrument_awaited_call_with_tracing(
+    await_expr: &syn::ExprAwait,
+    config: &PropagateConfig,
+) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    let guard_ident = hygienic_ident("__trace_guard");
+    match &*await_expr.base {
+        Expr::Call(call) if should_instrument_call(call, config) => {
+            let Some(func_name) = extract_function_name_from_call(call) else {
+                return quote! { #await_expr };
+            };
+            let func = &call.func;
+            let args = &call.args;
+            let span = if config.capture_child_args {
+                let arg_values = generate_child_arg_values(&call.args);
+                depth_gated_span(
+                    quote! {
+                        #runtime_root::tracer::interface::span_dynamic_with_args_located(
+                            #func_name,
+                            module_path!(),
+                            file!(),
+                            line!(),
+                            column!(),
+                            ::serde_json::Value::Array(vec![#(#arg_values),*]),
+                        )
+                    },
+                    config,
+                )
+            } else {
+                depth_gated_span(
+                    quote! { #runtime_root::tracer::interface::span_dynamic_located(#func_name, module_path!(), file!(), line!(), column!()) },
+                    config,
+                )
+            };
+            quote! {
+                {
+                    let #guard_ident = #span;
+                    #func(#args).await
+                }
+            }
+        }
+        Expr::MethodCall(call) if should_instrument_method_call(call, config) => {
+            let receiver = instrument_expr_with_tracing(&call.receiver, config);
+            let method = &call.method;
+            let turbofish = call.turbofish.iter();
+            let args = &call.args;
+            let method_name = method.to_string();
+            let span = depth_gated_span(
+                quote! { #runtime_root::tracer::interface::span_dynamic_located(#method_name, module_path!(), file!(), line!(), column!()) },
+                config,
+            );
+            quote! {
+                {
+                    let #guard_ident = #span;
+                    #receiver.#method #(#turbofish)* (#args).await
+                }
+            }
+        }
+        _ => quote! { #await_expr },
+    }
+}
+
+/// True for the spawn entry points whose closure should carry the trace
+/// across the thread boundary (`std::thread::spawn`, `rayon::spawn`)
+fn is_thread_spawn_call(call: &ExprCall) -> bool {
+    let Expr::Path(path_expr) = &*call.func else {
+        return false;
+    };
+    let segments: Vec<String> = path_expr
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    segments.last().map(String::as_str) == Some("spawn")
+        && segments.iter().any(|s| s == "thread" || s == "rayon")
+}
+
+/// Rewrite a spawn call so the child thread's stack is seeded with a
+/// link back to the spawning call
+///
+/// The link is captured outside the closure and moved into it; the
+/// closure body is instrumented as usual, so propagation continues
+/// inside the spawned work.
+fn instrument_spawn_call_with_tracing(call: &ExprCall, config: &PropagateConfig) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    let Some(Expr::Closure(closure)) = call.args.first() else {
+        return quote! { #call };
+    };
+
+    let body = instrument_expr_with_tracing(&closure.body, config);
+    let link_ident = hygienic_ident("__trace_spawn_link");
+    let guard_ident = hygienic_ident("__trace_guard");
+    let seeded_body = match syn::parse2::<Expr>(quote! {
+        {
+            let #guard_ident = #runtime_root::tracer::interface::span_spawned(#link_ident);
+            #body
+        }
+    }) {
+        Ok(seeded_body) => seeded_body,
+        Err(_) => return quote! { #call },
+    };
+
+    let mut closure = closure.clone();
+    // The link has to move into the closure; spawn requires 'static
+    // captures anyway, so forcing `move` does not change what compiles
+    closure.capture = Some(Default::default());
+    *closure.body = seeded_body;
+
+    let mut call = call.clone();
+    call.args[0] = Expr::Closure(closure);
+    quote! {
+        {
+            let #link_ident = #runtime_root::tracer::interface::spawn_link(file!(), line!());
+            #call
+        }
+    }
+}
+
 fn extract_function_name_from_call(call: &ExprCall) -> Option<String> {
     match &*call.func {
         Expr::Path(path_expr) => {
@@ -311,18 +694,44 @@ fn extract_function_name_from_call(call: &ExprCall) -> Option<String> {
     }
 }
 
-fn instrument_function_call_with_tracing(call: &ExprCall, _config: &PropagateConfig) -> proc_macro2::TokenStream {
+fn instrument_function_call_with_tracing(call: &ExprCall, config: &PropagateConfig) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
     let func = &call.func;
     let args = &call.args;
     
     if let Some(func_name) = extract_function_name_from_call(call) {
-        quote! {
-            {
-                ::trace_runtime::tracer::interface::enter_dynamic(#func_name, file!(), line!());
-                let __result = #func(#args);
-                ::trace_runtime::tracer::interface::exit();
-                
-                __result
+        let guard_ident = hygienic_ident("__trace_guard");
+        if config.capture_child_args {
+            let arg_values = generate_child_arg_values(&call.args);
+            let span = depth_gated_span(
+                quote! {
+                    #runtime_root::tracer::interface::span_dynamic_with_args_located(
+                        #func_name,
+                        module_path!(),
+                        file!(),
+                        line!(),
+                        column!(),
+                        ::serde_json::Value::Array(vec![#(#arg_values),*]),
+                    )
+                },
+                config,
+            );
+            quote! {
+                {
+                    let #guard_ident = #span;
+                    #func(#args)
+                }
+            }
+        } else {
+            let span = depth_gated_span(
+                quote! { #runtime_root::tracer::interface::span_dynamic_located(#func_name, module_path!(), file!(), line!(), column!()) },
+                config,
+            );
+            quote! {
+                {
+                    let #guard_ident = #span;
+                    #func(#args)
+                }
             }
         }
     } else {
@@ -330,113 +739,652 @@ fn instrument_function_call_with_tracing(call: &ExprCall, _config: &PropagateCon
     }
 }
 
+/// Serialize child-call arguments for the opt-in capture mode
+///
+/// Literals are serialized directly (re-evaluating them is free and pure);
+/// anything else is recorded as its source text, since the macro cannot prove
+/// an arbitrary expression's type serializable. The runtime caps oversized
+/// strings on top of this.
+fn generate_child_arg_values(
+    args: &syn::punctuated::Punctuated<Expr, syn::Token![,]>,
+) -> Vec<proc_macro2::TokenStream> {
+    args.iter()
+        .map(|arg| match arg {
+            Expr::Lit(_) => quote! { ::serde_json::json!(#arg) },
+            _ => {
+                let text = quote!(#arg).to_string();
+                quote! { ::serde_json::Value::String(format!("<expr: {}>", #text)) }
+            }
+        })
+        .collect()
+}
+
+/// Create an identifier with mixed-site hygiene for macro-introduced locals
+///
+/// Mixed-site spans resolve at the macro definition, so expansion locals like
+/// `__result` neither collide with nor capture identifiers of the same name
+/// in the traced function body.
+fn hygienic_ident(name: &str) -> proc_macro2::Ident {
+    proc_macro2::Ident::new(name, proc_macro2::Span::mixed_site())
+}
+
 #[proc_macro_attribute]
 pub fn rustforger_trace(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let config = parse_attributes(attr);
-    
-    let input_fn = parse_macro_input!(item as ItemFn);
+    let config = match parse_attributes(attr.into()) {
+        Ok(config) => config,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let parsed = parse_macro_input!(item as syn::Item);
+    let output = match parsed {
+        syn::Item::Fn(input_fn) => generate_tracing_instrumentation(&input_fn, &config),
+        syn::Item::Impl(item_impl) => instrument_impl_block(item_impl, &config),
+        syn::Item::Trait(item_trait) => instrument_trait_block(item_trait, &config),
+        syn::Item::Mod(item_mod) => instrument_module(item_mod, &config),
+        other => syn::Error::new_spanned(
+            &other,
+            "#[rustforger_trace] supports functions, impl blocks, traits and modules",
+        )
+        .to_compile_error(),
+    };
 
-    let output = generate_tracing_instrumentation(&input_fn, &config);
-    
     output.into()
 }
 
+/// Whether an item already carries its own `#[rustforger_trace]`
+/// attribute, which will expand on its own terms later
+fn has_trace_attr(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "rustforger_trace")
+    })
+}
+
+/// Instrument every function inside an inline `mod`, recursing into impl
+/// blocks, trait definitions and nested modules
+///
+/// Complements the CLI's `--all` flow for purely macro-driven setups.
+/// Items carrying their own `#[rustforger_trace]` attribute are left to
+/// expand with their own configuration.
+fn instrument_module(
+    mut item_mod: syn::ItemMod,
+    config: &PropagateConfig,
+) -> proc_macro2::TokenStream {
+    let Some((_, items)) = &mut item_mod.content else {
+        return syn::Error::new_spanned(
+            &item_mod,
+            "#[rustforger_trace] on a module requires an inline body",
+        )
+        .to_compile_error();
+    };
+
+    for item in items.iter_mut() {
+        let rewritten = match &*item {
+            syn::Item::Fn(input_fn) if !has_trace_attr(&input_fn.attrs) => {
+                generate_tracing_instrumentation(input_fn, config)
+            }
+            syn::Item::Impl(item_impl) if !has_trace_attr(&item_impl.attrs) => {
+                instrument_impl_block(item_impl.clone(), config)
+            }
+            syn::Item::Trait(item_trait) if !has_trace_attr(&item_trait.attrs) => {
+                instrument_trait_block(item_trait.clone(), config)
+            }
+            syn::Item::Mod(nested) if !has_trace_attr(&nested.attrs) => {
+                instrument_module(nested.clone(), config)
+            }
+            _ => continue,
+        };
+        if let Ok(rewritten) = syn::parse2(rewritten) {
+            *item = rewritten;
+        }
+    }
+
+    quote! { #item_mod }
+}
+
+/// Instrument every method of an `impl` block, recording calls under
+/// `Type::method` names
+fn instrument_impl_block(
+    mut item_impl: syn::ItemImpl,
+    config: &PropagateConfig,
+) -> proc_macro2::TokenStream {
+    let type_name = impl_type_name(&item_impl.self_ty);
+    let mut release_copies = Vec::new();
+    for item in &mut item_impl.items {
+        if let syn::ImplItem::Fn(method) = item {
+            let qualified = format!("{}::{}", type_name, method.sig.ident);
+            let body = instrumented_body(&method.sig, &method.block, &literal_name(&qualified), config);
+            if let Ok(block) = syn::parse2(body) {
+                if config.debug_only {
+                    let mut release = method.clone();
+                    release.attrs.push(syn::parse_quote! { #[cfg(not(debug_assertions))] });
+                    release_copies.push(syn::ImplItem::Fn(release));
+                    method.attrs.push(syn::parse_quote! { #[cfg(debug_assertions)] });
+                }
+                method.block = block;
+            }
+        }
+    }
+    item_impl.items.extend(release_copies);
+    quote! { #item_impl }
+}
+
+/// Instrument the default bodies of a trait definition; required methods
+/// are left untouched
+///
+/// Calls are recorded under the concrete `Self` type at runtime (e.g.
+/// `Counter::describe`), so traces show which implementor actually ran.
+fn instrument_trait_block(
+    mut item_trait: syn::ItemTrait,
+    config: &PropagateConfig,
+) -> proc_macro2::TokenStream {
+    let trait_name = item_trait.ident.to_string();
+    let mut release_copies = Vec::new();
+    for item in &mut item_trait.items {
+        if let syn::TraitItem::Fn(method) = item {
+            let Some(default_body) = &method.default else {
+                continue;
+            };
+            let method_name = method.sig.ident.to_string();
+            let body = instrumented_body(
+                &method.sig,
+                default_body,
+                &concrete_self_name(&trait_name, &method_name),
+                config,
+            );
+            if let Ok(block) = syn::parse2(body) {
+                if config.debug_only {
+                    let mut release = method.clone();
+                    release.attrs.push(syn::parse_quote! { #[cfg(not(debug_assertions))] });
+                    release_copies.push(syn::TraitItem::Fn(release));
+                    method.attrs.push(syn::parse_quote! { #[cfg(debug_assertions)] });
+                }
+                method.default = Some(block);
+            }
+        }
+    }
+    item_trait.items.extend(release_copies);
+    quote! { #item_trait }
+}
+
+/// Name expression for a statically known call name; evaluates to a
+/// borrowed `Cow<'static, str>` with no per-call allocation
+fn literal_name(name: &str) -> proc_macro2::TokenStream {
+    quote! { ::std::borrow::Cow::<'static, str>::Borrowed(#name) }
+}
+
+/// Name expression resolving the concrete `Self` type at runtime, for
+/// trait default bodies where the implementor is unknown at expansion time
+fn concrete_self_name(trait_name: &str, method_name: &str) -> proc_macro2::TokenStream {
+    let fallback = format!("{}::{}", trait_name, method_name);
+    quote! {
+        {
+            let full_type = ::std::any::type_name::<Self>();
+            let base = full_type.split('<').next().unwrap_or(full_type);
+            match base.rsplit("::").next() {
+                ::core::option::Option::Some(short_type) => ::std::borrow::Cow::Owned(
+                    ::std::format!("{}::{}", short_type, #method_name),
+                ),
+                ::core::option::Option::None => {
+                    ::std::borrow::Cow::<'static, str>::Borrowed(#fallback)
+                }
+            }
+        }
+    }
+}
+
+/// Short type name an impl block's methods are recorded under
+fn impl_type_name(self_ty: &Type) -> String {
+    if let Type::Path(type_path) = self_ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident.to_string();
+        }
+    }
+    quote!(#self_ty).to_string().replace(' ', "")
+}
+
+/// Return type annotation for the inner body closure, or `None` when the
+/// type cannot be named there (`impl Trait`, possibly nested) or the
+/// function returns unit, where inference does the right thing anyway
+fn closure_return_annotation(output: &syn::ReturnType) -> Option<&Type> {
+    let syn::ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let type_str = quote!(#ty).to_string();
+    if type_str.contains("impl ") {
+        return None;
+    }
+    Some(ty)
+}
+
+/// If the return type looks like `Result<..>`, return its Ok/Err type
+/// arguments; either may be absent for aliases like `io::Result<T>`
+fn result_type_args(ty: &Type) -> Option<(Option<&Type>, Option<&Type>)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let mut args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => return Some((None, None)),
+    };
+    Some((args.next(), args.next()))
+}
+
+/// Runtime severity constant for the configured `level` (info by default)
+fn runtime_level(config: &PropagateConfig) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    let variant = match config.level.as_deref() {
+        Some("trace") => quote! { Trace },
+        Some("debug") => quote! { Debug },
+        Some("warn") => quote! { Warn },
+        Some("error") => quote! { Error },
+        _ => quote! { Info },
+    };
+    quote! { #runtime_root::tracer::interface::TraceLevel::#variant }
+}
+
+/// tracing level constant matching the configured `level`, for the span
+/// emitted under `tracing_compat`
+fn tracing_level(config: &PropagateConfig) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    let variant = match config.level.as_deref() {
+        Some("trace") => quote! { TRACE },
+        Some("debug") => quote! { DEBUG },
+        Some("warn") => quote! { WARN },
+        Some("error") => quote! { ERROR },
+        _ => quote! { INFO },
+    };
+    quote! { #runtime_root::tracing::Level::#variant }
+}
+
+/// Serializer for one bound value; autoref specialization picks real
+/// serialization or a placeholder per type at the expansion site
+fn value_serializer(binding: &proc_macro2::Ident, config: &PropagateConfig) -> proc_macro2::TokenStream {
+    let common_root = config.common_root();
+    quote! { #common_root::serialize_any!(#binding) }
+}
+
+/// `fn main` is wrapped rather than traced: the wrapper installs
+/// auto-save up front and finalizes the tracer before handing back the
+/// exit value, so output reliably reaches disk. Propagate mode still
+/// instruments the calls inside the body.
+fn instrument_main(input_fn: &ItemFn, config: &PropagateConfig) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    let vis = &input_fn.vis;
+    let sig = &input_fn.sig;
+    let attrs = &input_fn.attrs;
+
+    let propagated;
+    let block = if config.enabled {
+        propagated = syn::parse2(instrument_block_with_tracing(&input_fn.block, config))
+            .unwrap_or_else(|_| (*input_fn.block).clone());
+        &propagated
+    } else {
+        &*input_fn.block
+    };
+
+    let result_ident = hygienic_ident("__result");
+    let eval_stmt = if sig.asyncness.is_some() {
+        quote! { let #result_ident = async move #block.await; }
+    } else {
+        let closure = match closure_return_annotation(&sig.output) {
+            Some(ret_ty) => quote! { move || -> #ret_ty #block },
+            None => quote! { move || #block },
+        };
+        quote! { let #result_ident = (#closure)(); }
+    };
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #runtime_root::tracer::interface::ensure_auto_save_initialized();
+            #eval_stmt
+            let _ = #runtime_root::tracer::interface::finalize_default();
+            #result_ident
+        }
+    }
+}
+
 fn generate_tracing_instrumentation(
     input_fn: &ItemFn,
-    _config: &PropagateConfig,
+    config: &PropagateConfig,
 ) -> proc_macro2::TokenStream {
+    if input_fn.sig.ident == "main" && input_fn.sig.inputs.is_empty() {
+        return instrument_main(input_fn, config);
+    }
+
     let vis = &input_fn.vis;
     let sig = &input_fn.sig;
-    let block = &input_fn.block;
     let attrs = &input_fn.attrs;
-    let fn_name = &sig.ident;
-    let fn_name_str = fn_name.to_string();
-    let is_async = sig.asyncness.is_some();
-    
-    let param_records = generate_parameter_records(sig);
-    
-    let serialize_args = if param_records.is_empty() {
-        quote! {
-            let __trace_inputs = ::serde_json::Value::Object(::serde_json::Map::new());
-        }
+    let fn_name_str = sig.ident.to_string();
+
+    let body = instrumented_body(sig, &input_fn.block, &literal_name(&fn_name_str), config);
+
+    // Under `debug_only` the instrumented copy exists solely in builds with
+    // debug assertions; release builds get the function exactly as written
+    if config.debug_only {
+        let original = &input_fn.block;
+        return quote! {
+            #[cfg(debug_assertions)]
+            #(#attrs)*
+            #vis #sig #body
+
+            #[cfg(not(debug_assertions))]
+            #(#attrs)*
+            #vis #sig #original
+        };
+    }
+
+    quote! {
+        #(#attrs)*
+        #vis #sig #body
+    }
+}
+
+/// Build the instrumented body block for one function or method
+///
+/// `fn_name_expr` evaluates to the `Cow<str>` name calls are recorded
+/// under; impl and trait expansion pass qualified `Type::method` names.
+fn instrumented_body(
+    sig: &syn::Signature,
+    block: &Block,
+    fn_name_expr: &proc_macro2::TokenStream,
+    config: &PropagateConfig,
+) -> proc_macro2::TokenStream {
+    let runtime_root = config.runtime_root();
+    let common_root = config.common_root();
+    // Propagate mode rewrites eligible calls inside the body first, so the
+    // wrappers below evaluate the instrumented statements
+    let propagated;
+    let block = if config.enabled {
+        propagated = syn::parse2(instrument_block_with_tracing(block, config))
+            .unwrap_or_else(|_| block.clone());
+        &propagated
     } else {
-        quote! {
-            let __trace_inputs = ::trace_common::args_json!(#(#param_records),*);
-        }
+        block
+    };
+
+    let param_records = generate_parameter_records(sig, config);
+
+    // Mixed-site hygiene keeps these from colliding with user locals of the
+    // same name inside #block
+    let name_ident = hygienic_ident("__trace_name");
+    let guard_ident = hygienic_ident("__trace_guard");
+    let start_ident = hygienic_ident("__trace_start");
+    let inputs_ident = hygienic_ident("__trace_inputs");
+    let result_ident = hygienic_ident("__result");
+    let output_ident = hygienic_ident("__trace_output");
+
+    // Either side can be suppressed wholesale for functions whose
+    // arguments or results are too large to ever serialize
+    let serialize_args = if config.no_inputs {
+        quote! { ::serde_json::Value::String("<omitted>".to_string()) }
+    } else if param_records.is_empty() {
+        quote! { ::serde_json::Value::Object(::serde_json::Map::new()) }
+    } else {
+        quote! { #common_root::args_json!(#(#param_records),*) }
     };
 
     let auto_init_code = quote! {
-        ::trace_runtime::tracer::interface::ensure_auto_save_initialized();
+        #runtime_root::tracer::interface::ensure_auto_save_initialized();
     };
-    match &sig.output {
-        syn::ReturnType::Default => {
-            if is_async {
-                quote! {
-                    #(#attrs)*
-                    #vis #sig {
-                        #auto_init_code
-                        ::trace_runtime::tracer::interface::enter(#fn_name_str, file!(), line!());
-                        #serialize_args
-                        let __result = #block;
-                        let __trace_output = ::serde_json::Value::Null;
-                        ::trace_runtime::tracer::interface::record_top_level_call(__trace_inputs, __trace_output);
-                        ::trace_runtime::tracer::interface::exit();
-                        __result
-                    }
-                }
-            } else {
-                quote! {
-                    #(#attrs)*
-                    #vis #sig {
-                        #auto_init_code
-                        ::trace_runtime::tracer::interface::enter(#fn_name_str, file!(), line!());
-                        #serialize_args
-                        let __result = #block;
-                        let __trace_output = ::serde_json::Value::Null;
-                        ::trace_runtime::tracer::interface::record_top_level_call(__trace_inputs, __trace_output);
-                        ::trace_runtime::tracer::interface::exit();
-                        __result
+    // Result returns get their Ok/Err arms serialized into distinct
+    // `output.ok` / `output.err` fields, with failures flagged so error
+    // paths stand out in traces
+    let serialize_method = if config.no_output {
+        quote! { ::serde_json::Value::String("<omitted>".to_string()) }
+    } else {
+        match &sig.output {
+            syn::ReturnType::Default => quote! { ::serde_json::Value::Null },
+            syn::ReturnType::Type(_, ty) => {
+                if result_type_args(ty).is_some() {
+                    let ok_ident = hygienic_ident("__trace_ok");
+                    let err_ident = hygienic_ident("__trace_err");
+                    let ok_value = value_serializer(&ok_ident, config);
+                    // Errors get the richer treatment: type name and
+                    // Display output survive even when `E: !Serialize`
+                    let err_value = quote! { #common_root::describe_error!(#err_ident) };
+                    quote! {
+                        match &#result_ident {
+                            ::core::result::Result::Ok(#ok_ident) => {
+                                ::serde_json::json!({ "ok": #ok_value })
+                            }
+                            ::core::result::Result::Err(#err_ident) => {
+                                ::serde_json::json!({ "err": #err_value, "failed": true })
+                            }
+                        }
                     }
+                } else {
+                    quote! { #common_root::serialize_any!(&#result_ident) }
                 }
             }
         }
-        syn::ReturnType::Type(_, ty) => {
-            let serialize_method = if might_be_serializable(ty) {
-                quote! { ::trace_common::serialize_if_serializable!(&__result) }
-            } else {
-                quote! { ::trace_common::placeholder_for!(&__result) }
+    };
+
+    // A mutable receiver is snapshotted again after the body ran, so the
+    // record shows the state transition and not just the return value
+    let serialize_method = if config.capture_self && reference_receiver(sig) == Some(true) {
+        quote! {
+            ::serde_json::json!({
+                "result": #serialize_method,
+                "self_after": #common_root::serialize_any!(&*self),
+            })
+        }
+    } else {
+        serialize_method
+    };
+
+    // The original body is wrapped in an inner scope that is evaluated in
+    // place, so `return` and `?` exit that scope instead of the outer
+    // function and cannot skip past the recording epilogue. Async bodies
+    // become an awaited `async move` block (the span opens when the future
+    // first runs, the record is written on completion); sync bodies become
+    // an immediately invoked closure, annotated with the declared return
+    // type where possible so `?` desugaring still infers the error type.
+    // `capture_self` needs the receiver back after the inner scope ran, so
+    // the scope borrows its captures instead of consuming them
+    let move_kw = if config.capture_self {
+        quote! {}
+    } else {
+        quote! { move }
+    };
+    let closure = match closure_return_annotation(&sig.output) {
+        Some(ret_ty) => quote! { #move_kw || -> #ret_ty #block },
+        None => quote! { #move_kw || #block },
+    };
+    let eval_stmt = if sig.asyncness.is_some() {
+        if config.catch_panics {
+            return quote! {
+                { compile_error!("catch_panics is not supported on async functions"); }
             };
-            
-            if is_async {
-                quote! {
-                    #(#attrs)*
-                    #vis #sig {
-                        #auto_init_code
-                        ::trace_runtime::tracer::interface::enter(#fn_name_str, file!(), line!());
-                        #serialize_args
-                        let __result = #block;
-                        let __trace_output = #serialize_method;
-                        ::trace_runtime::tracer::interface::record_top_level_call(__trace_inputs, __trace_output);
-                        ::trace_runtime::tracer::interface::exit();
-                        __result
+        }
+        if config.tracing_compat {
+            // Futures are instrumented rather than entered, so the span
+            // does not leak onto other tasks scheduled across awaits
+            let span_ident = hygienic_ident("__trace_tracing_span");
+            quote! {
+                let #result_ident = #runtime_root::tracing::Instrument::instrument(
+                    async #move_kw #block,
+                    #span_ident.clone(),
+                )
+                .await;
+            }
+        } else {
+            quote! { let #result_ident = async #move_kw #block.await; }
+        }
+    } else if config.catch_panics {
+        // A caught panic records the payload as a failed output and
+        // re-raises; the explicit guard drop keeps the exit ordered before
+        // the unwind resumes. Both arms diverge or fall through cleanly, so
+        // the moves out of the inputs/guard bindings stay conditional.
+        let payload_ident = hygienic_ident("__trace_panic");
+        quote! {
+            let #result_ident = match ::std::panic::catch_unwind(
+                ::std::panic::AssertUnwindSafe(#closure),
+            ) {
+                ::core::result::Result::Ok(value) => value,
+                ::core::result::Result::Err(#payload_ident) => {
+                    if let ::core::option::Option::Some(#inputs_ident) = #inputs_ident {
+                        let message = #payload_ident
+                            .downcast_ref::<&str>()
+                            .map(|s| s.to_string())
+                            .or_else(|| {
+                                #payload_ident
+                                    .downcast_ref::<::std::string::String>()
+                                    .cloned()
+                            })
+                            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+                        #runtime_root::tracer::interface::record_top_level_call_with_duration(
+                            #inputs_ident,
+                            ::serde_json::json!({ "panic": message, "failed": true }),
+                            #start_ident.elapsed(),
+                        );
                     }
+                    drop(#guard_ident);
+                    ::std::panic::resume_unwind(#payload_ident);
                 }
+            };
+        }
+    } else {
+        quote! { let #result_ident = (#closure)(); }
+    };
+
+    let elapsed_ident = hygienic_ident("__trace_elapsed");
+    // Under `tracing_compat` the same data additionally flows through a
+    // real tracing span, so existing subscriber pipelines see it too
+    let tracing_span_ident = hygienic_ident("__trace_tracing_span");
+    let tracing_entered_ident = hygienic_ident("__trace_tracing_entered");
+    // Sync bodies enter the span for their whole extent; async bodies are
+    // instrumented instead (see the eval statement above)
+    let enter_stmt = if sig.asyncness.is_some() {
+        quote! {}
+    } else {
+        quote! { let #tracing_entered_ident = #tracing_span_ident.enter(); }
+    };
+    let span_level = tracing_level(config);
+    let tracing_setup = if config.tracing_compat {
+        quote! {
+            let #tracing_span_ident = #runtime_root::tracing::span!(
+                target: "rustforger_trace",
+                #span_level,
+                "traced_call",
+                function = %#name_ident,
+                file = file!(),
+                line = line!(),
+                inputs = #runtime_root::tracing::field::Empty,
+                output = #runtime_root::tracing::field::Empty,
+            );
+            if let ::core::option::Option::Some(inputs) = &#inputs_ident {
+                #tracing_span_ident.record("inputs", #runtime_root::tracing::field::display(inputs));
+            }
+            #enter_stmt
+        }
+    } else {
+        quote! {}
+    };
+    let tracing_output_record = if config.tracing_compat {
+        quote! {
+            #tracing_span_ident.record("output", #runtime_root::tracing::field::display(&#output_ident));
+        }
+    } else {
+        quote! {}
+    };
+    let record_call = quote! {
+        if let ::core::option::Option::Some(#inputs_ident) = #inputs_ident {
+            let #output_ident = #serialize_method;
+            #tracing_output_record
+            #runtime_root::tracer::interface::record_top_level_call_with_duration(
+                #inputs_ident,
+                #output_ident,
+                #elapsed_ident,
+            );
+        }
+    };
+    // Calls finishing under the `min_duration_ms` threshold are measured
+    // but not recorded, keeping hot loops out of the output
+    let record_call = match config.min_duration_ms {
+        Some(millis) => quote! {
+            if #elapsed_ident >= ::std::time::Duration::from_millis(#millis) {
+                #record_call
+            }
+        },
+        None => record_call,
+    };
+
+    // Argument and output serialization are skipped entirely when the span
+    // is inactive (function disabled at runtime), keeping the disabled path
+    // close to free
+    // Sampled functions keep a per-expansion counter and only open every
+    // N-th span; sampled-out calls get an inactive guard, so no argument or
+    // output serialization happens for them either
+    let guard_level = runtime_level(config);
+    let span_expr = quote! {
+        if #runtime_root::tracer::interface::level_enabled(#guard_level) {
+            #runtime_root::tracer::interface::span_dynamic_located(&#name_ident, module_path!(), file!(), line!(), column!())
+        } else {
+            #runtime_root::tracer::interface::TraceGuard::inactive()
+        }
+    };
+    // Recursive functions can opt to record only their outermost frame;
+    // nested invocations find their own name already on the stack and get
+    // an inactive guard
+    let span_expr = if config.once_per_stack {
+        quote! {
+            if #runtime_root::tracer::interface::on_current_stack(&#name_ident) {
+                #runtime_root::tracer::interface::TraceGuard::inactive()
             } else {
-                quote! {
-                    #(#attrs)*
-                    #vis #sig {
-                        #auto_init_code
-                        ::trace_runtime::tracer::interface::enter(#fn_name_str, file!(), line!());
-                        #serialize_args
-                        let __result = #block;
-                        let __trace_output = #serialize_method;
-                        ::trace_runtime::tracer::interface::record_top_level_call(__trace_inputs, __trace_output);
-                        ::trace_runtime::tracer::interface::exit();
-                        __result
+                #span_expr
+            }
+        }
+    } else {
+        span_expr
+    };
+    let guard_init = match config.sample_rate {
+        Some(rate) => {
+            let period = (1.0 / rate).round().max(1.0) as u64;
+            let counter_ident = hygienic_ident("__TRACE_SAMPLE_COUNTER");
+            quote! {
+                {
+                    static #counter_ident: ::std::sync::atomic::AtomicU64 =
+                        ::std::sync::atomic::AtomicU64::new(0);
+                    if #counter_ident.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed) % #period == 0 {
+                        #span_expr
+                    } else {
+                        #runtime_root::tracer::interface::TraceGuard::inactive()
                     }
                 }
             }
         }
+        None => span_expr,
+    };
+
+    quote! {
+        {
+            #auto_init_code
+            let #name_ident = #fn_name_expr;
+            let #guard_ident = #guard_init;
+            let #inputs_ident = if #guard_ident.is_active() {
+                ::core::option::Option::Some(#serialize_args)
+            } else {
+                ::core::option::Option::None
+            };
+            #tracing_setup
+            let #start_ident = ::std::time::Instant::now();
+            #eval_stmt
+            let #elapsed_ident = #start_ident.elapsed();
+            #record_call
+            drop(#guard_ident);
+            #result_ident
+        }
     }
 }
 
@@ -446,30 +1394,8 @@ mod tests {
     use syn::parse_quote;
     
     fn parse_attributes_from_str(attr_str: &str) -> PropagateConfig {
-        let mut config = PropagateConfig::default();
-        
-        if attr_str.contains("propagate") {
-            config.enabled = true;
-        }
-    
-        if let Some(depth_match) = attr_str.find("max_depth") {
-            if let Some(eq_pos) = attr_str[depth_match..].find('=') {
-                let start = depth_match + eq_pos + 1;
-                if let Some(value_str) = attr_str[start..].split(',').next() {
-                    if let Ok(depth) = value_str.trim().parse::<usize>() {
-                        config.max_depth = Some(depth);
-                    }
-                }
-            }
-        }
-        
-        if attr_str.contains("exclude") {
-            if attr_str.contains("std::") {
-                config.exclude_patterns.push("std::".to_string());
-            }
-        }
-        
-        config
+        let tokens: proc_macro2::TokenStream = attr_str.parse().expect("attribute tokens");
+        parse_attributes(tokens).expect("attribute grammar")
     }
     
     #[test]
@@ -493,30 +1419,77 @@ mod tests {
     }
     
     #[test]
-    fn test_might_be_serializable_primitives() {
-        let ty: Type = parse_quote! { i32 };
-        assert!(might_be_serializable(&ty));
-        
-        let ty: Type = parse_quote! { String };
-        assert!(might_be_serializable(&ty));
-        
-        let ty: Type = parse_quote! { &str };
-        assert!(might_be_serializable(&ty));
+    fn test_parse_skip_attribute() {
+        let config = parse_attributes_from_str("skip(password, buffer)");
+        assert_eq!(config.skip_args, vec!["password", "buffer"]);
+
+        let config = parse_attributes_from_str("propagate, skip(token)");
+        assert!(config.enabled);
+        assert_eq!(config.skip_args, vec!["token"]);
     }
-    
+
     #[test]
-    fn test_might_be_serializable_complex() {
-        let ty: Type = parse_quote! { std::collections::HashMap<String, i32> };
-        assert!(!might_be_serializable(&ty));
+    fn test_parse_serializer_overrides() {
+        let config = parse_attributes_from_str(r#"serialize(conn = "my_mod :: summarize_conn")"#);
+        assert_eq!(
+            config.custom_serializers,
+            vec![("conn".to_string(), "my_mod :: summarize_conn".to_string())]
+        );
+        assert!(syn::parse_str::<syn::Path>(&config.custom_serializers[0].1).is_ok());
     }
-    
+
+    #[test]
+    fn test_parse_level_attribute() {
+        let config = parse_attributes_from_str(r#"level = "debug""#);
+        assert_eq!(config.level.as_deref(), Some("debug"));
+
+        let config = parse_attributes_from_str("");
+        assert_eq!(config.level, None);
+    }
+
+    #[test]
+    fn test_parse_crate_path_attribute() {
+        let config = parse_attributes_from_str(r#"crate_path = "my_facade""#);
+        let path = config.crate_path.expect("crate_path should parse");
+        assert_eq!(quote!(#path).to_string(), "my_facade");
+
+        let config = parse_attributes_from_str("");
+        assert!(config.crate_path.is_none());
+    }
+
+    #[test]
+    fn test_parse_exclude_attribute() {
+        let config = parse_attributes_from_str(r#"propagate, exclude("std::fs", "my_crate::helper")"#);
+        assert!(config.exclude_patterns.iter().any(|p| p == "std::fs"));
+        assert!(config.exclude_patterns.iter().any(|p| p == "my_crate::helper"));
+
+        let config = parse_attributes_from_str(r#"exclude = ["tokio::"]"#);
+        assert!(config.exclude_patterns.iter().any(|p| p == "tokio::"));
+    }
+
+    #[test]
+    fn test_invalid_attributes_are_rejected() {
+        let cases = [
+            "unknown_option",
+            r#"max_depth = "five""#,
+            "skip(password,, token)",
+            r#"serialize(conn = "not a path")"#,
+            r#"level = "loud""#,
+            r#"crate_path = "not a path""#,
+        ];
+        for case in cases {
+            let tokens: proc_macro2::TokenStream = case.parse().expect("attribute tokens");
+            assert!(parse_attributes(tokens).is_err(), "accepted invalid attribute: {case}");
+        }
+    }
+
     #[test]
     fn test_generate_parameter_records() {
         let sig: syn::Signature = parse_quote! {
             fn test_fn(x: i32, y: &str) -> String
         };
         
-        let records = generate_parameter_records(&sig);
+        let records = generate_parameter_records(&sig, &PropagateConfig::default());
         assert_eq!(records.len(), 2);
     }
     
@@ -531,18 +1504,22 @@ mod tests {
     #[test]
     fn test_should_instrument_call_enabled() {
         let call: ExprCall = parse_quote! { user_function() };
-        let mut config = PropagateConfig::default();
-        config.enabled = true;
-        
+        let config = PropagateConfig {
+            enabled: true,
+            ..PropagateConfig::default()
+        };
+
         assert!(should_instrument_call(&call, &config));
     }
     
     #[test]
     fn test_should_instrument_call_excluded() {
         let call: ExprCall = parse_quote! { std::process::exit(0) };
-        let mut config = PropagateConfig::default();
-        config.enabled = true;
-        
+        let config = PropagateConfig {
+            enabled: true,
+            ..PropagateConfig::default()
+        };
+
         assert!(!should_instrument_call(&call, &config));
     }
     
@@ -2,6 +2,14 @@
 #[cfg(feature = "with_macro")]
 pub use trace_macro;
 
+// Re-exported for code generated under the macro's `tracing_compat`
+// option, so user crates need no direct tracing dependency
+pub use tracing;
+
+// Re-exported so a facade crate named via `crate_path = "..."` can reach
+// the shared serialization macros through a single root
+pub use trace_common;
+
 // use tracing::{Subscriber, subscriber::set_global_default};
 // use tracing_subscriber::{Layer, Registry, layer::SubscriberExt};
 // use std::sync::{Arc, Mutex, RwLock};
@@ -17,24 +25,112 @@ pub mod trace_data {
     /// Represents a single function call in the call stack
     #[derive(Debug, Serialize)]
     pub struct CallNode {
+        /// Process-wide unique ID for this call, assigned at enter time
+        pub call_id: u64,
         pub name: String,
+        /// Module path of the traced function, from `module_path!()`; lets
+        /// identically named functions in different modules be told apart
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub module_path: Option<String>,
         pub file: String,
         pub line: u32,
+        /// Column of the call site, from `column!()`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub column: Option<u32>,
+        /// Stable hash of name + file + line + column; identical across
+        /// runs, so tools can deduplicate and cross-reference call sites
+        pub call_site_id: u64,
+        /// Random OpenTelemetry-compatible span ID (16 lowercase hex
+        /// characters), fresh for each call
+        pub span_id: String,
+        /// Resolved backtrace captured when this call was entered outside any
+        /// traced parent; only present when backtrace capture is enabled
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub backtrace: Option<String>,
+        /// Serialized call-site arguments for propagated child calls;
+        /// only present when the opt-in capture mode is enabled
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub args: Option<Value>,
+        /// Ad-hoc checkpoint events recorded while this call was active
+        #[serde(
+            serialize_with = "serialize_mutex_events",
+            skip_serializing_if = "mutex_vec_is_empty"
+        )]
+        pub events: Mutex<Vec<CheckpointEvent>>,
+        /// Key/value metadata attached via `interface::tag`, e.g. request
+        /// or user IDs for correlating traces with external logs
+        #[serde(
+            serialize_with = "serialize_mutex_map",
+            skip_serializing_if = "mutex_map_is_empty"
+        )]
+        pub tags: Mutex<serde_json::Map<String, Value>>,
         #[serde(serialize_with = "serialize_mutex_vec")]
         pub children: Mutex<Vec<Arc<CallNode>>>,
     }
 
+    /// An ad-hoc checkpoint recorded mid-function via
+    /// [`crate::trace_event!`] / `interface::event`
+    #[derive(Debug, Clone, Serialize)]
+    pub struct CheckpointEvent {
+        pub timestamp_utc: String,
+        pub name: String,
+        pub data: Value,
+    }
+
     impl Clone for CallNode {
         fn clone(&self) -> Self {
             Self {
+                call_id: self.call_id,
                 name: self.name.clone(),
+                module_path: self.module_path.clone(),
                 file: self.file.clone(),
                 line: self.line,
-                children: Mutex::new(Vec::new()), 
+                column: self.column,
+                call_site_id: self.call_site_id,
+                span_id: self.span_id.clone(),
+                backtrace: self.backtrace.clone(),
+                args: self.args.clone(),
+                events: Mutex::new(Vec::new()),
+                tags: Mutex::new(serde_json::Map::new()),
+                children: Mutex::new(Vec::new()),
             }
         }
     }
 
+    fn serialize_mutex_events<S>(mutex_vec: &Mutex<Vec<CheckpointEvent>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+        let locked_vec = mutex_vec.lock().unwrap();
+        let mut seq = serializer.serialize_seq(Some(locked_vec.len()))?;
+        for element in locked_vec.iter() {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+
+    fn mutex_vec_is_empty<T>(mutex_vec: &Mutex<Vec<T>>) -> bool {
+        mutex_vec.lock().map(|v| v.is_empty()).unwrap_or(true)
+    }
+
+    fn serialize_mutex_map<S>(mutex_map: &Mutex<serde_json::Map<String, Value>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let locked_map = mutex_map.lock().unwrap();
+        let mut map = serializer.serialize_map(Some(locked_map.len()))?;
+        for (key, value) in locked_map.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+
+    fn mutex_map_is_empty(mutex_map: &Mutex<serde_json::Map<String, Value>>) -> bool {
+        mutex_map.lock().map(|map| map.is_empty()).unwrap_or(true)
+    }
+
     fn serialize_mutex_vec<S>(mutex_vec: &Mutex<Vec<Arc<CallNode>>>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -48,15 +144,96 @@ pub mod trace_data {
         seq.end()
     }
 
+    /// Version of the on-disk trace format emitted by this crate
+    /// Mirrors [`trace_common::schema::SCHEMA_VERSION`]; bump both together
+    /// with a new arm in `trace_common::schema::migrate`
+    pub const TRACE_SCHEMA_VERSION: u32 = trace_common::schema::SCHEMA_VERSION;
+
+    /// Wall-clock time as RFC 3339; `wasm32-unknown-unknown` has no system
+    /// clock, so the epoch is recorded there
+    pub(crate) fn wall_clock_rfc3339() -> String {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            chrono::Utc::now().to_rfc3339()
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            "1970-01-01T00:00:00+00:00".to_string()
+        }
+    }
+
+    /// Metadata header emitted as the first record of every output file
+    ///
+    /// Lets downstream tooling detect which format variant it is reading as
+    /// the format evolves.
+    #[derive(Debug, Clone, Serialize)]
+    pub struct TraceHeader {
+        pub schema_version: u32,
+        pub tool_version: String,
+        pub start_time: String,
+        pub hostname: String,
+        pub cmdline: Vec<String>,
+    }
+
+    impl TraceHeader {
+        /// Capture header metadata for the current process
+        pub fn capture() -> Self {
+            Self {
+                schema_version: TRACE_SCHEMA_VERSION,
+                tool_version: env!("CARGO_PKG_VERSION").to_string(),
+                start_time: wall_clock_rfc3339(),
+                hostname: hostname(),
+                cmdline: std::env::args().collect(),
+            }
+        }
+    }
+
+    fn hostname() -> String {
+        #[cfg(unix)]
+        {
+            let mut buf = [0u8; 256];
+            // Safety: buf is valid for writes of its full length; gethostname
+            // null-terminates on success.
+            let rc = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+            if rc == 0 {
+                let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+                if let Ok(name) = std::str::from_utf8(&buf[..end]) {
+                    return name.to_string();
+                }
+            }
+        }
+
+        std::env::var("HOSTNAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string())
+    }
+
     /// Complete trace data for a function call
     #[derive(Debug, Serialize)]
     pub struct CallData {
         pub timestamp_utc: String,
         pub thread_id: String,
+        /// OpenTelemetry-compatible trace ID (32 lowercase hex characters)
+        /// shared by every record this process emits; settable via
+        /// `interface::set_trace_id` to join traces across processes
+        pub trace_id: String,
+        /// Tokio task ID when captured inside a task (requires the
+        /// `tokio_ids` feature); lets interleaved async traces be grouped
+        /// per task
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub task_id: Option<String>,
+        /// Name of the runtime worker thread the call ran on, when known
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub worker_name: Option<String>,
         #[serde(serialize_with = "serialize_arc_call_node")]
         pub root_node: Arc<CallNode>,
         pub inputs: Value,
         pub output: Value,
+        /// Wall time spent in the call body, in nanoseconds; absent for
+        /// records produced by callers that do not measure (manual
+        /// `record_top_level_call` users, pre-duration trace files)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub duration_ns: Option<u64>,
     }
 
     fn serialize_arc_call_node<S>(arc_node: &Arc<CallNode>, serializer: S) -> Result<S::Ok, S::Error>
@@ -65,17 +242,77 @@ pub mod trace_data {
     {
         arc_node.as_ref().serialize(serializer)
     }
+
+    impl CallNode {
+        /// Snapshot this in-flight node into the canonical plain schema
+        /// from [`trace_common::schema`], which is what trace files hold
+        pub fn to_schema(&self) -> trace_common::schema::CallNode {
+            trace_common::schema::CallNode {
+                call_id: self.call_id,
+                name: self.name.clone(),
+                module_path: self.module_path.clone(),
+                file: self.file.clone(),
+                line: self.line,
+                column: self.column,
+                call_site_id: self.call_site_id,
+                span_id: Some(self.span_id.clone()),
+                backtrace: self.backtrace.clone(),
+                args: self.args.clone(),
+                events: self
+                    .events
+                    .lock()
+                    .map(|events| events.iter().map(CheckpointEvent::to_schema).collect())
+                    .unwrap_or_default(),
+                tags: self.tags.lock().map(|tags| tags.clone()).unwrap_or_default(),
+                children: self
+                    .children
+                    .lock()
+                    .map(|children| children.iter().map(|child| child.to_schema()).collect())
+                    .unwrap_or_default(),
+            }
+        }
+    }
+
+    impl CheckpointEvent {
+        /// Convert into the canonical schema's event type
+        pub fn to_schema(&self) -> trace_common::schema::CheckpointEvent {
+            trace_common::schema::CheckpointEvent {
+                timestamp_utc: self.timestamp_utc.clone(),
+                name: self.name.clone(),
+                data: self.data.clone(),
+            }
+        }
+    }
+
+    impl CallData {
+        /// Snapshot this record into the canonical plain schema from
+        /// [`trace_common::schema`]; serializing either produces the same JSON
+        pub fn to_schema(&self) -> trace_common::schema::CallData {
+            trace_common::schema::CallData {
+                timestamp_utc: self.timestamp_utc.clone(),
+                thread_id: self.thread_id.clone(),
+                trace_id: Some(self.trace_id.clone()),
+                task_id: self.task_id.clone(),
+                worker_name: self.worker_name.clone(),
+                root_node: self.root_node.to_schema(),
+                inputs: self.inputs.clone(),
+                output: self.output.clone(),
+                duration_ns: self.duration_ns,
+            }
+        }
+    }
 }
 
 // --- tracer module ---
 pub mod tracer {
-    use crate::trace_data::{CallData, CallNode};
+    use crate::trace_data::{CallData, CallNode, CheckpointEvent, TraceHeader, wall_clock_rfc3339};
     use std::collections::HashMap;
     use std::fs::{File, OpenOptions};
     use std::io::{Write, BufWriter};
     use std::path::{Path, PathBuf};
     use std::sync::{Arc, Mutex};
     use std::thread;
+    use std::time::{Duration, Instant};
 
     /// Errors that can occur during tracing operations
     #[derive(Debug)]
@@ -111,13 +348,189 @@ pub mod tracer {
         }
     }
 
+    impl From<TraceError> for trace_common::Error {
+        fn from(err: TraceError) -> Self {
+            match err {
+                TraceError::Io(e) => trace_common::Error::Io(e),
+                TraceError::Serialization(e) => trace_common::Error::Serde(e),
+                TraceError::LockPoisoned => {
+                    trace_common::Error::Config("tracer state lock poisoned".to_string())
+                }
+                TraceError::TracingSetup(e) => trace_common::Error::Config(e),
+            }
+        }
+    }
+
+    /// Controls how often the stream writer flushes buffered events to disk.
+    ///
+    /// Flushing after every event is the safest option but destroys throughput
+    /// for high-frequency traces (e.g. propagated instrumentation). The other
+    /// policies trade crash-safety for fewer syscalls.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub enum FlushPolicy {
+        /// Flush after every recorded event (safest, slowest)
+        #[default]
+        EveryEvent,
+        /// Flush after every N recorded events
+        EveryN(usize),
+        /// Flush at most once per interval; the first event after the
+        /// interval elapses triggers the flush
+        Interval(Duration),
+        /// Only flush when the trace is finalized or cleared
+        OnFinalize,
+    }
+
+    /// On-disk framing used by [`OutputMode::Stream`]
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub enum StreamFraming {
+        /// JSON array (or JSON Lines with `append`); events may span
+        /// multiple writes, so concurrent tailers can observe partial events
+        #[default]
+        Delimited,
+        /// Each record is written as one frame — its payload byte length in
+        /// ASCII decimal, a newline, the payload, a newline — issued as a
+        /// single write. A tailer reads the length line and then waits until
+        /// that many bytes are present, so it never parses a half-written
+        /// event.
+        LengthPrefixed,
+    }
+
     /// Output configuration for trace data
     #[derive(Debug, Clone)]
     pub enum OutputMode {
         /// Store in memory, write only on manual finalize
         Memory,
         /// Stream directly to file with automatic cleanup
-        Stream { path: PathBuf },
+        ///
+        /// With `append` set, events are written as JSON Lines appended to
+        /// any existing file (prefixed by a session-start marker record)
+        /// instead of truncating it into a fresh JSON array. This keeps
+        /// traces from repeated short runs of the same program.
+        Stream { path: PathBuf, flush_policy: FlushPolicy, append: bool, framing: StreamFraming },
+    }
+
+    /// Retention policy for previously written trace files
+    ///
+    /// Platform default output locations accumulate `trace_output.json` files
+    /// indefinitely; a retention policy prunes the output directory when
+    /// auto-save is initialized. Files are removed oldest-first.
+    #[derive(Debug, Clone, Default)]
+    pub struct RetentionPolicy {
+        /// Remove oldest files until the directory's trace files total at
+        /// most this many bytes
+        pub max_total_bytes: Option<u64>,
+        /// Remove trace files older than this
+        pub max_age: Option<Duration>,
+    }
+
+    impl RetentionPolicy {
+        fn is_active(&self) -> bool {
+            self.max_total_bytes.is_some() || self.max_age.is_some()
+        }
+    }
+
+    /// Environment variable carrying the trace output path template
+    ///
+    /// Set for child processes (see [`interface::propagate_to_children`]) so
+    /// spawned programs write their own correlated trace files. The value may
+    /// contain a `%p` placeholder which expands to the process ID at init.
+    pub const TRACE_OUTPUT_ENV: &str = "TRACE_OUTPUT_FILE";
+
+    /// Expand the `%p` pid placeholder in an output path template
+    fn expand_pid_placeholder(path: &Path) -> PathBuf {
+        let raw = path.to_string_lossy();
+        if raw.contains("%p") {
+            PathBuf::from(raw.replace("%p", &std::process::id().to_string()))
+        } else {
+            path.to_path_buf()
+        }
+    }
+
+    /// Cap on in-memory result buffering; when exceeded, older events are
+    /// transparently spilled to a temp file and stitched back at finalize
+    #[derive(Debug, Clone, Default)]
+    pub struct MemoryCap {
+        /// Spill once this many events are buffered
+        pub max_events: Option<usize>,
+        /// Spill once the serialized events exceed this many bytes
+        pub max_bytes: Option<u64>,
+    }
+
+    impl MemoryCap {
+        /// Returns true if neither limit is set
+        pub fn is_unbounded(&self) -> bool {
+            self.max_events.is_none() && self.max_bytes.is_none()
+        }
+    }
+
+    /// Levels used when mirroring tracer activity through the `log` facade
+    ///
+    /// Only available with the `log_bridge` feature; see
+    /// [`interface::enable_log_bridge`].
+    #[cfg(feature = "log_bridge")]
+    #[derive(Debug, Clone)]
+    pub struct LogBridgeConfig {
+        /// Level for function-enter messages
+        pub enter_level: log::Level,
+        /// Level for function-exit messages
+        pub exit_level: log::Level,
+        /// Level for completed call records
+        pub record_level: log::Level,
+    }
+
+    #[cfg(feature = "log_bridge")]
+    impl Default for LogBridgeConfig {
+        fn default() -> Self {
+            Self {
+                enter_level: log::Level::Debug,
+                exit_level: log::Level::Debug,
+                record_level: log::Level::Info,
+            }
+        }
+    }
+
+    /// Aggregated per-session metrics, maintained incrementally so a quick
+    /// overview never requires post-processing the raw trace data
+    #[derive(Debug, Default, Clone, serde::Serialize)]
+    pub struct TraceSummary {
+        /// Per-function call counts and cumulative wall-clock time
+        pub per_function: std::collections::BTreeMap<String, FunctionSummary>,
+        /// Deepest call nesting observed on any thread
+        pub max_depth: usize,
+        /// Total number of recorded events
+        pub total_events: u64,
+        /// Events dropped instead of recorded (e.g. due to limits)
+        pub dropped_events: u64,
+    }
+
+    /// Per-function slice of a [`TraceSummary`]
+    #[derive(Debug, Default, Clone, serde::Serialize)]
+    pub struct FunctionSummary {
+        pub calls: u64,
+        pub cumulative_micros: u64,
+    }
+
+    /// Point-in-time tracer statistics returned by `interface::get_stats`
+    ///
+    /// The drop counters distinguish events lost to policy (disabled
+    /// functions, bounded-channel overflow) from events that were simply
+    /// never produced.
+    #[derive(Debug, Clone, Default, serde::Serialize)]
+    pub struct TracerStats {
+        /// Completed call records accepted this session
+        pub events_recorded: u64,
+        /// Events dropped because the background writer channel was full
+        pub events_dropped_overflow: u64,
+        /// Spans skipped because their function was disabled at runtime
+        pub events_skipped_disabled: u64,
+        /// Call records currently buffered in memory
+        pub buffered_events: usize,
+        /// Event payload bytes written to stream and spill files
+        pub bytes_written: u64,
+        /// Threads with a live call stack entry
+        pub active_threads: usize,
+        /// Current call depth per thread label
+        pub depth_per_thread: std::collections::BTreeMap<String, usize>,
     }
 
     /// Configuration for auto-save functionality
@@ -126,6 +539,10 @@ pub mod tracer {
         pub path: PathBuf,
         pub enable_panic_hook: bool,
         pub enable_exit_hook: bool,
+        pub flush_policy: FlushPolicy,
+        pub retention: RetentionPolicy,
+        pub append: bool,
+        pub framing: StreamFraming,
     }
 
     impl Default for AutoSaveConfig {
@@ -134,6 +551,10 @@ pub mod tracer {
                 path: Self::default_path(),
                 enable_panic_hook: true,
                 enable_exit_hook: true,
+                flush_policy: FlushPolicy::default(),
+                retention: RetentionPolicy::default(),
+                append: false,
+                framing: StreamFraming::default(),
             }
         }
     }
@@ -145,6 +566,10 @@ pub mod tracer {
                 path: path.into(),
                 enable_panic_hook: true,
                 enable_exit_hook: true,
+                flush_policy: FlushPolicy::default(),
+                retention: RetentionPolicy::default(),
+                append: false,
+                framing: StreamFraming::default(),
             }
         }
 
@@ -155,19 +580,55 @@ pub mod tracer {
             self
         }
 
+        /// Set the flush policy used for the streaming output file
+        pub fn with_flush_policy(mut self, policy: FlushPolicy) -> Self {
+            self.flush_policy = policy;
+            self
+        }
+
+        /// Set the retention policy applied to the output directory at init
+        pub fn with_retention(mut self, policy: RetentionPolicy) -> Self {
+            self.retention = policy;
+            self
+        }
+
+        /// Append JSONL events to an existing file instead of truncating it
+        pub fn with_append(mut self, append: bool) -> Self {
+            self.append = append;
+            self
+        }
+
+        /// Select the on-disk framing used for streamed events
+        pub fn with_framing(mut self, framing: StreamFraming) -> Self {
+            self.framing = framing;
+            self
+        }
+
         /// Generate a reasonable default output path following platform conventions
-        fn default_path() -> PathBuf {
+        ///
+        /// Resolution order: the [`TRACE_OUTPUT_ENV`] environment variable, a
+        /// resolver registered via [`interface::set_default_path_resolver`](crate::tracer::interface::set_default_path_resolver),
+        /// the platform data directory, the current directory, and finally the
+        /// system temp directory.
+        pub fn default_path() -> PathBuf {
             // Priority 1: Explicit environment variable override
-            if let Ok(path) = std::env::var("TRACE_OUTPUT_FILE") {
+            if let Ok(path) = std::env::var(TRACE_OUTPUT_ENV) {
                 return PathBuf::from(path);
             }
 
-            // Priority 2: Try to use platform-appropriate directories
+            // Priority 2: Embedder-registered resolver
+            if let Ok(resolver) = DEFAULT_PATH_RESOLVER.lock() {
+                if let Some(resolver) = resolver.as_ref() {
+                    return resolver();
+                }
+            }
+
+            // Priority 3: Try to use platform-appropriate directories
             if let Some(data_dir) = Self::get_app_data_dir() {
                 return data_dir.join("trace_output.json");
             }
 
-            // Priority 3: Fallback to current working directory
+            // Priority 4: Fallback to current working directory
             if let Ok(current_dir) = std::env::current_dir() {
                 current_dir.join("trace_output.json")
             } else {
@@ -197,8 +658,11 @@ pub mod tracer {
 
             #[cfg(target_os = "linux")]
             {
-                // Follow XDG Base Directory Specification
-                if let Ok(xdg_data_home) = std::env::var("XDG_DATA_HOME") {
+                // Follow XDG Base Directory Specification; traces are closer
+                // to state than data, so XDG_STATE_HOME wins when set
+                if let Ok(xdg_state_home) = std::env::var("XDG_STATE_HOME") {
+                    Some(PathBuf::from(xdg_state_home).join("rust-tracer"))
+                } else if let Ok(xdg_data_home) = std::env::var("XDG_DATA_HOME") {
                     Some(PathBuf::from(xdg_data_home).join("rust-tracer"))
                 } else if let Ok(home) = std::env::var("HOME") {
                     Some(PathBuf::from(home).join(".local").join("share").join("rust-tracer"))
@@ -223,7 +687,7 @@ pub mod tracer {
             
             // Attempt to create parent directories
             if let Some(parent) = path.parent() {
-                if let Err(_) = std::fs::create_dir_all(parent) {
+                if std::fs::create_dir_all(parent).is_err() {
                     // If we can't create the preferred directory, fall back to current dir
                     return Self::new("trace_output.json");
                 }
@@ -234,27 +698,98 @@ pub mod tracer {
     }
 
     #[derive(Debug)]
-    struct TracerState {
+    pub(crate) struct TracerState {
+        header: TraceHeader,
         call_stacks: HashMap<thread::ThreadId, Vec<Arc<CallNode>>>,
         results: Vec<CallData>,
         output_mode: OutputMode,
         stream_writer: Option<BufWriter<File>>,
         tracing_initialized: bool,
-        stream_event_count: usize, 
+        stream_event_count: usize,
+        events_since_flush: usize,
+        last_flush: Instant,
+        summary: TraceSummary,
+        call_started: HashMap<u64, Instant>,
+        thread_labels: HashMap<thread::ThreadId, String>,
+        background_tx: Option<std::sync::mpsc::SyncSender<CallData>>,
+        background_writer: Option<thread::JoinHandle<()>>,
+        memory_cap: Option<MemoryCap>,
+        memory_bytes: u64,
+        bytes_written: u64,
+        max_total_events: Option<u64>,
+        capped: bool,
+        spill_path: Option<PathBuf>,
+        spill_writer: Option<BufWriter<File>>,
     }
 
     impl TracerState {
         fn new() -> Self {
             TracerState {
+                header: TraceHeader::capture(),
                 call_stacks: HashMap::new(),
                 results: Vec::new(),
                 output_mode: OutputMode::Memory,
                 stream_writer: None,
                 tracing_initialized: false,
                 stream_event_count: 0,
+                events_since_flush: 0,
+                last_flush: Instant::now(),
+                summary: TraceSummary::default(),
+                call_started: HashMap::new(),
+                thread_labels: HashMap::new(),
+                background_tx: None,
+                background_writer: None,
+                memory_cap: None,
+                memory_bytes: 0,
+                bytes_written: 0,
+                max_total_events: None,
+                capped: false,
+                spill_path: None,
+                spill_writer: None,
+            }
+        }
+
+        /// Identifier recorded for a thread; stable t0/t1/... labels in
+        /// deterministic mode, the debug ThreadId otherwise
+        fn thread_label(&mut self, thread_id: thread::ThreadId) -> String {
+            if !deterministic() {
+                return format!("{:?}", thread_id);
+            }
+            let next = self.thread_labels.len();
+            self.thread_labels
+                .entry(thread_id)
+                .or_insert_with(|| format!("t{}", next))
+                .clone()
+        }
+
+        /// Header as written to output; sanitized in deterministic mode so
+        /// golden files do not differ across hosts and invocations
+        fn effective_header(&self) -> TraceHeader {
+            if deterministic() {
+                TraceHeader {
+                    start_time: "1970-01-01T00:00:00+00:00".to_string(),
+                    hostname: "localhost".to_string(),
+                    cmdline: Vec::new(),
+                    ..self.header.clone()
+                }
+            } else {
+                self.header.clone()
             }
         }
 
+        fn stream_is_append(&self) -> bool {
+            matches!(self.output_mode, OutputMode::Stream { append: true, .. })
+        }
+
+        /// Whether the active stream file is a JSON array that still needs
+        /// its closing bracket written
+        fn stream_writes_array_footer(&self) -> bool {
+            matches!(
+                self.output_mode,
+                OutputMode::Stream { append: false, framing: StreamFraming::Delimited, .. }
+            )
+        }
+
         fn ensure_tracing_initialized(&mut self) -> Result<(), TraceError> {
             if !self.tracing_initialized {
                 self.tracing_initialized = true;
@@ -264,8 +799,10 @@ pub mod tracer {
 
         fn set_output_mode(&mut self, mode: OutputMode) -> Result<(), TraceError> {
             if let Some(mut writer) = self.stream_writer.take() {
-                let _ = writeln!(writer, "");
-                let _ = writeln!(writer, "]");
+                if self.stream_writes_array_footer() {
+                    let _ = writeln!(writer);
+                    let _ = writeln!(writer, "]");
+                }
                 let _ = writer.flush();
             }
             
@@ -273,20 +810,40 @@ pub mod tracer {
                 OutputMode::Memory => {
                     self.stream_writer = None;
                 }
-                OutputMode::Stream { path } => {
+                OutputMode::Stream { path, append, framing, .. } => {
                     if let Some(parent) = path.parent() {
                         std::fs::create_dir_all(parent)?;
                     }
-                    let file = OpenOptions::new()
-                        .create(true)
-                        .write(true)
-                        .truncate(true)
-                        .open(path)?;
+                    let mut options = OpenOptions::new();
+                    options.create(true).write(true);
+                    if *append {
+                        options.append(true);
+                    } else {
+                        options.truncate(true);
+                    }
+                    let file = options.open(path)?;
                     let mut writer = BufWriter::new(file);
-                    writeln!(writer, "[")?;
+                    self.header.start_time = timestamp_now();
+                    let header = self.effective_header();
+                    if *framing == StreamFraming::LengthPrefixed {
+                        let _ = write_frame(&mut writer, &serde_json::to_string(&header)?)?;
+                    } else if *append {
+                        // Session-start marker so readers can split sessions;
+                        // carries the full metadata header plus the pid
+                        let mut marker = serde_json::to_value(&header)?;
+                        marker["session_start"] = serde_json::json!(true);
+                        marker["pid"] = serde_json::json!(if deterministic() { 0 } else { std::process::id() });
+                        writeln!(writer, "{}", marker)?;
+                    } else {
+                        writeln!(writer, "[")?;
+                        let header_json = serde_json::to_string_pretty(&header)?;
+                        write!(writer, "{}", header_json)?;
+                    }
                     writer.flush()?;
                     self.stream_writer = Some(writer);
-                    self.stream_event_count = 0; 
+                    self.stream_event_count = 0;
+                    self.events_since_flush = 0;
+                    self.last_flush = Instant::now();
                 }
             }
             
@@ -295,14 +852,45 @@ pub mod tracer {
         }
 
         fn write_stream_event(&mut self, call_data: &CallData) -> Result<(), TraceError> {
+            let (flush_policy, append, framing) = match &self.output_mode {
+                OutputMode::Stream { flush_policy, append, framing, .. } => {
+                    (flush_policy.clone(), *append, framing.clone())
+                }
+                OutputMode::Memory => (FlushPolicy::default(), false, StreamFraming::default()),
+            };
+
             if let Some(writer) = &mut self.stream_writer {
-                if self.stream_event_count > 0 {
+                if framing == StreamFraming::LengthPrefixed {
+                    let written = write_frame(writer, &serde_json::to_string(call_data)?)?;
+                    self.bytes_written += written as u64;
+                } else if append {
+                    // JSONL flavor: one complete event per line
+                    let json_string = serde_json::to_string(call_data)?;
+                    writeln!(writer, "{}", json_string)?;
+                    self.bytes_written += json_string.len() as u64 + 1;
+                } else {
+                    // The metadata header is always element 0 of the array,
+                    // so every event is preceded by a separator
                     writeln!(writer, ",")?;
+                    let json_string = serde_json::to_string_pretty(call_data)?;
+                    write!(writer, "{}", json_string)?;
+                    self.bytes_written += json_string.len() as u64 + 2;
                 }
-                let json_string = serde_json::to_string_pretty(call_data)?;
-                write!(writer, "{}", json_string)?;
-                writer.flush()?;
                 self.stream_event_count += 1;
+                self.events_since_flush += 1;
+
+                let should_flush = match flush_policy {
+                    FlushPolicy::EveryEvent => true,
+                    FlushPolicy::EveryN(n) => self.events_since_flush >= n.max(1),
+                    FlushPolicy::Interval(interval) => self.last_flush.elapsed() >= interval,
+                    FlushPolicy::OnFinalize => false,
+                };
+
+                if should_flush {
+                    writer.flush()?;
+                    self.events_since_flush = 0;
+                    self.last_flush = Instant::now();
+                }
             }
             Ok(())
         }
@@ -313,15 +901,28 @@ pub mod tracer {
                     if let Some(parent) = output_path.parent() {
                         std::fs::create_dir_all(parent)?;
                     }
-                    let json_string = serde_json::to_string_pretty(&self.results)?;
+                    // Spilled events come before the in-memory tail so the
+                    // stitched file stays in recording order
+                    let mut document = Vec::with_capacity(self.results.len() + 1);
+                    document.push(serde_json::to_value(self.effective_header())?);
+                    document.extend(self.take_spilled_events()?);
+                    for call_data in &self.results {
+                        document.push(serde_json::to_value(call_data)?);
+                    }
+                    if self.capped {
+                        document.push(self.capped_marker());
+                    }
+                    let json_string = serde_json::to_string_pretty(&document)?;
                     let mut file = File::create(output_path)?;
                     file.write_all(json_string.as_bytes())?;
                     file.flush()?;
                 },
-                OutputMode::Stream { path: stream_path } => {
+                OutputMode::Stream { path: stream_path, append, framing, .. } => {
                     if let Some(mut writer) = self.stream_writer.take() {
-                        writeln!(writer, "")?;
-                        writeln!(writer, "]")?;
+                        if !append && *framing == StreamFraming::Delimited {
+                            writeln!(writer)?;
+                            writeln!(writer, "]")?;
+                        }
                         writer.flush()?;
                         
                         if output_path != stream_path {
@@ -335,19 +936,182 @@ pub mod tracer {
             Ok(())
         }
 
+        /// Marker record appended to output when the event cap fires, so
+        /// readers can tell the trace was truncated by policy rather than a
+        /// crash
+        fn capped_marker(&self) -> serde_json::Value {
+            serde_json::json!({
+                "trace_capped": true,
+                "max_total_events": self.max_total_events,
+                "timestamp_utc": timestamp_now(),
+            })
+        }
+
+        /// Stop recording once `max_total_events` is reached
+        ///
+        /// Returns `true` when recording is capped. On the transition it
+        /// writes the capped-notification marker and finalizes any open
+        /// stream so the file on disk is complete and parseable.
+        fn enforce_event_cap(&mut self) -> bool {
+            if self.capped {
+                return true;
+            }
+            let Some(max) = self.max_total_events else {
+                return false;
+            };
+            if self.summary.total_events < max {
+                return false;
+            }
+
+            self.capped = true;
+            tracing::warn!(
+                target: "rustforger_trace",
+                "max_total_events ({}) reached; tracing stopped",
+                max
+            );
+
+            if let Some(mut writer) = self.stream_writer.take() {
+                let marker = self.capped_marker();
+                let (append, framing) = match &self.output_mode {
+                    OutputMode::Stream { append, framing, .. } => (*append, framing.clone()),
+                    OutputMode::Memory => (false, StreamFraming::default()),
+                };
+                if framing == StreamFraming::LengthPrefixed {
+                    let _ = write_frame(&mut writer, &marker.to_string());
+                } else if append {
+                    let _ = writeln!(writer, "{}", marker);
+                } else {
+                    let _ = writeln!(writer, ",");
+                    let _ = write!(writer, "{}", serde_json::to_string_pretty(&marker).unwrap_or_else(|_| marker.to_string()));
+                    let _ = writeln!(writer);
+                    let _ = writeln!(writer, "]");
+                }
+                let _ = writer.flush();
+            }
+            true
+        }
+
+        /// Spill the older half of buffered results to the temp spill file
+        /// when the configured memory cap is exceeded
+        fn enforce_memory_cap(&mut self) -> Result<(), TraceError> {
+            let cap = match &self.memory_cap {
+                Some(cap) if !cap.is_unbounded() => cap.clone(),
+                _ => return Ok(()),
+            };
+
+            let over = cap.max_events.is_some_and(|max| self.results.len() > max)
+                || cap.max_bytes.is_some_and(|max| self.memory_bytes > max);
+            if !over || self.results.len() < 2 {
+                return Ok(());
+            }
+
+            if self.spill_writer.is_none() {
+                let path = std::env::temp_dir()
+                    .join(format!("rustforger_spill_{}.jsonl", std::process::id()));
+                let file = OpenOptions::new().create(true).append(true).open(&path)?;
+                self.spill_path = Some(path);
+                self.spill_writer = Some(BufWriter::new(file));
+            }
+
+            let spill_count = self.results.len() / 2;
+            let drained: Vec<CallData> = self.results.drain(..spill_count).collect();
+            if let Some(writer) = &mut self.spill_writer {
+                for call_data in &drained {
+                    let line = serde_json::to_string(call_data)?;
+                    self.memory_bytes = self.memory_bytes.saturating_sub(line.len() as u64);
+                    writeln!(writer, "{}", line)?;
+                    self.bytes_written += line.len() as u64 + 1;
+                }
+                writer.flush()?;
+            }
+
+            tracing::info!(
+                target: "rustforger_trace",
+                "Spilled {} events to disk to respect memory cap",
+                spill_count
+            );
+            Ok(())
+        }
+
+        /// Read back any spilled events so finalize can stitch the full
+        /// session together in order
+        fn take_spilled_events(&mut self) -> Result<Vec<serde_json::Value>, TraceError> {
+            if let Some(mut writer) = self.spill_writer.take() {
+                writer.flush()?;
+            }
+            let path = match self.spill_path.take() {
+                Some(path) => path,
+                None => return Ok(Vec::new()),
+            };
+
+            let content = std::fs::read_to_string(&path)?;
+            let mut events = Vec::new();
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                events.push(serde_json::from_str(line)?);
+            }
+            let _ = std::fs::remove_file(&path);
+            self.memory_bytes = 0;
+            Ok(events)
+        }
+
+        /// Snapshot the current in-memory results to a timestamped file
+        /// without clearing them or stopping tracing
+        fn dump_snapshot(&self) -> Result<PathBuf, TraceError> {
+            #[cfg(not(target_arch = "wasm32"))]
+            let stamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+            #[cfg(target_arch = "wasm32")]
+            let stamp = "00000000_000000".to_string();
+            let dump_path = PathBuf::from(format!("trace_dump_{}.json", stamp));
+            let json_string = serde_json::to_string_pretty(&self.document_with_header()?)?;
+            let mut file = File::create(&dump_path)?;
+            file.write_all(json_string.as_bytes())?;
+            file.flush()?;
+            Ok(dump_path)
+        }
+
+        /// Write the session summary metrics next to the given trace file
+        fn write_summary(&mut self, output_path: &Path) -> Result<(), TraceError> {
+            let summary_path = output_path
+                .parent()
+                .map(|dir| dir.join("trace_summary.json"))
+                .unwrap_or_else(|| PathBuf::from("trace_summary.json"));
+            let json_string = serde_json::to_string_pretty(&self.summary)?;
+            let mut file = File::create(&summary_path)?;
+            file.write_all(json_string.as_bytes())?;
+            file.flush()?;
+            self.summary = TraceSummary::default();
+            Ok(())
+        }
+
+        /// Build the serialized output document: metadata header followed by
+        /// all recorded events
+        fn document_with_header(&self) -> Result<Vec<serde_json::Value>, TraceError> {
+            let mut document = Vec::with_capacity(self.results.len() + 1);
+            document.push(serde_json::to_value(self.effective_header())?);
+            for call_data in &self.results {
+                document.push(serde_json::to_value(call_data)?);
+            }
+            if self.capped {
+                document.push(self.capped_marker());
+            }
+            Ok(document)
+        }
+
         fn emergency_save(&mut self) -> Result<(), TraceError> {
             match &self.output_mode {
-                OutputMode::Stream { .. } => {
+                OutputMode::Stream { append, framing, .. } => {
                     if let Some(mut writer) = self.stream_writer.take() {
-                        let _ = writeln!(writer, "");
-                        let _ = writeln!(writer, "]");
+                        if !append && *framing == StreamFraming::Delimited {
+                            let _ = writeln!(writer);
+                            let _ = writeln!(writer, "]");
+                        }
                         let _ = writer.flush();
                     }
                 },
                 OutputMode::Memory => {
                     if !self.results.is_empty() {
                         let emergency_path = "emergency_trace_backup.json";
-                        let json_string = serde_json::to_string_pretty(&self.results)?;
+                        let json_string = serde_json::to_string_pretty(&self.document_with_header()?)?;
                         let mut file = File::create(emergency_path)?;
                         file.write_all(json_string.as_bytes())?;
                         file.flush()?;
@@ -359,15 +1123,218 @@ pub mod tracer {
     }
 
     lazy_static::lazy_static! {
+        /// Optional embedder-supplied resolver consulted by
+        /// [`AutoSaveConfig::default_path`] before the platform heuristics
+        static ref DEFAULT_PATH_RESOLVER: Mutex<Option<Box<dyn Fn() -> PathBuf + Send + Sync>>> =
+            Mutex::new(None);
+
+        /// Function names currently switched off at runtime
+        static ref DISABLED_FUNCTIONS: std::sync::RwLock<std::collections::HashSet<String>> =
+            std::sync::RwLock::new(std::collections::HashSet::new());
+
         static ref TRACER: Mutex<TracerState> = Mutex::new(TracerState::new());
     }
 
+    /// Active `log` facade bridge, `None` while disabled
+    #[cfg(feature = "log_bridge")]
+    static LOG_BRIDGE: Mutex<Option<LogBridgeConfig>> = Mutex::new(None);
+
+    /// Mirror one tracer event through the `log` facade when the bridge is on
+    ///
+    /// The message is built lazily so a disabled bridge costs only a lock
+    /// probe at each call site.
+    #[cfg(feature = "log_bridge")]
+    fn log_bridge_emit(pick: fn(&LogBridgeConfig) -> log::Level, message: impl FnOnce() -> String) {
+        if let Ok(slot) = LOG_BRIDGE.lock() {
+            if let Some(config) = slot.as_ref() {
+                log::log!(target: "rustforger_trace", pick(config), "{}", message());
+            }
+        }
+    }
+
+    /// Optional callback sink invoked with every completed call record
+    ///
+    /// The primary escape hatch on targets without a usable filesystem
+    /// (browser WASM); the embedder forwards events wherever it likes.
+    static EVENT_CALLBACK: Mutex<Option<fn(&CallData)>> = Mutex::new(None);
+
+    /// Spans skipped because their function was disabled at runtime
+    static SKIPPED_DISABLED: std::sync::atomic::AtomicU64 =
+        std::sync::atomic::AtomicU64::new(0);
+
+    /// Minimum severity recorded, as a [`interface::TraceLevel`]
+    /// discriminant; spans below it become inactive no-ops
+    static MIN_LEVEL: std::sync::atomic::AtomicU8 =
+        std::sync::atomic::AtomicU8::new(0);
+
+    /// Bumped on every disable/enable change; a zero value is the fast path
+    /// telling span creation that no function has ever been disabled
+    static DISABLED_GENERATION: std::sync::atomic::AtomicU64 =
+        std::sync::atomic::AtomicU64::new(0);
+
+    /// Monotonic source of process-wide unique call IDs
+    static NEXT_CALL_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+    fn next_call_id() -> u64 {
+        NEXT_CALL_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Stable FNV-1a hash of a call site's identity; `const` so external
+    /// tools (and tests) can recompute the same ID for cross-referencing
+    pub const fn call_site_hash(fn_name: &str, file: &str, line: u32, column: u32) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        const fn fold(mut hash: u64, bytes: &[u8]) -> u64 {
+            let mut i = 0;
+            while i < bytes.len() {
+                hash ^= bytes[i] as u64;
+                hash = hash.wrapping_mul(PRIME);
+                i += 1;
+            }
+            hash
+        }
+
+        let mut hash = fold(OFFSET_BASIS, fn_name.as_bytes());
+        hash = fold(hash, file.as_bytes());
+        hash = fold(hash, &line.to_le_bytes());
+        fold(hash, &column.to_le_bytes())
+    }
+
+    /// When set, entering a traced call with no traced parent captures a
+    /// resolved backtrace showing how un-instrumented code reached it
+    static CAPTURE_ORPHAN_BACKTRACES: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+
+    /// Longest string kept when capping captured child-call arguments
+    const MAX_CHILD_ARG_STRING_LEN: usize = 256;
+
+    /// Redaction rules applied to inputs and outputs as records are built,
+    /// before anything reaches a sink; `None` records values verbatim
+    static REDACTION_RULES: Mutex<Option<trace_common::redact::RedactionRules>> = Mutex::new(None);
+
+    /// Trace ID stamped on every record this process emits; lazily
+    /// generated on first use unless set via `interface::set_trace_id`
+    static TRACE_ID: Mutex<Option<String>> = Mutex::new(None);
+
+    /// The process trace ID, generating one on first use
+    ///
+    /// In deterministic mode the ID is all-ones instead of random so golden
+    /// files stay byte-stable across runs.
+    fn current_trace_id() -> String {
+        if deterministic() {
+            return "f".repeat(32);
+        }
+        TRACE_ID
+            .lock()
+            .map(|mut slot| {
+                slot.get_or_insert_with(trace_common::schema::generate_trace_id)
+                    .clone()
+            })
+            .unwrap_or_else(|_| trace_common::schema::generate_trace_id())
+    }
+
+    /// A span ID for a freshly entered call; derived from the call's own
+    /// ID in deterministic mode, random otherwise
+    fn next_span_id(call_id: u64) -> String {
+        if deterministic() {
+            format!("{:016x}", call_id)
+        } else {
+            trace_common::schema::generate_span_id()
+        }
+    }
+
+    /// Apply the globally configured redaction rules, if any
+    fn apply_redaction(value: &mut serde_json::Value) {
+        if let Ok(rules) = REDACTION_RULES.lock() {
+            if let Some(rules) = rules.as_ref() {
+                rules.apply(value);
+            }
+        }
+    }
+
+    /// Cap string sizes inside captured child-call arguments so a single
+    /// large argument cannot bloat the trace
+    fn cap_arg_strings(value: &mut serde_json::Value) {
+        let limits = trace_common::truncate::TruncateLimits::new()
+            .with_max_string_len(MAX_CHILD_ARG_STRING_LEN);
+        trace_common::truncate::truncate_value(value, &limits);
+    }
+
+    /// Tokio task ID and worker thread name for the current call, when the
+    /// `tokio` feature is enabled and the call runs inside a task
+    fn current_task_context() -> (Option<String>, Option<String>) {
+        #[cfg(all(feature = "tokio_ids", not(target_arch = "wasm32")))]
+        {
+            if let Some(task_id) = tokio::task::try_id() {
+                let worker_name = thread::current().name().map(|name| name.to_string());
+                return (Some(task_id.to_string()), worker_name);
+            }
+        }
+        (None, None)
+    }
+
+    /// When set, recorded traces use fixed timestamps, stable thread labels
+    /// (t0, t1, ...) and a sanitized header so output is byte-stable across
+    /// runs and can be committed as golden files
+    static DETERMINISTIC: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+
+    fn deterministic() -> bool {
+        DETERMINISTIC.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Write one length-prefixed frame as a single `write_all`
+    ///
+    /// See [`StreamFraming::LengthPrefixed`] for the reader protocol.
+    fn write_frame(writer: &mut BufWriter<File>, payload: &str) -> Result<usize, TraceError> {
+        let frame = format!("{}\n{}\n", payload.len(), payload);
+        writer.write_all(frame.as_bytes())?;
+        Ok(frame.len())
+    }
+
+    /// Current timestamp, or the epoch in deterministic mode
+    fn timestamp_now() -> String {
+        if deterministic() {
+            "1970-01-01T00:00:00+00:00".to_string()
+        } else {
+            wall_clock_rfc3339()
+        }
+    }
+
+    /// Set by the Unix signal handler; a watcher thread turns it into an
+    /// actual dump since file IO is not async-signal-safe
+    #[cfg(unix)]
+    static DUMP_REQUESTED: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+
+    /// Guards against spawning more than one dump watcher thread
+    #[cfg(unix)]
+    static DUMP_WATCHER_STARTED: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+
+    #[cfg(unix)]
+    extern "C" fn request_dump(_signal: libc::c_int) {
+        DUMP_REQUESTED.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    fn orphan_backtrace() -> Option<String> {
+        if CAPTURE_ORPHAN_BACKTRACES.load(std::sync::atomic::Ordering::Relaxed) {
+            Some(std::backtrace::Backtrace::force_capture().to_string())
+        } else {
+            None
+        }
+    }
+
     /// Public interface for tracing operations
     pub mod interface {
         use super::*;
         use serde_json::Value;
 
-        pub use super::{TraceError, OutputMode, AutoSaveConfig};
+        pub use super::{TraceError, OutputMode, AutoSaveConfig, FlushPolicy, RetentionPolicy, TraceSummary, FunctionSummary, MemoryCap, StreamFraming, TracerStats, call_site_hash};
+
+        #[cfg(feature = "log_bridge")]
+        pub use super::LogBridgeConfig;
 
         /// Initialize tracing system (should be called once at startup)
         pub fn init() -> Result<(), TraceError> {
@@ -375,88 +1342,554 @@ pub mod tracer {
             state.ensure_tracing_initialized()
         }
 
-        /// Enter a function call (static function name)
-        pub fn enter(fn_name: &'static str, file: &'static str, line: u32) {
-            let _ = init();
-        
-            tracing::info!(
-                target: "rustforger_trace",
-                "Entering function: {} at {}:{}",
-                fn_name, file, line
-            );
-            
-            if let Ok(mut state) = TRACER.lock() {
+        /// Enable or disable backtrace capture for orphan calls
+        ///
+        /// When enabled, a traced function entered outside any traced parent
+        /// records a resolved [`std::backtrace::Backtrace`] on its call node,
+        /// showing where it was reached from un-instrumented code. Off by
+        /// default because resolving frames is expensive.
+        pub fn set_backtrace_capture(enabled: bool) {
+            CAPTURE_ORPHAN_BACKTRACES.store(enabled, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        /// Enable or disable deterministic output mode
+        ///
+        /// In deterministic mode recorded traces use fixed timestamps, stable
+        /// thread labels (t0, t1, ...) and a sanitized metadata header, so
+        /// repeated runs of the same workload produce byte-identical files
+        /// suitable for snapshot testing.
+        pub fn set_deterministic(enabled: bool) {
+            DETERMINISTIC.store(enabled, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        /// Register SIGUSR1/SIGUSR2 handlers that dump the current in-memory
+        /// results to a timestamped `trace_dump_*.json` file (Unix only)
+        ///
+        /// The dump does not clear results or stop tracing, so a hung
+        /// production process can be inspected with `kill -USR1 <pid>`.
+        /// Because file IO is not async-signal-safe, the handler only sets a
+        /// flag; a background watcher thread performs the actual write.
+        #[cfg(unix)]
+        pub fn enable_signal_dump() -> Result<(), TraceError> {
+            let handler = request_dump as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            unsafe {
+                if libc::signal(libc::SIGUSR1, handler) == libc::SIG_ERR
+                    || libc::signal(libc::SIGUSR2, handler) == libc::SIG_ERR
+                {
+                    return Err(TraceError::TracingSetup(
+                        "failed to install SIGUSR1/SIGUSR2 handlers".to_string(),
+                    ));
+                }
+            }
+
+            if DUMP_WATCHER_STARTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                return Ok(());
+            }
+
+            thread::spawn(|| loop {
+                thread::sleep(std::time::Duration::from_millis(100));
+                if DUMP_REQUESTED.swap(false, std::sync::atomic::Ordering::SeqCst) {
+                    if let Ok(state) = TRACER.lock() {
+                        match state.dump_snapshot() {
+                            Ok(path) => tracing::info!(
+                                target: "rustforger_trace",
+                                "Dumped trace snapshot to {}",
+                                path.display()
+                            ),
+                            Err(e) => tracing::warn!(
+                                target: "rustforger_trace",
+                                "Failed to dump trace snapshot: {}",
+                                e
+                            ),
+                        }
+                    }
+                }
+            });
+
+            Ok(())
+        }
+
+        /// Shared body of the `enter*` family: logs the entry and pushes a
+        /// new [`CallNode`] onto the current thread's call stack
+        fn enter_impl(
+            fn_name: &str,
+            module_path: Option<&'static str>,
+            file: &'static str,
+            line: u32,
+            column: Option<u32>,
+            args: Option<Value>,
+        ) {
+            let _ = init();
+
+            tracing::info!(
+                target: "rustforger_trace",
+                "Entering function: {} at {}:{}",
+                fn_name, file, line
+            );
+
+            #[cfg(feature = "log_bridge")]
+            log_bridge_emit(|config| config.enter_level, || {
+                format!("enter {} at {}:{}", fn_name, file, line)
+            });
+
+            if let Ok(mut state) = TRACER.lock() {
                 let thread_id = thread::current().id();
                 let stack = state.call_stacks.entry(thread_id).or_default();
-                
+
+                let args = args.map(|mut args| {
+                    cap_arg_strings(&mut args);
+                    args
+                });
+                let call_id = next_call_id();
                 let node = Arc::new(CallNode {
+                    call_id,
                     name: fn_name.to_string(),
+                    module_path: module_path.map(str::to_string),
                     file: file.to_string(),
                     line,
+                    column,
+                    call_site_id: call_site_hash(fn_name, file, line, column.unwrap_or(0)),
+                    span_id: next_span_id(call_id),
+                    backtrace: if stack.is_empty() { orphan_backtrace() } else { None },
+                    args,
+                    events: Mutex::new(Vec::new()),
+                    tags: Mutex::new(serde_json::Map::new()),
                     children: Mutex::new(Vec::new()),
                 });
-                
+
                 if let Some(parent) = stack.last() {
                     if let Ok(mut children) = parent.children.lock() {
                         children.push(node.clone());
                     }
                 }
-                
-                stack.push(node);
+
+                stack.push(node.clone());
+                let depth = stack.len();
+                state.summary.max_depth = state.summary.max_depth.max(depth);
+                state.summary.per_function.entry(node.name.clone()).or_default().calls += 1;
+                state.call_started.insert(node.call_id, Instant::now());
             }
         }
 
+        /// Enter a function call (static function name)
+        pub fn enter(fn_name: &'static str, file: &'static str, line: u32) {
+            enter_impl(fn_name, None, file, line, None, None);
+        }
+
         /// Enter a function call (dynamic function name)
         pub fn enter_dynamic(fn_name: &str, file: &'static str, line: u32) {
+            enter_impl(fn_name, None, file, line, None, None);
+        }
+
+        /// Enter a dynamically named function call, storing serialized
+        /// call-site arguments on the created node
+        ///
+        /// Used by the macro's opt-in child-argument capture; string values
+        /// inside `args` are capped in size before being stored.
+        pub fn enter_dynamic_with_args(fn_name: &str, file: &'static str, line: u32, args: Value) {
+            enter_impl(fn_name, None, file, line, None, Some(args));
+        }
+
+        /// Enter a dynamically named function call with its full source
+        /// location, including `module_path!()` and `column!()`
+        pub fn enter_dynamic_located(
+            fn_name: &str,
+            module_path: &'static str,
+            file: &'static str,
+            line: u32,
+            column: u32,
+        ) {
+            enter_impl(fn_name, Some(module_path), file, line, Some(column), None);
+        }
+
+        /// Enter a dynamically named function call with its full source
+        /// location and captured call-site arguments
+        pub fn enter_dynamic_with_args_located(
+            fn_name: &str,
+            module_path: &'static str,
+            file: &'static str,
+            line: u32,
+            column: u32,
+            args: Value,
+        ) {
+            enter_impl(fn_name, Some(module_path), file, line, Some(column), Some(args));
+        }
+
+        /// RAII guard for a traced span
+        ///
+        /// Created by [`span`] / [`span_dynamic`]; calls [`exit`] when dropped,
+        /// so the per-thread call stack stays balanced even when the traced
+        /// body returns early or panics.
+        #[must_use = "dropping the guard immediately exits the span"]
+        pub struct TraceGuard {
+            active: bool,
+        }
+
+        impl TraceGuard {
+            /// Whether this span actually entered the call tree; `false` when
+            /// the function was disabled via [`disable_function`]
+            pub fn is_active(&self) -> bool {
+                self.active
+            }
+
+            /// A guard that never entered the call tree and exits nothing on
+            /// drop; used by generated code when a call is sampled out
+            pub fn inactive() -> Self {
+                TraceGuard { active: false }
+            }
+        }
+
+        impl Drop for TraceGuard {
+            fn drop(&mut self) {
+                if self.active {
+                    exit();
+                }
+            }
+        }
+
+        /// Enter a function call and return a guard that exits it on drop
+        ///
+        /// Returns an inactive guard without touching the call tree when the
+        /// function has been switched off via [`disable_function`].
+        pub fn span(fn_name: &'static str, file: &'static str, line: u32) -> TraceGuard {
+            if !function_enabled(fn_name) {
+                SKIPPED_DISABLED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                return TraceGuard { active: false };
+            }
+            enter(fn_name, file, line);
+            TraceGuard { active: true }
+        }
+
+        /// Enter a dynamically named function call and return a guard that
+        /// exits it on drop
+        pub fn span_dynamic(fn_name: &str, file: &'static str, line: u32) -> TraceGuard {
+            if !function_enabled(fn_name) {
+                SKIPPED_DISABLED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                return TraceGuard { active: false };
+            }
+            enter_dynamic(fn_name, file, line);
+            TraceGuard { active: true }
+        }
+
+        /// Enter a dynamically named call with captured arguments and return
+        /// a guard that exits it on drop
+        pub fn span_dynamic_with_args(fn_name: &str, file: &'static str, line: u32, args: Value) -> TraceGuard {
+            if !function_enabled(fn_name) {
+                SKIPPED_DISABLED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                return TraceGuard { active: false };
+            }
+            enter_dynamic_with_args(fn_name, file, line, args);
+            TraceGuard { active: true }
+        }
+
+        /// Enter a dynamically named call with its full source location and
+        /// return a guard that exits it on drop
+        ///
+        /// Unlike [`span_dynamic`], this also records `module_path!()` and
+        /// `column!()` on the node, so identically named functions in
+        /// different modules can be told apart in the trace.
+        pub fn span_dynamic_located(
+            fn_name: &str,
+            module_path: &'static str,
+            file: &'static str,
+            line: u32,
+            column: u32,
+        ) -> TraceGuard {
+            if !function_enabled(fn_name) {
+                SKIPPED_DISABLED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                return TraceGuard { active: false };
+            }
+            enter_dynamic_located(fn_name, module_path, file, line, column);
+            TraceGuard { active: true }
+        }
+
+        /// Enter a dynamically named call with its full source location and
+        /// captured arguments, returning a guard that exits it on drop
+        pub fn span_dynamic_with_args_located(
+            fn_name: &str,
+            module_path: &'static str,
+            file: &'static str,
+            line: u32,
+            column: u32,
+            args: Value,
+        ) -> TraceGuard {
+            if !function_enabled(fn_name) {
+                SKIPPED_DISABLED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                return TraceGuard { active: false };
+            }
+            enter_dynamic_with_args_located(fn_name, module_path, file, line, column, args);
+            TraceGuard { active: true }
+        }
+
+        /// Link from a spawned thread back to the call that spawned it
+        ///
+        /// Captured on the spawning thread via [`spawn_link`] and consumed
+        /// on the child thread via [`span_spawned`]. Propagate-generated
+        /// wrappers use the pair around `std::thread::spawn` / `rayon::spawn`
+        /// so spawned work no longer shows up as a disconnected root event.
+        #[derive(Debug, Clone)]
+        pub struct SpawnLink {
+            parent: Arc<CallNode>,
+            file: &'static str,
+            line: u32,
+        }
+
+        /// Capture a link to the current call for handing to a spawned thread
+        ///
+        /// Returns `None` when no traced call is active on this thread, in
+        /// which case [`span_spawned`] is a no-op.
+        pub fn spawn_link(file: &'static str, line: u32) -> Option<SpawnLink> {
+            let state = TRACER.lock().ok()?;
+            let top = state.call_stacks.get(&thread::current().id())?.last()?;
+            Some(SpawnLink {
+                parent: top.clone(),
+                file,
+                line,
+            })
+        }
+
+        /// Seed the current (spawned) thread's call stack from a link
+        /// captured on the spawning thread
+        ///
+        /// Pushes a `<parent>::spawn` node that is attached as a child of
+        /// the spawning call's node, so work recorded on the child thread
+        /// ends up inside the spawner's call tree instead of forming a
+        /// disconnected root.
+        pub fn span_spawned(link: Option<SpawnLink>) -> TraceGuard {
+            let Some(link) = link else {
+                return TraceGuard::inactive();
+            };
             let _ = init();
-            
-            tracing::info!(
-                target: "rustforger_trace",
-                "Entering function: {} at {}:{}",
-                fn_name, file, line
-            );
-            
+
             if let Ok(mut state) = TRACER.lock() {
-                let thread_id = thread::current().id();
-                let stack = state.call_stacks.entry(thread_id).or_default();
-                
+                let mut tags = serde_json::Map::new();
+                tags.insert(
+                    "spawned_from_call_id".to_string(),
+                    Value::from(link.parent.call_id),
+                );
+                let call_id = next_call_id();
                 let node = Arc::new(CallNode {
-                    name: fn_name.to_string(),
-                    file: file.to_string(),
-                    line,
+                    call_id,
+                    name: format!("{}::spawn", link.parent.name),
+                    module_path: None,
+                    file: link.file.to_string(),
+                    line: link.line,
+                    column: None,
+                    call_site_id: call_site_hash(&link.parent.name, link.file, link.line, 0),
+                    span_id: next_span_id(call_id),
+                    backtrace: None,
+                    args: None,
+                    events: Mutex::new(Vec::new()),
+                    tags: Mutex::new(tags),
                     children: Mutex::new(Vec::new()),
                 });
-                
-                if let Some(parent) = stack.last() {
-                    if let Ok(mut children) = parent.children.lock() {
-                        children.push(node.clone());
+
+                if let Ok(mut children) = link.parent.children.lock() {
+                    children.push(node.clone());
+                }
+
+                let stack = state.call_stacks.entry(thread::current().id()).or_default();
+                stack.push(node.clone());
+                let depth = stack.len();
+                state.summary.max_depth = state.summary.max_depth.max(depth);
+                state.summary.per_function.entry(node.name.clone()).or_default().calls += 1;
+                state.call_started.insert(node.call_id, Instant::now());
+                TraceGuard { active: true }
+            } else {
+                TraceGuard::inactive()
+            }
+        }
+
+        /// Whether a call with this name is already open on the current
+        /// thread's stack
+        ///
+        /// Backs the macro's `once_per_stack` option: recursive functions
+        /// use it to record only their outermost invocation.
+        pub fn on_current_stack(fn_name: &str) -> bool {
+            TRACER
+                .lock()
+                .map(|state| {
+                    state
+                        .call_stacks
+                        .get(&thread::current().id())
+                        .is_some_and(|stack| stack.iter().any(|node| node.name == fn_name))
+                })
+                .unwrap_or(false)
+        }
+
+        /// Depth of the current thread's active call stack
+        ///
+        /// Propagate-generated wrappers consult this to enforce their
+        /// `max_depth` bound before opening a child span.
+        pub fn current_depth() -> usize {
+            TRACER
+                .lock()
+                .map(|state| {
+                    state
+                        .call_stacks
+                        .get(&thread::current().id())
+                        .map(|stack| stack.len())
+                        .unwrap_or(0)
+                })
+                .unwrap_or(0)
+        }
+
+        /// RAII guard for a manually traced code region
+        ///
+        /// Creates a named child node in the current call tree without the
+        /// attribute macro, so arbitrary code regions (not just whole
+        /// functions) can be instrumented by hand:
+        ///
+        /// ```
+        /// use trace_runtime::tracer::interface::TraceScope;
+        ///
+        /// let _scope = TraceScope::new("load_config");
+        /// // ... region of interest ...
+        /// ```
+        ///
+        /// The [`crate::trace_scope!`] macro is a shorthand for this.
+        #[must_use = "dropping the scope immediately exits it"]
+        pub struct TraceScope {
+            _guard: TraceGuard,
+        }
+
+        impl TraceScope {
+            /// Open a named scope at the caller's source location
+            #[track_caller]
+            pub fn new(name: &str) -> Self {
+                let location = std::panic::Location::caller();
+                Self {
+                    _guard: span_dynamic(name, location.file(), location.line()),
+                }
+            }
+        }
+
+        /// Get the unique ID of the call currently at the top of this
+        /// thread's trace stack
+        ///
+        /// Returns `None` when called outside any traced function. Useful for
+        /// tagging application logs/artifacts so they can be cross-referenced
+        /// with the rustforger trace.
+        pub fn current_call_id() -> Option<u64> {
+            let state = TRACER.lock().ok()?;
+            let thread_id = thread::current().id();
+            state.call_stacks.get(&thread_id)?.last().map(|node| node.call_id)
+        }
+
+        /// Record an ad-hoc checkpoint event on the current call node
+        ///
+        /// Lets traced code capture intermediate state mid-function, not just
+        /// inputs and outputs. A no-op outside any traced call. The
+        /// [`crate::trace_event!`] macro is a shorthand for this.
+        pub fn event(name: &str, data: Value) {
+            tracing::info!(
+                target: "rustforger_trace",
+                "Recording checkpoint event: {}",
+                name
+            );
+
+            if let Ok(state) = TRACER.lock() {
+                let thread_id = thread::current().id();
+                let current_node = state
+                    .call_stacks
+                    .get(&thread_id)
+                    .and_then(|stack| stack.last());
+
+                if let Some(node) = current_node {
+                    if let Ok(mut events) = node.events.lock() {
+                        events.push(CheckpointEvent {
+                            timestamp_utc: timestamp_now(),
+                            name: name.to_string(),
+                            data,
+                        });
+                    }
+                }
+            }
+        }
+
+        /// Attach key/value metadata to the current call node
+        ///
+        /// Tags are serialized on the node itself (e.g. a request or user
+        /// ID), making it easy to correlate a trace with external logs.
+        /// Re-tagging an existing key overwrites its value; outside any
+        /// traced call the tag is dropped with a diagnostic.
+        pub fn tag(key: &str, value: Value) {
+            if let Ok(state) = TRACER.lock() {
+                let thread_id = thread::current().id();
+                let current_node = state
+                    .call_stacks
+                    .get(&thread_id)
+                    .and_then(|stack| stack.last());
+
+                if let Some(node) = current_node {
+                    if let Ok(mut tags) = node.tags.lock() {
+                        tags.insert(key.to_string(), value);
                     }
+                } else {
+                    tracing::warn!(
+                        target: "rustforger_trace",
+                        "tag '{}' dropped: no active traced call on this thread",
+                        key
+                    );
                 }
-                
-                stack.push(node);
             }
         }
 
         /// Exit the current function call
         pub fn exit() {
             tracing::info!(target: "rustforger_trace", "Exiting function");
+
+            #[cfg(feature = "log_bridge")]
+            log_bridge_emit(|config| config.exit_level, || "exit".to_string());
             
             if let Ok(mut state) = TRACER.lock() {
                 let thread_id = thread::current().id();
-                if let Some(stack) = state.call_stacks.get_mut(&thread_id) {
-                    stack.pop();
+                let node = state
+                    .call_stacks
+                    .get_mut(&thread_id)
+                    .and_then(|stack| stack.pop());
+                if let Some(node) = node {
+                    if let Some(started) = state.call_started.remove(&node.call_id) {
+                        let elapsed = started.elapsed().as_micros().min(u64::MAX as u128) as u64;
+                        state
+                            .summary
+                            .per_function
+                            .entry(node.name.clone())
+                            .or_default()
+                            .cumulative_micros += elapsed;
+                    }
                 }
             }
         }
 
         pub fn record_function_call(inputs: Value, output: Value) {
+            record_function_call_with_duration(inputs, output, None);
+        }
+
+        pub fn record_function_call_with_duration(
+            mut inputs: Value,
+            mut output: Value,
+            duration: Option<Duration>,
+        ) {
+            apply_redaction(&mut inputs);
+            apply_redaction(&mut output);
+
             tracing::info!(
                 target: "rustforger_trace",
                 "Recording function call with inputs: {:?}, output: {:?}",
                 inputs, output
             );
+
+            #[cfg(feature = "log_bridge")]
+            log_bridge_emit(|config| config.record_level, || {
+                format!("record call inputs={} output={}", inputs, output)
+            });
             
             if let Ok(mut state) = TRACER.lock() {
+                if state.enforce_event_cap() {
+                    return;
+                }
+
                 let thread_id = thread::current().id();
 
                 let should_record = if let Some(stack) = state.call_stacks.get(&thread_id) {
@@ -473,20 +1906,59 @@ pub mod tracer {
                     };
 
                     if let Some(current_node) = current_node_option {
+                        let (task_id, worker_name) = current_task_context();
                         let call_data = CallData {
-                            timestamp_utc: chrono::Utc::now().to_rfc3339(),
-                            thread_id: format!("{:?}", thread_id),
+                            timestamp_utc: timestamp_now(),
+                            thread_id: state.thread_label(thread_id),
+                            trace_id: current_trace_id(),
+                            task_id,
+                            worker_name,
                             root_node: current_node,
                             inputs,
                             output,
+                            duration_ns: duration.map(|d| {
+                                d.as_nanos().min(u64::MAX as u128) as u64
+                            }),
                         };
 
+                        if let Ok(callback) = EVENT_CALLBACK.lock() {
+                            if let Some(callback) = callback.as_ref() {
+                                callback(&call_data);
+                            }
+                        }
+
+                        state.summary.total_events += 1;
                         match &state.output_mode {
                             OutputMode::Memory => {
+                                if state.memory_cap.as_ref().is_some_and(|cap| cap.max_bytes.is_some()) {
+                                    let size = serde_json::to_string(&call_data)
+                                        .map(|json| json.len() as u64)
+                                        .unwrap_or(0);
+                                    state.memory_bytes += size;
+                                }
                                 state.results.push(call_data);
+                                if state.enforce_memory_cap().is_err() {
+                                    // Keep buffering in memory if spilling fails
+                                    state.memory_cap = None;
+                                }
                             },
                             OutputMode::Stream { .. } => {
-                                if state.write_stream_event(&call_data).is_err() {
+                                if let Some(tx) = &state.background_tx {
+                                    // Serialization and IO happen on the
+                                    // writer thread; count drops when the
+                                    // bounded channel is full
+                                    match tx.try_send(call_data) {
+                                        Ok(()) => {}
+                                        Err(std::sync::mpsc::TrySendError::Full(_)) => {
+                                            state.summary.dropped_events += 1;
+                                        }
+                                        Err(std::sync::mpsc::TrySendError::Disconnected(call_data)) => {
+                                            if state.write_stream_event(&call_data).is_err() {
+                                                state.results.push(call_data);
+                                            }
+                                        }
+                                    }
+                                } else if state.write_stream_event(&call_data).is_err() {
                                     // Fallback to memory on stream error
                                     state.results.push(call_data);
                                 }
@@ -502,11 +1974,31 @@ pub mod tracer {
             record_function_call(inputs, output);
         }
 
+        /// Record a complete top-level function call with its measured
+        /// wall time; the macro expansion takes `Instant::now()` at entry
+        /// and passes the elapsed time here
+        pub fn record_top_level_call_with_duration(
+            inputs: Value,
+            output: Value,
+            duration: Duration,
+        ) {
+            record_function_call_with_duration(inputs, output, Some(duration));
+        }
+
         /// Enable auto-save with robust configuration
         pub fn enable_auto_save(config: AutoSaveConfig) -> Result<(), TraceError> {
+            if config.retention.is_active() {
+                apply_retention_policy(&config.path, &config.retention);
+            }
+
             {
                 let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
-                state.set_output_mode(OutputMode::Stream { path: config.path.clone() })?;
+                state.set_output_mode(OutputMode::Stream {
+                    path: expand_pid_placeholder(&config.path),
+                    flush_policy: config.flush_policy.clone(),
+                    append: config.append,
+                    framing: config.framing.clone(),
+                })?;
             }
 
             if config.enable_panic_hook {
@@ -530,6 +2022,79 @@ pub mod tracer {
             Ok(())
         }
 
+        /// Prune old trace files in the output directory per the retention
+        /// policy; pruning failures are logged and never abort initialization
+        fn apply_retention_policy(output_path: &Path, policy: &RetentionPolicy) {
+            let Some(dir) = output_path.parent() else {
+                return;
+            };
+
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                return;
+            };
+
+            // Collect existing trace files with size and modification time,
+            // excluding the file we are about to write to
+            let mut files: Vec<(PathBuf, u64, std::time::SystemTime)> = entries
+                .flatten()
+                .filter(|entry| {
+                    let path = entry.path();
+                    path != output_path
+                        && path.extension().is_some_and(|ext| ext == "json")
+                })
+                .filter_map(|entry| {
+                    let metadata = entry.metadata().ok()?;
+                    let modified = metadata.modified().ok()?;
+                    Some((entry.path(), metadata.len(), modified))
+                })
+                .collect();
+
+            // Oldest first so size-based pruning removes stale files first
+            files.sort_by_key(|(_, _, modified)| *modified);
+
+            let mut to_remove: Vec<PathBuf> = Vec::new();
+
+            if let Some(max_age) = policy.max_age {
+                let now = std::time::SystemTime::now();
+                files.retain(|(path, _, modified)| {
+                    let expired = now
+                        .duration_since(*modified)
+                        .map(|age| age > max_age)
+                        .unwrap_or(false);
+                    if expired {
+                        to_remove.push(path.clone());
+                    }
+                    !expired
+                });
+            }
+
+            if let Some(max_total_bytes) = policy.max_total_bytes {
+                let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+                for (path, size, _) in &files {
+                    if total <= max_total_bytes {
+                        break;
+                    }
+                    to_remove.push(path.clone());
+                    total -= size;
+                }
+            }
+
+            for path in to_remove {
+                match std::fs::remove_file(&path) {
+                    Ok(()) => tracing::info!(
+                        target: "rustforger_trace",
+                        "Retention policy pruned old trace file: {}",
+                        path.display()
+                    ),
+                    Err(e) => tracing::warn!(
+                        target: "rustforger_trace",
+                        "Retention policy failed to remove {}: {}",
+                        path.display(), e
+                    ),
+                }
+            }
+        }
+
         /// Emergency save for panic/exit situations
         fn emergency_save() -> Result<(), TraceError> {
             if let Ok(mut state) = TRACER.try_lock() {
@@ -539,18 +2104,302 @@ pub mod tracer {
             }
         }
 
+        /// Propagate trace configuration to child processes
+        ///
+        /// Sets [`TRACE_OUTPUT_ENV`](super::TRACE_OUTPUT_ENV) in this
+        /// process's environment, which spawned children inherit. Include a
+        /// `%p` placeholder in the template so each child expands it to its
+        /// own PID and writes a separate correlated trace file instead of
+        /// clobbering the parent's output.
+        pub fn propagate_to_children(output_template: impl Into<PathBuf>) {
+            let template: PathBuf = output_template.into();
+            std::env::set_var(super::TRACE_OUTPUT_ENV, template.as_os_str());
+        }
+
+        /// Move event serialization and file IO to a dedicated writer thread
+        ///
+        /// `record_function_call` then only pushes events onto a bounded
+        /// channel of the given capacity; when the channel is full the event
+        /// is dropped and counted in the session summary instead of blocking
+        /// the traced code. Only affects streaming output.
+        pub fn enable_background_writer(capacity: usize) -> Result<(), TraceError> {
+            let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+            if state.background_tx.is_some() {
+                return Ok(());
+            }
+
+            let (tx, rx) = std::sync::mpsc::sync_channel::<CallData>(capacity.max(1));
+            let handle = thread::spawn(move || {
+                while let Ok(call_data) = rx.recv() {
+                    if let Ok(mut state) = TRACER.lock() {
+                        if state.write_stream_event(&call_data).is_err() {
+                            state.results.push(call_data);
+                        }
+                    }
+                }
+            });
+
+            state.background_tx = Some(tx);
+            state.background_writer = Some(handle);
+            Ok(())
+        }
+
+        /// Stop the background writer (if any) and wait until every queued
+        /// event has been written
+        fn drain_background_writer() {
+            let (tx, handle) = match TRACER.lock() {
+                Ok(mut state) => (state.background_tx.take(), state.background_writer.take()),
+                Err(_) => return,
+            };
+            // Dropping the sender lets the writer thread drain the channel
+            // and exit; join outside the lock since the thread needs it
+            drop(tx);
+            if let Some(handle) = handle {
+                let _ = handle.join();
+            }
+        }
+
+        /// Switch a function's tracing off mid-run
+        ///
+        /// `fn_name` must match the name recorded in traces (the bare
+        /// function name for macro-instrumented code). Subsequent spans for
+        /// it become inactive no-ops until [`enable_function`] is called.
+        pub fn disable_function(fn_name: &str) -> Result<(), TraceError> {
+            let mut set = DISABLED_FUNCTIONS.write().map_err(|_| TraceError::LockPoisoned)?;
+            set.insert(fn_name.to_string());
+            DISABLED_GENERATION.fetch_add(1, std::sync::atomic::Ordering::Release);
+            Ok(())
+        }
+
+        /// Re-enable tracing for a function disabled via [`disable_function`]
+        pub fn enable_function(fn_name: &str) -> Result<(), TraceError> {
+            let mut set = DISABLED_FUNCTIONS.write().map_err(|_| TraceError::LockPoisoned)?;
+            set.remove(fn_name);
+            DISABLED_GENERATION.fetch_add(1, std::sync::atomic::Ordering::Release);
+            Ok(())
+        }
+
+        /// Severity a generated span carries, set via the macro's `level`
+        /// attribute argument (`info` when unspecified)
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        pub enum TraceLevel {
+            Trace = 0,
+            Debug = 1,
+            Info = 2,
+            Warn = 3,
+            Error = 4,
+        }
+
+        /// Drop spans below this severity
+        ///
+        /// The default (`Trace`) records everything; raising the bound to
+        /// e.g. `Warn` turns spans from functions marked `level = "debug"`
+        /// or `level = "info"` into inactive no-ops.
+        pub fn set_min_level(level: TraceLevel) {
+            MIN_LEVEL.store(level as u8, std::sync::atomic::Ordering::Release);
+        }
+
+        /// The currently configured minimum severity
+        pub fn min_level() -> TraceLevel {
+            match MIN_LEVEL.load(std::sync::atomic::Ordering::Acquire) {
+                0 => TraceLevel::Trace,
+                1 => TraceLevel::Debug,
+                3 => TraceLevel::Warn,
+                4 => TraceLevel::Error,
+                _ => TraceLevel::Info,
+            }
+        }
+
+        /// Cheap prologue check used by span creation alongside
+        /// [`function_enabled`]; a single atomic load
+        pub fn level_enabled(level: TraceLevel) -> bool {
+            level as u8 >= MIN_LEVEL.load(std::sync::atomic::Ordering::Acquire)
+        }
+
+        /// Redact recorded inputs and outputs with these rules before they
+        /// reach any sink; see [`trace_common::redact::RedactionRules`]
+        pub fn set_redaction_rules(rules: trace_common::redact::RedactionRules) {
+            if let Ok(mut slot) = REDACTION_RULES.lock() {
+                *slot = Some(rules);
+            }
+        }
+
+        /// Record values verbatim again
+        pub fn clear_redaction_rules() {
+            if let Ok(mut slot) = REDACTION_RULES.lock() {
+                *slot = None;
+            }
+        }
+
+        /// The trace ID stamped on every record this process emits,
+        /// generating one on first use; pass it to child processes (and
+        /// call [`set_trace_id`] there) to join their traces into one
+        pub fn trace_id() -> String {
+            super::current_trace_id()
+        }
+
+        /// Adopt a trace ID instead of generating one, typically one
+        /// received from a parent process or an OpenTelemetry context
+        pub fn set_trace_id(trace_id: impl Into<String>) {
+            if let Ok(mut slot) = super::TRACE_ID.lock() {
+                *slot = Some(trace_id.into());
+            }
+        }
+
+        /// Cheap prologue check used by span creation; a single atomic load
+        /// on the common path where nothing was ever disabled
+        pub fn function_enabled(fn_name: &str) -> bool {
+            if DISABLED_GENERATION.load(std::sync::atomic::Ordering::Acquire) == 0 {
+                return true;
+            }
+            DISABLED_FUNCTIONS
+                .read()
+                .map(|set| !set.contains(fn_name))
+                .unwrap_or(true)
+        }
+
+        /// Register a callback invoked with every completed call record
+        ///
+        /// Runs alongside the configured [`OutputMode`], so it can be used as
+        /// the sole sink on targets without a filesystem (e.g. shipping
+        /// events to JS from `wasm32-unknown-unknown`) or as a tap next to
+        /// normal file output.
+        pub fn set_event_callback(callback: fn(&CallData)) -> Result<(), TraceError> {
+            let mut slot = EVENT_CALLBACK.lock().map_err(|_| TraceError::LockPoisoned)?;
+            *slot = Some(callback);
+            Ok(())
+        }
+
+        /// Remove a previously registered event callback
+        pub fn clear_event_callback() -> Result<(), TraceError> {
+            let mut slot = EVENT_CALLBACK.lock().map_err(|_| TraceError::LockPoisoned)?;
+            *slot = None;
+            Ok(())
+        }
+
+        /// Mirror tracer enter/exit/record activity through the `log` facade
+        ///
+        /// Lets projects standardized on `log` + `env_logger` see tracer
+        /// activity in their normal logs without adopting `tracing`. Levels
+        /// are configurable per event kind via [`LogBridgeConfig`].
+        #[cfg(feature = "log_bridge")]
+        pub fn enable_log_bridge(config: LogBridgeConfig) -> Result<(), TraceError> {
+            let mut slot = LOG_BRIDGE.lock().map_err(|_| TraceError::LockPoisoned)?;
+            *slot = Some(config);
+            Ok(())
+        }
+
+        /// Stop mirroring tracer activity through the `log` facade
+        #[cfg(feature = "log_bridge")]
+        pub fn disable_log_bridge() -> Result<(), TraceError> {
+            let mut slot = LOG_BRIDGE.lock().map_err(|_| TraceError::LockPoisoned)?;
+            *slot = None;
+            Ok(())
+        }
+
+        /// Register a resolver that decides where auto-save output lands
+        ///
+        /// Consulted by [`AutoSaveConfig::default_path`] after the
+        /// [`TRACE_OUTPUT_ENV`](super::TRACE_OUTPUT_ENV) environment
+        /// variable but before the platform directory heuristics, so end
+        /// users can still override an embedder's choice.
+        pub fn set_default_path_resolver<F>(resolver: F) -> Result<(), TraceError>
+        where
+            F: Fn() -> PathBuf + Send + Sync + 'static,
+        {
+            let mut slot = DEFAULT_PATH_RESOLVER.lock().map_err(|_| TraceError::LockPoisoned)?;
+            *slot = Some(Box::new(resolver));
+            Ok(())
+        }
+
+        /// Remove a previously registered default-path resolver
+        pub fn clear_default_path_resolver() -> Result<(), TraceError> {
+            let mut slot = DEFAULT_PATH_RESOLVER.lock().map_err(|_| TraceError::LockPoisoned)?;
+            *slot = None;
+            Ok(())
+        }
+
+        /// Stop recording after this many events in total
+        ///
+        /// When the limit is reached the tracer writes a capped-notification
+        /// marker, finalizes any open stream and ignores further events, so
+        /// runaway propagation loops cannot fill the disk. `None` removes
+        /// the limit and resumes recording.
+        pub fn set_max_total_events(limit: Option<u64>) -> Result<(), TraceError> {
+            let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+            state.max_total_events = limit;
+            state.capped = false;
+            Ok(())
+        }
+
+        /// Cap in-memory buffering, spilling older events to a temp file
+        ///
+        /// Protects against OOM in [`OutputMode::Memory`] without forcing a
+        /// switch to streaming; the spilled events are stitched back in at
+        /// [`finalize`].
+        pub fn set_memory_cap(cap: MemoryCap) -> Result<(), TraceError> {
+            let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+            state.memory_cap = if cap.is_unbounded() { None } else { Some(cap) };
+            Ok(())
+        }
+
         /// Finalize and write trace data to specified path
         pub fn finalize(output_path: &Path) -> Result<(), TraceError> {
+            drain_background_writer();
             let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
             state.finalize_to_path(output_path)
         }
 
+        /// Finalize to the active stream path, or to the auto-save default
+        /// path when tracing ran in memory
+        ///
+        /// The macro's `fn main` wrapper calls this before returning, so
+        /// trace output reaches disk without CLI-driven source edits.
+        pub fn finalize_default() -> Result<(), TraceError> {
+            drain_background_writer();
+            let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+            let path = match &state.output_mode {
+                OutputMode::Stream { path, .. } => path.clone(),
+                _ => expand_pid_placeholder(&AutoSaveConfig::default_path()),
+            };
+            state.finalize_to_path(&path)
+        }
+
+        /// Finalize like [`finalize`], and additionally write a
+        /// `trace_summary.json` next to the trace file with per-function call
+        /// counts, cumulative time, max call depth, and dropped-event counts
+        pub fn finalize_with_summary(output_path: &Path) -> Result<(), TraceError> {
+            drain_background_writer();
+            let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+            state.finalize_to_path(output_path)?;
+            state.write_summary(output_path)
+        }
+
         /// Get current tracing statistics
-        pub fn get_stats() -> Result<(usize, usize), TraceError> {
-            let state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
-            let total_events = state.results.len();
-            let active_threads = state.call_stacks.len();
-            Ok((total_events, active_threads))
+        pub fn get_stats() -> Result<TracerStats, TraceError> {
+            let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+
+            let depths: Vec<(thread::ThreadId, usize)> = state
+                .call_stacks
+                .iter()
+                .filter(|(_, stack)| !stack.is_empty())
+                .map(|(thread_id, stack)| (*thread_id, stack.len()))
+                .collect();
+            let mut depth_per_thread = std::collections::BTreeMap::new();
+            for (thread_id, depth) in depths {
+                depth_per_thread.insert(state.thread_label(thread_id), depth);
+            }
+
+            Ok(TracerStats {
+                events_recorded: state.summary.total_events,
+                events_dropped_overflow: state.summary.dropped_events,
+                events_skipped_disabled: SKIPPED_DISABLED
+                    .load(std::sync::atomic::Ordering::Relaxed),
+                buffered_events: state.results.len(),
+                bytes_written: state.bytes_written,
+                active_threads: depth_per_thread.len(),
+                depth_per_thread,
+            })
         }
 
         /// Clear all trace data (useful for testing)
@@ -558,14 +2407,17 @@ pub mod tracer {
             let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
             
             if let Some(mut writer) = state.stream_writer.take() {
-                let _ = writeln!(writer, "]");
+                if !state.stream_is_append() {
+                    let _ = writeln!(writer, "]");
+                }
                 let _ = writer.flush();
             }
             
             state.results.clear();
             state.call_stacks.clear();
             state.output_mode = OutputMode::Memory;
-            state.stream_event_count = 0; 
+            state.stream_event_count = 0;
+            state.bytes_written = 0;
             
             Ok(())
         }
@@ -598,4 +2450,458 @@ pub mod tracer {
             });
         }
     }
-}
\ No newline at end of file
+
+    /// Swap a fresh in-memory state into the global tracer, returning the
+    /// previous one; used by [`crate::test_support`]
+    pub(crate) fn swap_in_fresh_state() -> Result<TracerState, TraceError> {
+        let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+        Ok(std::mem::replace(&mut *state, TracerState::new()))
+    }
+
+    /// Restore a tracer state previously taken by [`swap_in_fresh_state`]
+    pub(crate) fn restore_state(saved: TracerState) {
+        if let Ok(mut state) = TRACER.lock() {
+            *state = saved;
+        }
+    }
+
+    /// Clone the active output mode; used by [`crate::bench`] to replicate
+    /// the current configuration against a scratch sink
+    pub(crate) fn clone_output_mode() -> Result<OutputMode, TraceError> {
+        let state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+        Ok(state.output_mode.clone())
+    }
+
+    /// Apply an output mode directly, without the auto-save hook machinery
+    pub(crate) fn set_output_mode_direct(mode: OutputMode) -> Result<(), TraceError> {
+        let mut state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+        state.set_output_mode(mode)
+    }
+
+    /// Serialize the currently buffered call records; used by
+    /// [`crate::test_support`] so matchers see the full call trees
+    pub(crate) fn snapshot_results() -> Result<Vec<serde_json::Value>, TraceError> {
+        let state = TRACER.lock().map_err(|_| TraceError::LockPoisoned)?;
+        let mut snapshot = Vec::with_capacity(state.results.len());
+        for call_data in &state.results {
+            snapshot.push(serde_json::to_value(call_data)?);
+        }
+        Ok(snapshot)
+    }
+}
+// --- future module ---
+pub mod future {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// Future wrapper created by [`trace_future`]
+    ///
+    /// Enters the named span each time the inner future is polled and exits
+    /// it when the poll returns, so work done by child awaits is attributed
+    /// to the awaiting task rather than to whatever thread happens to poll it.
+    pub struct TraceFuture<F> {
+        name: String,
+        inner: F,
+    }
+
+    impl<F: Future> Future for TraceFuture<F> {
+        type Output = F::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            // Safety: we never move `inner` out of `this`; the projection
+            // below re-pins it immediately.
+            let this = unsafe { self.get_unchecked_mut() };
+            let _guard = crate::tracer::interface::span_dynamic(&this.name, file!(), line!());
+            // Safety: `inner` is structurally pinned (see above).
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+            inner.poll(cx)
+        }
+    }
+
+    /// Wrap a future so every poll runs inside a traced span
+    ///
+    /// The plain enter/exit pair around an `async` body only covers the code
+    /// up to the first await; this wrapper keeps the whole future attributed
+    /// to `name` no matter which thread polls it.
+    pub fn trace_future<F: Future>(name: impl Into<String>, fut: F) -> TraceFuture<F> {
+        TraceFuture {
+            name: name.into(),
+            inner: fut,
+        }
+    }
+}
+
+pub use future::{trace_future, TraceFuture};
+
+pub mod test_support {
+    //! Test harness for asserting on traced calls
+    //!
+    //! The tracer is a process-wide singleton, so parallel `cargo test` runs
+    //! that poke it directly trample each other. [`CapturedTracer`] swaps a
+    //! fresh in-memory state into the tracer for the duration of one test,
+    //! serializes concurrent captures behind a harness lock, and restores
+    //! the previous state on drop:
+    //!
+    //! ```
+    //! use trace_runtime::test_support::CapturedTracer;
+    //! use trace_runtime::tracer::interface;
+    //!
+    //! let tracer = CapturedTracer::capture();
+    //! {
+    //!     let _span = interface::span_dynamic("traced_workload", file!(), line!());
+    //!     interface::record_top_level_call(serde_json::json!({}), serde_json::Value::Null);
+    //! }
+    //! tracer.assert_called("traced_workload");
+    //! ```
+
+    use std::sync::{Mutex, MutexGuard};
+
+    use serde_json::Value;
+
+    use crate::tracer::{restore_state, snapshot_results, swap_in_fresh_state, TracerState};
+
+    /// Serializes captures so concurrent tests cannot interleave their calls
+    static HARNESS_LOCK: Mutex<()> = Mutex::new(());
+
+    /// An exclusive, isolated tracing session for one test
+    pub struct CapturedTracer {
+        saved: Option<TracerState>,
+        _serialize: MutexGuard<'static, ()>,
+    }
+
+    impl CapturedTracer {
+        /// Begin an isolated capture; ambient tracer state is parked and
+        /// restored when the returned harness is dropped
+        pub fn capture() -> Self {
+            let guard = HARNESS_LOCK
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            // Fire the macro's one-time auto-save init against the ambient
+            // state, so it cannot flip the captured session into stream mode
+            // partway through the test
+            crate::tracer::interface::ensure_auto_save_initialized();
+            let saved = swap_in_fresh_state().ok();
+            Self {
+                saved,
+                _serialize: guard,
+            }
+        }
+
+        /// All call records captured so far, as serialized JSON values
+        pub fn calls(&self) -> Vec<Value> {
+            snapshot_results().unwrap_or_default()
+        }
+
+        /// Number of captured call nodes (at any depth) with this name
+        pub fn call_count(&self, fn_name: &str) -> usize {
+            self.calls()
+                .iter()
+                .filter_map(|record| record.get("root_node"))
+                .map(|root| count_named(root, fn_name))
+                .sum()
+        }
+
+        /// Panic unless a call node with this name was captured
+        #[track_caller]
+        pub fn assert_called(&self, fn_name: &str) {
+            if self.call_count(fn_name) == 0 {
+                panic!(
+                    "expected a traced call to '{}', captured: {:?}",
+                    fn_name,
+                    self.captured_names()
+                );
+            }
+        }
+
+        /// Panic unless a call node with this name was captured exactly
+        /// `expected` times
+        #[track_caller]
+        pub fn assert_call_count(&self, fn_name: &str, expected: usize) {
+            let actual = self.call_count(fn_name);
+            if actual != expected {
+                panic!(
+                    "expected {} traced calls to '{}', found {}",
+                    expected, fn_name, actual
+                );
+            }
+        }
+
+        /// Panic unless some captured call tree contains this root-to-
+        /// descendant chain of names (consecutive parent/child links)
+        #[track_caller]
+        pub fn assert_call_path(&self, path: &[&str]) {
+            let found = self
+                .calls()
+                .iter()
+                .filter_map(|record| record.get("root_node"))
+                .any(|root| matches_path(root, path));
+            if !found {
+                panic!(
+                    "expected call path {:?}, captured: {:?}",
+                    path,
+                    self.captured_names()
+                );
+            }
+        }
+
+        /// Flat list of every captured node name, for failure messages
+        fn captured_names(&self) -> Vec<String> {
+            let mut names = Vec::new();
+            for record in self.calls() {
+                if let Some(root) = record.get("root_node") {
+                    collect_names(root, &mut names);
+                }
+            }
+            names
+        }
+    }
+
+    impl Drop for CapturedTracer {
+        fn drop(&mut self) {
+            if let Some(saved) = self.saved.take() {
+                restore_state(saved);
+            }
+        }
+    }
+
+    fn node_name(node: &Value) -> Option<&str> {
+        node.get("name").and_then(|name| name.as_str())
+    }
+
+    fn node_children(node: &Value) -> &[Value] {
+        node.get("children")
+            .and_then(|children| children.as_array())
+            .map(|children| children.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn count_named(node: &Value, fn_name: &str) -> usize {
+        let own = usize::from(node_name(node) == Some(fn_name));
+        own + node_children(node)
+            .iter()
+            .map(|child| count_named(child, fn_name))
+            .sum::<usize>()
+    }
+
+    fn collect_names(node: &Value, names: &mut Vec<String>) {
+        if let Some(name) = node_name(node) {
+            names.push(name.to_string());
+        }
+        for child in node_children(node) {
+            collect_names(child, names);
+        }
+    }
+
+    /// True when `path` matches a chain starting at `node` or anywhere below
+    fn matches_path(node: &Value, path: &[&str]) -> bool {
+        fn chain_from(node: &Value, path: &[&str]) -> bool {
+            match path {
+                [] => true,
+                [head, rest @ ..] => {
+                    node_name(node) == Some(*head)
+                        && (rest.is_empty()
+                            || node_children(node).iter().any(|child| chain_from(child, rest)))
+                }
+            }
+        }
+        if path.is_empty() {
+            return true;
+        }
+        chain_from(node, path) || node_children(node).iter().any(|child| matches_path(child, path))
+    }
+}
+
+pub mod bench {
+    //! Built-in tracer overhead measurement
+    //!
+    //! Answers "what does tracing cost per call with my current
+    //! configuration?" so sampling and flush policies can be chosen from
+    //! numbers instead of guesses. Measurement runs against a scratch sink
+    //! configured like the live one, so no benchmark events leak into real
+    //! trace output.
+
+    use std::time::Instant;
+
+    use crate::tracer::{
+        clone_output_mode, interface, restore_state, set_output_mode_direct,
+        swap_in_fresh_state, OutputMode, TraceError,
+    };
+
+    /// Per-call tracer cost measured by [`measure_overhead`]
+    #[derive(Debug, Clone, serde::Serialize)]
+    pub struct OverheadReport {
+        /// Calls measured per phase
+        pub iterations: u64,
+        /// Cost of span enter + exit alone, per call
+        pub enter_exit_ns: u64,
+        /// Additional cost of serializing and routing the call record
+        /// (including IO for stream modes), per call
+        pub record_ns: u64,
+        /// Full cost of one traced call (enter, record, exit)
+        pub per_call_ns: u64,
+    }
+
+    /// Measure per-call tracer overhead for the current configuration
+    ///
+    /// The live tracer state is parked during measurement and restored
+    /// afterwards; stream configurations are replayed against a temp file
+    /// with the same flush policy and framing.
+    pub fn measure_overhead(iterations: u64) -> Result<OverheadReport, TraceError> {
+        let iterations = iterations.max(1);
+        let current_mode = clone_output_mode()?;
+        let saved = swap_in_fresh_state()?;
+
+        let scratch_path = std::env::temp_dir().join(format!(
+            "rustforger_bench_{}.json",
+            std::process::id()
+        ));
+        let bench_mode = match current_mode {
+            OutputMode::Memory => OutputMode::Memory,
+            OutputMode::Stream { flush_policy, append, framing, .. } => OutputMode::Stream {
+                path: scratch_path.clone(),
+                flush_policy,
+                append,
+                framing,
+            },
+        };
+
+        let result = (|| {
+            set_output_mode_direct(bench_mode)?;
+
+            // Warmup so lazy initialization does not land in the timings
+            for _ in 0..iterations.min(100) {
+                traced_call(0);
+            }
+
+            let started = Instant::now();
+            for _ in 0..iterations {
+                let guard = interface::span_dynamic("bench_enter_exit", file!(), line!());
+                drop(guard);
+            }
+            let enter_exit_ns = per_call_ns(started, iterations);
+
+            let started = Instant::now();
+            for i in 0..iterations {
+                traced_call(i);
+            }
+            let per_call = per_call_ns(started, iterations);
+
+            Ok(OverheadReport {
+                iterations,
+                enter_exit_ns,
+                record_ns: per_call.saturating_sub(enter_exit_ns),
+                per_call_ns: per_call,
+            })
+        })();
+
+        restore_state(saved);
+        let _ = std::fs::remove_file(&scratch_path);
+        result
+    }
+
+    /// One full traced call: enter, serialize + record, exit
+    fn traced_call(i: u64) {
+        let guard = interface::span_dynamic("bench_full_call", file!(), line!());
+        interface::record_top_level_call(
+            serde_json::json!({ "i": i }),
+            serde_json::Value::Null,
+        );
+        drop(guard);
+    }
+
+    fn per_call_ns(started: Instant, iterations: u64) -> u64 {
+        (started.elapsed().as_nanos() / iterations.max(1) as u128)
+            .min(u64::MAX as u128) as u64
+    }
+}
+
+/// Open a manually traced scope that ends when the returned guard is dropped
+///
+/// Shorthand for [`tracer::interface::TraceScope::new`]:
+///
+/// ```
+/// let _g = trace_runtime::trace_scope!("phase_name");
+/// ```
+#[macro_export]
+macro_rules! trace_scope {
+    ($name:expr) => {
+        $crate::tracer::interface::TraceScope::new($name)
+    };
+}
+
+/// Record an ad-hoc checkpoint event on the current call node
+///
+/// The optional payload takes the same syntax as [`serde_json::json!`]:
+///
+/// ```
+/// trace_runtime::trace_event!("retry", { "attempt": 2, "backoff_ms": 100 });
+/// trace_runtime::trace_event!("cache_miss");
+/// ```
+#[macro_export]
+macro_rules! trace_event {
+    ($name:expr) => {
+        $crate::tracer::interface::event($name, ::serde_json::Value::Null)
+    };
+    ($name:expr, $($data:tt)+) => {
+        $crate::tracer::interface::event($name, ::serde_json::json!($($data)+))
+    };
+}
+
+/// Trace an arbitrary block as if it were a named function
+///
+/// Enters a span named `$name`, evaluates the block, records one call and
+/// exits, then yields the block's value:
+///
+/// ```
+/// let x = trace_runtime::trace_block!("parse step", {
+///     2 + 2
+/// });
+/// assert_eq!(x, 4);
+/// ```
+///
+/// The block's value passes through unchanged. Inputs and outputs are
+/// recorded as empty/null since the macro cannot prove an arbitrary
+/// expression serializable; use [`trace_event!`] inside the block to attach
+/// payloads.
+#[macro_export]
+macro_rules! trace_block {
+    ($name:expr, $body:block) => {{
+        let __trace_guard = $crate::tracer::interface::span_dynamic($name, file!(), line!());
+        let __result = $body;
+        if __trace_guard.is_active() {
+            $crate::tracer::interface::record_top_level_call(
+                ::serde_json::Value::Object(::serde_json::Map::new()),
+                ::serde_json::Value::Null,
+            );
+        }
+        drop(__trace_guard);
+        __result
+    }};
+}
+
+/// Call a zero-argument closure inside a traced span
+///
+/// Like [`trace_block!`] but takes any callable, so an existing closure or
+/// function value can be traced without restating its body:
+///
+/// ```
+/// let parse = || 21 * 2;
+/// let x = trace_runtime::trace_closure!("parse", parse);
+/// assert_eq!(x, 42);
+/// ```
+#[macro_export]
+macro_rules! trace_closure {
+    ($name:expr, $closure:expr) => {{
+        let __trace_guard = $crate::tracer::interface::span_dynamic($name, file!(), line!());
+        let __result = ($closure)();
+        if __trace_guard.is_active() {
+            $crate::tracer::interface::record_top_level_call(
+                ::serde_json::Value::Object(::serde_json::Map::new()),
+                ::serde_json::Value::Null,
+            );
+        }
+        drop(__trace_guard);
+        __result
+    }};
+}
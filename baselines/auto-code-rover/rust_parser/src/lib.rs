@@ -245,6 +245,399 @@ fn parse_mod(
     (structs, functions, variables, macros, traits)
 }
 
+// 定义 RustFeatureGatedItem：记录被 cfg(feature) 限制的条目
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RustFeatureGatedItem {
+    name: String,
+    kind: String,
+    features: Vec<String>,
+    start_line: usize,
+    end_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RustFeatureMatrix {
+    items: Vec<RustFeatureGatedItem>,
+    all_features: Vec<String>,
+}
+
+// 解析 #[cfg(feature = "...")] 属性，返回每个条目所需的 feature 集合
+#[pyfunction]
+fn detect_feature_gates(code: &str) -> PyResult<String> {
+    match syn::parse_file(code) {
+        Ok(ast) => {
+            let mut items = Vec::new();
+            collect_feature_gates(&ast.items, &[], &mut items);
+
+            let mut all_features: Vec<String> = items
+                .iter()
+                .flat_map(|item| item.features.iter().cloned())
+                .collect();
+            all_features.sort();
+            all_features.dedup();
+
+            let result = RustFeatureMatrix { items, all_features };
+            Ok(serde_json::to_string(&result).unwrap())
+        }
+        Err(e) => Err(pyo3::exceptions::PySyntaxError::new_err(e.to_string())),
+    }
+}
+
+// 递归收集各条目的 feature 约束，inherited 为外层 mod 传下来的 feature
+fn collect_feature_gates(
+    items: &[Item],
+    inherited: &[String],
+    out: &mut Vec<RustFeatureGatedItem>,
+) {
+    for item in items {
+        let (name, kind, attrs) = match item {
+            Item::Fn(f) => (f.sig.ident.to_string(), "function", &f.attrs),
+            Item::Struct(s) => (s.ident.to_string(), "struct", &s.attrs),
+            Item::Enum(e) => (e.ident.to_string(), "enum", &e.attrs),
+            Item::Trait(t) => (t.ident.to_string(), "trait", &t.attrs),
+            Item::Impl(imp) => {
+                let impl_type = &imp.self_ty;
+                (quote::quote! { #impl_type }.to_string(), "impl", &imp.attrs)
+            }
+            Item::Mod(md) => {
+                let mut features = inherited.to_vec();
+                features.extend(cfg_features(&md.attrs));
+                features.sort();
+                features.dedup();
+                if !features.is_empty() {
+                    out.push(RustFeatureGatedItem {
+                        name: md.ident.to_string(),
+                        kind: "mod".to_string(),
+                        features: features.clone(),
+                        start_line: md.span().start().line,
+                        end_line: md.span().end().line,
+                    });
+                }
+                // mod 内的条目继承外层的 feature 约束
+                if let Some((_, ref nested_items)) = &md.content {
+                    collect_feature_gates(nested_items, &features, out);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        let mut features = inherited.to_vec();
+        features.extend(cfg_features(attrs));
+        features.sort();
+        features.dedup();
+        if !features.is_empty() {
+            out.push(RustFeatureGatedItem {
+                name,
+                kind: kind.to_string(),
+                features,
+                start_line: item.span().start().line,
+                end_line: item.span().end().line,
+            });
+        }
+    }
+}
+
+// 提取属性中 cfg 引用的全部 feature 名（含 any/all/not 嵌套）
+fn cfg_features(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut features = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("cfg") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| collect_cfg_meta(&meta, &mut features));
+    }
+    features
+}
+
+fn collect_cfg_meta(
+    meta: &syn::meta::ParseNestedMeta,
+    features: &mut Vec<String>,
+) -> syn::Result<()> {
+    if meta.path.is_ident("feature") {
+        let value = meta.value()?;
+        let lit: syn::LitStr = value.parse()?;
+        features.push(lit.value());
+    } else if meta.path.is_ident("any") || meta.path.is_ident("all") || meta.path.is_ident("not") {
+        meta.parse_nested_meta(|nested| collect_cfg_meta(&nested, features))?;
+    } else if meta.input.peek(syn::Token![=]) {
+        // 跳过 target_os = "..." 之类的其它 cfg 键值对
+        let value = meta.value()?;
+        let _: syn::LitStr = value.parse()?;
+    }
+    Ok(())
+}
+
+// 定义 RustConstructor：某个类型的构造入口
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RustConstructor {
+    name: String,
+    returns_self: bool,
+    start_line: usize,
+    end_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RustConstructorReport {
+    struct_name: String,
+    constructors: Vec<RustConstructor>,
+    has_default_impl: bool,
+    builders: Vec<String>,
+}
+
+// 检测给定 struct 的构造函数、Default 实现和 builder 类型
+#[pyfunction]
+fn detect_constructors(code: &str, struct_name: &str) -> PyResult<String> {
+    match syn::parse_file(code) {
+        Ok(ast) => {
+            let mut report = RustConstructorReport {
+                struct_name: struct_name.to_string(),
+                constructors: Vec::new(),
+                has_default_impl: false,
+                builders: Vec::new(),
+            };
+            collect_constructors(&ast.items, struct_name, &mut report);
+            report.builders.sort();
+            report.builders.dedup();
+            Ok(serde_json::to_string(&report).unwrap())
+        }
+        Err(e) => Err(pyo3::exceptions::PySyntaxError::new_err(e.to_string())),
+    }
+}
+
+fn collect_constructors(items: &[Item], struct_name: &str, report: &mut RustConstructorReport) {
+    for item in items {
+        match item {
+            Item::Struct(s) => {
+                let name = s.ident.to_string();
+                // #[derive(Default)] 也算 Default 实现
+                if name == struct_name && has_default_derive(&s.attrs) {
+                    report.has_default_impl = true;
+                }
+                // 命名约定：FooBuilder 视为 Foo 的 builder
+                if name == format!("{}Builder", struct_name) {
+                    report.builders.push(name);
+                }
+            }
+
+            Item::Impl(imp) => {
+                let impl_type = if let Type::Path(path) = imp.self_ty.as_ref() {
+                    path.path.segments.last().map(|seg| seg.ident.to_string())
+                } else {
+                    None
+                };
+                let impl_type = match impl_type {
+                    Some(name) => name,
+                    None => continue,
+                };
+
+                // Default impl
+                if impl_type == struct_name {
+                    if let Some((_, trait_path, _)) = &imp.trait_ {
+                        if trait_path
+                            .segments
+                            .last()
+                            .map(|seg| seg.ident == "Default")
+                            .unwrap_or(false)
+                        {
+                            report.has_default_impl = true;
+                        }
+                        continue;
+                    }
+                }
+
+                for impl_item in &imp.items {
+                    let method = match impl_item {
+                        ImplItem::Fn(method) => method,
+                        _ => continue,
+                    };
+                    // 构造函数：无 self 接收者且返回 Self / 目标类型
+                    let takes_self = method
+                        .sig
+                        .inputs
+                        .iter()
+                        .any(|arg| matches!(arg, FnArg::Receiver(_)));
+                    let returns_target = return_type_mentions(&method.sig.output, struct_name);
+
+                    if impl_type == struct_name && !takes_self && returns_target {
+                        report.constructors.push(RustConstructor {
+                            name: method.sig.ident.to_string(),
+                            returns_self: true,
+                            start_line: method.span().start().line,
+                            end_line: method.span().end().line,
+                        });
+                    }
+
+                    // 其它类型上返回目标类型的 build 方法 => builder
+                    if impl_type != struct_name
+                        && method.sig.ident == "build"
+                        && takes_self
+                        && returns_target
+                    {
+                        report.builders.push(impl_type.clone());
+                    }
+                }
+            }
+
+            Item::Mod(md) => {
+                if let Some((_, ref nested_items)) = &md.content {
+                    collect_constructors(nested_items, struct_name, report);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+// 返回类型中是否出现 Self 或目标类型名（含 Result<Foo, _> 等包装）
+fn return_type_mentions(output: &ReturnType, struct_name: &str) -> bool {
+    match output {
+        ReturnType::Default => false,
+        ReturnType::Type(_, ty) => {
+            let rendered = quote::quote! { #ty }.to_string();
+            rendered.split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|word| word == "Self" || word == struct_name)
+        }
+    }
+}
+
+fn has_default_derive(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        if !attr.path().is_ident("derive") {
+            return false;
+        }
+        let mut found = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("Default") {
+                found = true;
+            }
+            Ok(())
+        });
+        found
+    })
+}
+
+// 定义 RustImport：一条 use 引入及其使用情况
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RustImport {
+    // 完整路径，例如 std::collections::HashMap
+    path: String,
+    // 在本文件中绑定的名字（含 as 重命名）
+    name: String,
+    start_line: usize,
+    used: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RustImportReport {
+    imports: Vec<RustImport>,
+    // use 语句指向的模块路径（去掉最后一段），即文件间依赖边
+    edges: Vec<String>,
+    unused: Vec<String>,
+}
+
+// 解析 use 语句，返回模块依赖边并标记未被引用的导入
+#[pyfunction]
+fn analyze_imports(code: &str) -> PyResult<String> {
+    match syn::parse_file(code) {
+        Ok(ast) => {
+            let mut imports = Vec::new();
+            collect_imports(&ast.items, &mut imports);
+
+            // 把 use 之外的代码展开成 token 流，用于判断名字是否被引用
+            let mut body_tokens = String::new();
+            for item in &ast.items {
+                if !matches!(item, Item::Use(_)) {
+                    body_tokens.push_str(&item.to_token_stream().to_string());
+                    body_tokens.push(' ');
+                }
+            }
+            let words: std::collections::HashSet<&str> = body_tokens
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|w| !w.is_empty())
+                .collect();
+
+            for import in &mut imports {
+                // glob 导入无法静态判断，视为已使用
+                import.used = import.name == "*" || words.contains(import.name.as_str());
+            }
+
+            let mut edges: Vec<String> = imports
+                .iter()
+                .filter_map(|import| import.path.rsplit_once("::").map(|(module, _)| module.to_string()))
+                .collect();
+            edges.sort();
+            edges.dedup();
+
+            let unused: Vec<String> = imports
+                .iter()
+                .filter(|import| !import.used)
+                .map(|import| import.path.clone())
+                .collect();
+
+            let result = RustImportReport { imports, edges, unused };
+            Ok(serde_json::to_string(&result).unwrap())
+        }
+        Err(e) => Err(pyo3::exceptions::PySyntaxError::new_err(e.to_string())),
+    }
+}
+
+fn collect_imports(items: &[Item], out: &mut Vec<RustImport>) {
+    for item in items {
+        match item {
+            Item::Use(u) => {
+                let line = u.span().start().line;
+                flatten_use_tree(&u.tree, String::new(), line, out);
+            }
+            Item::Mod(md) => {
+                if let Some((_, ref nested_items)) = &md.content {
+                    collect_imports(nested_items, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// 展开 use 树（路径、分组、重命名、glob）为单条导入记录
+fn flatten_use_tree(tree: &syn::UseTree, prefix: String, line: usize, out: &mut Vec<RustImport>) {
+    match tree {
+        syn::UseTree::Path(path) => {
+            let prefix = if prefix.is_empty() {
+                path.ident.to_string()
+            } else {
+                format!("{}::{}", prefix, path.ident)
+            };
+            flatten_use_tree(&path.tree, prefix, line, out);
+        }
+        syn::UseTree::Name(name) => {
+            let ident = name.ident.to_string();
+            let path = if prefix.is_empty() { ident.clone() } else { format!("{}::{}", prefix, ident) };
+            out.push(RustImport { path, name: ident, start_line: line, used: false });
+        }
+        syn::UseTree::Rename(rename) => {
+            let ident = rename.ident.to_string();
+            let path = if prefix.is_empty() { ident } else { format!("{}::{}", prefix, rename.ident) };
+            out.push(RustImport {
+                path,
+                name: rename.rename.to_string(),
+                start_line: line,
+                used: false,
+            });
+        }
+        syn::UseTree::Glob(_) => {
+            let path = format!("{}::*", prefix);
+            out.push(RustImport { path, name: "*".to_string(), start_line: line, used: false });
+        }
+        syn::UseTree::Group(group) => {
+            for item in &group.items {
+                flatten_use_tree(item, prefix.clone(), line, out);
+            }
+        }
+    }
+}
+
 // 压缩 Rust 代码的函数
 #[pyfunction]
 fn compress_rust_code(code: &str) -> PyResult<String> {
@@ -475,5 +868,8 @@ fn compress_mod(items: &[Item], depth: usize) -> String {
 fn rust_parser(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parse_rust_code, m)?)?;
     m.add_function(wrap_pyfunction!(compress_rust_code, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_feature_gates, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_constructors, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_imports, m)?)?;
     Ok(())
 }
\ No newline at end of file